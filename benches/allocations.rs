@@ -0,0 +1,108 @@
+#![feature(test)]
+
+extern crate test;
+extern crate uniprot;
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::io::Cursor;
+use std::io::Write;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use test::Bencher;
+use uniprot::parser::SequentialParser;
+use uniprot::parser::UniprotDatabase;
+
+/// A global allocator counting the allocations made by the program.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Parse all the entries of `txt` with a sequential parser.
+fn parse<D: UniprotDatabase>(txt: &str) -> usize {
+    SequentialParser::<_, D>::new(Cursor::new(txt))
+        .map(|entry| entry.unwrap())
+        .count()
+}
+
+/// Read views over all the entries of `txt` with a `RefParser`.
+fn parse_refs(txt: &str) -> usize {
+    let mut parser = uniprot::uniprot::parse_refs(Cursor::new(txt));
+    let mut entries = 0;
+    while let Some(entry) = parser.next_entry() {
+        entry.unwrap();
+        entries += 1;
+    }
+    entries
+}
+
+/// Count the allocations made by `parse` on the given text.
+fn count_allocations(txt: &str, parse: fn(&str) -> usize) -> (usize, usize) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let entries = parse(txt);
+    (ALLOCATIONS.load(Ordering::Relaxed) - before, entries)
+}
+
+/// Benchmark a parser, and report the allocations per iteration.
+fn bench_allocations(b: &mut Bencher, path: &str, name: &str, parse: fn(&str) -> usize) {
+    let txt = std::fs::read_to_string(path).unwrap();
+
+    let (allocations, entries) = count_allocations(&txt, parse);
+    // write to the handle directly, since `eprintln!` output is captured
+    let _ = writeln!(
+        std::io::stderr(),
+        "{} ({}): {} allocations for {} entries ({} per entry)",
+        path,
+        name,
+        allocations,
+        entries,
+        allocations / entries.max(1),
+    );
+
+    b.iter(|| parse(&txt));
+    b.bytes = txt.len() as u64;
+}
+
+#[bench]
+fn bench_uniprot_allocations(b: &mut Bencher) {
+    let parse = parse::<uniprot::uniprot::UniProt>;
+    bench_allocations(b, "tests/uniprot.xml", "SequentialParser", parse);
+}
+
+#[bench]
+fn bench_uniprot_ref_allocations(b: &mut Bencher) {
+    bench_allocations(b, "tests/uniprot.xml", "RefParser", parse_refs);
+}
+
+#[bench]
+fn bench_uniref_allocations(b: &mut Bencher) {
+    let parse = parse::<uniprot::uniref::UniRef>;
+    bench_allocations(b, "tests/uniref50.xml", "SequentialParser", parse);
+}
+
+#[bench]
+fn bench_uniparc_allocations(b: &mut Bencher) {
+    let parse = parse::<uniprot::uniparc::UniParc>;
+    bench_allocations(b, "tests/uniparc.xml", "SequentialParser", parse);
+}
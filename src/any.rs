@@ -0,0 +1,129 @@
+//! Parsing of XML files from any of the supported databases.
+
+use std::io::BufRead;
+use std::io::Chain;
+use std::io::Cursor;
+use std::io::Read;
+
+use quick_xml::Error as XmlError;
+
+use crate::error::Error;
+use crate::parser::UniprotDatabase;
+use crate::uniparc::UniParc;
+use crate::uniprot::UniProt;
+use crate::uniref::UniRef;
+
+/// The reader of a file whose beginning was read to detect its database.
+pub type PeekedReader<B> = Chain<Cursor<Vec<u8>>, B>;
+
+/// The entries of an XML file from any of the supported databases.
+#[allow(clippy::large_enum_variant)] // created once per file, boxing is not worth it
+pub enum AnyEntries<B: BufRead + Send + 'static> {
+    /// Entries of a UniProtKB file, with a `uniprot` root element.
+    UniProt(crate::uniprot::Parser<PeekedReader<B>>),
+    /// Entries of a UniRef file, with a `UniRef` root element.
+    UniRef(crate::uniref::Parser<PeekedReader<B>>),
+    /// Entries of a UniParc file, with a `uniparc` root element.
+    UniParc(crate::uniparc::Parser<PeekedReader<B>>),
+}
+
+/// Parse an XML file from any database, detected from its root element.
+///
+/// The beginning of the file is read until the root element is found, and
+/// then given back to the parser of the matching database. An error is
+/// returned if the root element is not one of a supported database.
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniref50.xml").unwrap();
+/// match uniprot::parse_any(std::io::BufReader::new(f)).unwrap() {
+///     uniprot::AnyEntries::UniRef(entries) => assert_eq!(entries.count(), 59),
+///     _ => panic!("expected UniRef entries"),
+/// }
+/// ```
+pub fn parse_any<B: BufRead + Send + 'static>(mut reader: B) -> Result<AnyEntries<B>, Error> {
+    let eof = || Error::from(XmlError::UnexpectedEof(String::from("xml")));
+
+    let mut prefix = Vec::new();
+    let root = loop {
+        // skip everything until the beginning of a tag
+        reader.read_until(b'<', &mut prefix)?;
+        if !prefix.ends_with(b"<") {
+            return Err(eof());
+        }
+        let start = prefix.len() - 1;
+        if reader.read_until(b'>', &mut prefix)? == 0 {
+            return Err(eof());
+        }
+
+        // skip comments, which may contain `>` characters
+        if prefix[start..].starts_with(b"<!--") {
+            while !prefix.ends_with(b"-->") {
+                if reader.read_until(b'>', &mut prefix)? == 0 {
+                    return Err(eof());
+                }
+            }
+            continue;
+        }
+
+        // skip declarations and processing instructions
+        let tag = &prefix[start..];
+        if !tag.starts_with(b"<?") && !tag.starts_with(b"<!") {
+            let n = tag
+                .iter()
+                .position(|c| matches!(c, b' ' | b'\t' | b'\r' | b'\n' | b'>' | b'/'))
+                .unwrap_or(tag.len());
+            let name = &tag[1..n];
+            break name.rsplit(|&c| c == b':').next().unwrap_or(name).to_vec();
+        }
+    };
+
+    let reader = Cursor::new(prefix).chain(reader);
+    if UniProt::ROOTS.contains(&root.as_slice()) {
+        Ok(AnyEntries::UniProt(crate::uniprot::parse(reader)))
+    } else if UniRef::ROOTS.contains(&root.as_slice()) {
+        Ok(AnyEntries::UniRef(crate::uniref::parse(reader)))
+    } else if UniParc::ROOTS.contains(&root.as_slice()) {
+        Ok(AnyEntries::UniParc(crate::uniparc::parse(reader)))
+    } else {
+        let root = String::from_utf8_lossy(&root).into_owned();
+        Err(Error::UnexpectedRoot(root))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn open(path: &str) -> std::io::BufReader<std::fs::File> {
+        std::io::BufReader::new(std::fs::File::open(path).unwrap())
+    }
+
+    #[test]
+    fn detect_database() {
+        match parse_any(open("tests/uniprot.xml")).unwrap() {
+            AnyEntries::UniProt(entries) => assert_eq!(entries.count(), 250),
+            _ => panic!("expected UniProt entries"),
+        }
+        match parse_any(open("tests/uniref50.xml")).unwrap() {
+            AnyEntries::UniRef(entries) => assert_eq!(entries.count(), 59),
+            _ => panic!("expected UniRef entries"),
+        }
+        match parse_any(open("tests/uniparc.xml")).unwrap() {
+            AnyEntries::UniParc(entries) => assert_eq!(entries.count(), 64),
+            _ => panic!("expected UniParc entries"),
+        }
+    }
+
+    #[test]
+    fn unexpected_root() {
+        let txt = &b"<?xml version=\"1.0\"?>\n<!-- a <comment> -->\n<html><body/></html>"[..];
+        match parse_any(txt) {
+            Err(Error::UnexpectedRoot(root)) => assert_eq!(root, "html"),
+            Err(e) => panic!("unexpected error: {:?}", e),
+            Ok(_) => panic!("unexpected success"),
+        }
+        assert!(matches!(parse_any(&b""[..]), Err(Error::Xml(_))));
+    }
+}
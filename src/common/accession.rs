@@ -0,0 +1,151 @@
+use core::convert::TryFrom;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::ops::Deref;
+use core::str::FromStr;
+
+use crate::common::prelude::*;
+use crate::common::ShortString;
+use crate::error::InvalidValue;
+
+/// A validated UniProtKB accession number.
+///
+/// Accessions follow the format documented by UniProt, either with 6
+/// characters (e.g. `P12345`) or with 10 characters (e.g. `A0A023GPI8`),
+/// and may only contain uppercase letters and digits.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct Accession {
+    accession: ShortString,
+}
+
+impl Accession {
+    /// Get the accession as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.accession
+    }
+
+    /// Check whether the accession has the historical Swiss-Prot format.
+    ///
+    /// This is only a heuristic: 6-character accessions starting with `O`,
+    /// `P` or `Q` were originally assigned to Swiss-Prot entries, but they
+    /// may now belong to TrEMBL entries, and reviewed entries may also have
+    /// any other accession.
+    pub fn is_swissprot_style(&self) -> bool {
+        self.accession.len() == 6 && matches!(self.accession.as_bytes()[0], b'O'..=b'Q')
+    }
+
+    /// Check whether the given string is a well-formed accession.
+    ///
+    /// This implements the regular expression given in the UniProt
+    /// documentation: `[OPQ][0-9][A-Z0-9]{3}[0-9]` or
+    /// `[A-NR-Z][0-9]([A-Z][A-Z0-9]{2}[0-9]){1,2}`.
+    fn is_valid(s: &str) -> bool {
+        let b = s.as_bytes();
+        if b.len() != 6 && b.len() != 10 {
+            return false;
+        }
+        if !b[1].is_ascii_digit() {
+            return false;
+        }
+        match b[0] {
+            b'O' | b'P' | b'Q' if b.len() == 6 => {
+                b[2..5]
+                    .iter()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+                    && b[5].is_ascii_digit()
+            }
+            b'A'..=b'Z' if !matches!(b[0], b'O'..=b'Q') => b[2..].chunks(4).all(|block| {
+                block[0].is_ascii_uppercase()
+                    && block[1..3]
+                        .iter()
+                        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+                    && block[3].is_ascii_digit()
+            }),
+            _ => false,
+        }
+    }
+}
+
+impl AsRef<str> for Accession {
+    fn as_ref(&self) -> &str {
+        &self.accession
+    }
+}
+
+impl Deref for Accession {
+    type Target = str;
+    fn deref(&self) -> &str {
+        &self.accession
+    }
+}
+
+impl Display for Accession {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(&self.accession)
+    }
+}
+
+impl FromStr for Accession {
+    type Err = InvalidValue;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if Self::is_valid(s) {
+            Ok(Accession {
+                accession: ShortString::from(s),
+            })
+        } else {
+            Err(InvalidValue::from(s))
+        }
+    }
+}
+
+impl TryFrom<String> for Accession {
+    type Error = InvalidValue;
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::from_str(&s)
+    }
+}
+
+impl From<Accession> for String {
+    fn from(accession: Accession) -> Self {
+        String::from(accession.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        for accession in ["P12345", "Q65209", "A0A0A0A0A0", "A0A023GPI8", "A2BC19"] {
+            let parsed = Accession::from_str(accession).unwrap();
+            assert_eq!(parsed.as_str(), accession);
+        }
+        for accession in [
+            "p12345",
+            "XYZ",
+            "",
+            "P1234",
+            "P123456",
+            "O1234A",
+            "A0A0A0A0A",
+            "AAA0A0",
+        ] {
+            assert!(Accession::from_str(accession).is_err(), "{}", accession);
+        }
+    }
+
+    #[test]
+    fn test_is_swissprot_style() {
+        assert!(Accession::from_str("P12345").unwrap().is_swissprot_style());
+        assert!(Accession::from_str("Q65209").unwrap().is_swissprot_style());
+        assert!(!Accession::from_str("A2BC19").unwrap().is_swissprot_style());
+        assert!(!Accession::from_str("A0A023GPI8")
+            .unwrap()
+            .is_swissprot_style());
+    }
+}
@@ -1,22 +1,55 @@
-use std::ops::Deref;
-use std::ops::DerefMut;
-use std::str::FromStr;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
 
 use chrono::format::ParseError;
 use chrono::naive::NaiveDate;
-use chrono::offset::Local;
 use chrono::Datelike;
 
+use crate::common::prelude::*;
+use crate::error::InvalidValue;
+
+/// The components of a [`Date`] that were given in its source.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DatePrecision {
+    /// A date with a year only, in `YYYY` format.
+    Year,
+    /// A date with a year and a month, in `YYYY-MM` format.
+    Month,
+    /// A complete date, in `YYYY-MM-DD` format.
+    #[default]
+    Day,
+}
+
 /// A naive date in `YYYY-MM-DD` format.
-#[derive(Debug, Clone)]
+///
+/// Dates are ordered chronologically, and the default date sorts before
+/// any other date. Dates parsed with [`Date::from_partial`] keep track of
+/// their [`DatePrecision`], so that they are formatted back with the same
+/// components by their `Display` implementation.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Date {
     date: NaiveDate,
+    precision: DatePrecision,
 }
 
 impl Date {
     /// Create a new `Date` from a `chrono` object.
     pub fn new(date: NaiveDate) -> Self {
-        Self { date }
+        Self::with_precision(date, DatePrecision::Day)
+    }
+
+    /// Create a new `Date` from a `chrono` object with the given precision.
+    pub fn with_precision(date: NaiveDate, precision: DatePrecision) -> Self {
+        Self { date, precision }
+    }
+
+    /// Get the precision of the date.
+    pub fn precision(&self) -> DatePrecision {
+        self.precision
     }
 
     /// Get the year component of the date.
@@ -33,6 +66,70 @@ impl Date {
     pub fn day(&self) -> u32 {
         self.date.day()
     }
+
+    /// Get the date as a `chrono` object.
+    pub fn to_naive_date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// Get the number of days elapsed since the `other` date.
+    ///
+    /// The result is negative if `other` is after this date.
+    pub fn days_since(&self, other: &Date) -> i64 {
+        self.date.signed_duration_since(other.date).num_days()
+    }
+
+    /// Parse a date strictly in the ISO 8601 `YYYY-MM-DD` format.
+    ///
+    /// Contrary to [`Date::from_str`](#method.from_str), this rejects any
+    /// trailing timezone, and requires all components to be zero-padded.
+    pub fn from_iso8601(s: &str) -> Result<Self, InvalidValue> {
+        let b = s.as_bytes();
+        let well_formed = b.len() == 10
+            && b[4] == b'-'
+            && b[7] == b'-'
+            && b.iter()
+                .enumerate()
+                .all(|(i, c)| i == 4 || i == 7 || c.is_ascii_digit());
+        if !well_formed {
+            return Err(InvalidValue::from(s));
+        }
+        NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map(Date::new)
+            .map_err(|_| InvalidValue::from(s))
+    }
+
+    /// Parse a possibly partial date in `YYYY`, `YYYY-MM` or `YYYY-MM-DD` format.
+    ///
+    /// Missing components default to the first month or the first day, so
+    /// that `2001` is parsed as `2001-01-01` and `2003-03` as `2003-03-01`,
+    /// and the precision of the date records which components were given.
+    pub fn from_partial(s: &str) -> Result<Self, InvalidValue> {
+        let mut parts = s.splitn(3, '-');
+        let mut next = |default: u32| -> Result<u32, InvalidValue> {
+            match parts.next() {
+                None => Ok(default),
+                Some(x) if !x.is_empty() && x.bytes().all(|c| c.is_ascii_digit()) => {
+                    x.parse().map_err(|_| InvalidValue::from(s))
+                }
+                Some(_) => Err(InvalidValue::from(s)),
+            }
+        };
+        let year = next(0)?;
+        let month = next(1)?;
+        let day = next(1)?;
+        if year < 1000 {
+            return Err(InvalidValue::from(s));
+        }
+        let precision = match s.matches('-').count() {
+            0 => DatePrecision::Year,
+            1 => DatePrecision::Month,
+            _ => DatePrecision::Day,
+        };
+        NaiveDate::from_ymd_opt(year as i32, month, day)
+            .map(|date| Date::with_precision(date, precision))
+            .ok_or_else(|| InvalidValue::from(s))
+    }
 }
 
 impl AsRef<NaiveDate> for Date {
@@ -62,7 +159,17 @@ impl DerefMut for Date {
 
 impl Default for Date {
     fn default() -> Self {
-        Local::now().date_naive().into()
+        NaiveDate::MIN.into()
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self.precision {
+            DatePrecision::Year => write!(f, "{:04}", self.year()),
+            DatePrecision::Month => write!(f, "{:04}-{:02}", self.year(), self.month()),
+            DatePrecision::Day => self.date.fmt(f),
+        }
     }
 }
 
@@ -81,7 +188,7 @@ impl From<Date> for NaiveDate {
 impl FromStr for Date {
     type Err = ParseError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        const FORMATS: &[&'static str] = &["%Y-%m-%d", "%Y-%m-%d%Z", "%Y-%m-%d%:z"];
+        const FORMATS: &[&str] = &["%Y-%m-%d", "%Y-%m-%d%Z", "%Y-%m-%d%:z"];
         for (i, fmt) in FORMATS.iter().enumerate() {
             match NaiveDate::parse_from_str(s, fmt) {
                 Ok(dt) => return Ok(Date::new(dt)),
@@ -93,6 +200,31 @@ impl FromStr for Date {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Date {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Date {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DateVisitor;
+        impl<'de> serde::de::Visitor<'de> for DateVisitor {
+            type Value = Date;
+            fn expecting(&self, f: &mut Formatter<'_>) -> FmtResult {
+                f.write_str("a date in YYYY, YYYY-MM or YYYY-MM-DD format")
+            }
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Date, E> {
+                Date::from_partial(v)
+                    .map_err(|_| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+            }
+        }
+        deserializer.deserialize_str(DateVisitor)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -107,6 +239,83 @@ mod tests {
         assert_eq!(date.day(), 25);
     }
 
+    #[test]
+    fn test_from_iso8601() {
+        let date = Date::from_iso8601("2019-12-18").unwrap();
+        assert_eq!(date.year(), 2019);
+        assert_eq!(date.month(), 12);
+        assert_eq!(date.day(), 18);
+
+        assert!(Date::from_iso8601("").is_err());
+        assert!(Date::from_iso8601("2019-12-18Z").is_err());
+        assert!(Date::from_iso8601("2019-12-18garbage").is_err());
+        assert!(Date::from_iso8601("2019-1-8").is_err());
+        assert!(Date::from_iso8601("2019-13-01").is_err());
+    }
+
+    #[test]
+    fn test_from_partial() {
+        let date = Date::from_partial("2001").unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2001, 1, 1));
+        let date = Date::from_partial("2003-03").unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2003, 3, 1));
+        let date = Date::from_partial("2019-12-18").unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2019, 12, 18));
+
+        assert!(Date::from_partial("").is_err());
+        assert!(Date::from_partial("20").is_err());
+        assert!(Date::from_partial("2003-").is_err());
+        assert!(Date::from_partial("2003-13").is_err());
+        assert!(Date::from_partial("2003-MAR").is_err());
+    }
+
+    #[test]
+    fn test_precision() {
+        for s in ["2001", "2001-01", "2019-01-01", "2003-03", "2019-12-18"] {
+            assert_eq!(Date::from_partial(s).unwrap().to_string(), s);
+        }
+        assert_eq!(
+            Date::from_partial("2001").unwrap().precision(),
+            DatePrecision::Year
+        );
+        assert_eq!(
+            Date::from_partial("2003-03").unwrap().precision(),
+            DatePrecision::Month
+        );
+        assert_eq!(
+            Date::from_str("2012-12-25").unwrap().precision(),
+            DatePrecision::Day
+        );
+    }
+
+    #[test]
+    fn test_ordering() {
+        let d1 = Date::from_str("2009-05-05").unwrap();
+        let d2 = Date::from_str("2012-12-25").unwrap();
+        let d3 = Date::from_str("2012-12-31").unwrap();
+        assert!(d1 < d2);
+        assert!(d2 < d3);
+        assert_eq!(d2.clone().max(d3.clone()), d3);
+        assert!(Date::default() < d1);
+
+        let mut dates = vec![d3.clone(), Date::default(), d1.clone(), d2.clone()];
+        dates.sort();
+        assert_eq!(dates, vec![Date::default(), d1, d2, d3]);
+    }
+
+    #[test]
+    fn test_days_since() {
+        let d1 = Date::from_str("2012-12-25").unwrap();
+        let d2 = Date::from_str("2013-01-01").unwrap();
+        assert_eq!(d2.days_since(&d1), 7);
+        assert_eq!(d1.days_since(&d2), -7);
+        assert_eq!(d1.days_since(&d1), 0);
+        assert_eq!(
+            d1.to_naive_date(),
+            NaiveDate::from_ymd_opt(2012, 12, 25).unwrap()
+        );
+    }
+
     #[test]
     fn test_datetime_from_str() {
         let date = Date::from_str("2012-12-25").unwrap();
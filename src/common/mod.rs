@@ -1,5 +1,7 @@
 //! Common types for `uniprot` and `uniref`.
+pub mod accession;
 pub mod date;
+pub mod prelude;
 pub mod property;
 pub mod sequence;
 
@@ -9,4 +11,4 @@ pub type ShortString = smartstring::alias::String;
 
 /// The string type used throughout the library.
 #[cfg(not(feature = "smartstring"))]
-pub type ShortString = std::string::String;
+pub type ShortString = alloc::string::String;
@@ -0,0 +1,12 @@
+//! Items of the `std` prelude that are missing from the `core` prelude.
+//!
+//! This module is glob-imported by the data model so that it compiles
+//! without the `std` feature.
+
+pub use alloc::borrow::ToOwned;
+pub use alloc::boxed::Box;
+pub use alloc::format;
+pub use alloc::string::String;
+pub use alloc::string::ToString;
+pub use alloc::vec;
+pub use alloc::vec::Vec;
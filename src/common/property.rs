@@ -1,15 +1,33 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_empty_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A single key-value property.
 pub struct Property {
     pub ty: ShortString,
@@ -22,11 +40,13 @@ impl Property {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Property {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"property");
 
@@ -43,3 +63,13 @@ impl FromXml for Property {
         Ok(Property::new(ty, value))
     }
 }
+
+#[cfg(feature = "parser")]
+impl ToXml for Property {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("property");
+        elem.push_attribute(("type", self.ty.as_ref()));
+        elem.push_attribute(("value", self.value.as_ref()));
+        write_empty_element(writer, elem)
+    }
+}
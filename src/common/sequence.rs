@@ -1,11 +1,21 @@
+#[cfg(feature = "parser")]
 use std::io::BufRead;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
 
 /// A protein sequence.
@@ -16,27 +26,101 @@ pub struct Sequence {
     pub checksum: u64,
 }
 
+impl Sequence {
+    /// Format the sequence as a FASTA record with the given header.
+    ///
+    /// The header is written after the leading `>`, and the sequence is
+    /// wrapped at 60 columns.
+    pub fn to_fasta(&self, header: &str) -> String {
+        format_fasta(header, &self.sequence)
+    }
+}
+
+/// Format a sequence as a FASTA record wrapped at 60 columns.
+pub(crate) fn format_fasta(header: &str, sequence: &str) -> String {
+    let mut fasta = String::with_capacity(header.len() + sequence.len() * 61 / 60 + 3);
+    fasta.push('>');
+    fasta.push_str(header);
+    fasta.push('\n');
+    for (i, c) in sequence.chars().enumerate() {
+        if i > 0 && i % 60 == 0 {
+            fasta.push('\n');
+        }
+        fasta.push(c);
+    }
+    if !sequence.is_empty() {
+        fasta.push('\n');
+    }
+    fasta
+}
+
+/// Remove the ASCII whitespace from the text of a sequence element.
+///
+/// Sequences are usually written on a single line, but may be wrapped or
+/// indented, and the XML reader does not always trim the surrounding text.
+#[cfg(feature = "parser")]
+pub(crate) fn strip_whitespace(text: ShortString) -> ShortString {
+    if text.bytes().any(|b| b.is_ascii_whitespace()) {
+        text.chars().filter(|c| !c.is_ascii_whitespace()).collect()
+    } else {
+        text
+    }
+}
+
+/// The lookup table of the CRC64 checksum, built at compile time.
+const CRC64_TABLE: [u64; 256] = {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut part = i as u64;
+        let mut j = 0;
+        while j < 8 {
+            part = if part & 1 != 0 {
+                (part >> 1) ^ 0xD800_0000_0000_0000
+            } else {
+                part >> 1
+            };
+            j += 1;
+        }
+        table[i] = part;
+        i += 1;
+    }
+    table
+};
+
+/// Compute the CRC64 checksum of a sequence.
+///
+/// This is the ISO 3309 CRC64 variant used by UniProt, without initial
+/// value nor final XOR.
+pub(crate) fn crc64(sequence: &str) -> u64 {
+    sequence.bytes().fold(0, |crc, byte| {
+        CRC64_TABLE[((crc ^ byte as u64) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Sequence {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"sequence");
 
-        // decode attributes
-        let length = decode_attribute(event, reader, "length", "sequence")?;
+        // decode attributes, using the number of residues as the length
+        decode_attribute::<_, usize>(event, reader, "length", "sequence")?;
         let checksum = extract_attribute(event, "checksum")?
             .map(|x| x.decode_and_unescape_value(reader))
             .transpose()?
             .map(|x| u64::from_str_radix(&x, 16))
             .ok_or(Error::MissingAttribute("checksum", "sequence"))??;
 
-        // extract `sequence` element
-        let sequence = parse_text!(event, reader, buffer);
+        // extract `sequence` element, ignoring whitespace
+        let sequence = strip_whitespace(parse_text!(event, reader, buffer));
         Ok(Sequence {
+            length: sequence.len(),
             sequence,
-            length,
             checksum,
         })
     }
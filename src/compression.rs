@@ -0,0 +1,46 @@
+//! Helpers to decode compressed data.
+
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+
+use crate::error::Error;
+
+#[cfg(feature = "gzip")]
+/// The magic bytes found at the beginning of a Gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[cfg(feature = "xz")]
+/// The magic bytes found at the beginning of an XZ stream.
+const XZ_MAGIC: [u8; 6] = [0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00];
+
+#[cfg(feature = "zstd")]
+/// The magic bytes found at the beginning of a Zstandard frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Wrap a reader to decompress its content if it is compressed.
+///
+/// The compression is detected from the magic bytes at the beginning of
+/// the stream, among the formats enabled by the `gzip`, `xz` and `zstd`
+/// features; data without any of these is returned unchanged. Streams made
+/// of several members, such as BGZF files, are fully decoded.
+pub fn reader<R: Read + Send + 'static>(reader: R) -> Result<Box<dyn BufRead + Send>, Error> {
+    let mut reader = BufReader::new(reader);
+    let magic = reader.fill_buf()?;
+    #[cfg(feature = "gzip")]
+    if magic.starts_with(&GZIP_MAGIC) {
+        let decoder = libflate::gzip::MultiDecoder::new(reader)?;
+        return Ok(Box::new(BufReader::new(decoder)));
+    }
+    #[cfg(feature = "xz")]
+    if magic.starts_with(&XZ_MAGIC) {
+        let decoder = xz2::bufread::XzDecoder::new_multi_decoder(reader);
+        return Ok(Box::new(BufReader::new(decoder)));
+    }
+    #[cfg(feature = "zstd")]
+    if magic.starts_with(&ZSTD_MAGIC) {
+        let decoder = zstd::stream::read::Decoder::with_buffer(reader)?;
+        return Ok(Box::new(BufReader::new(decoder)));
+    }
+    Ok(Box::new(reader))
+}
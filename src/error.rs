@@ -1,23 +1,41 @@
 //! Ubiquitous types for error management.
 
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::num::ParseIntError;
+use core::str::ParseBoolError;
+#[cfg(feature = "std")]
 use std::error::Error as StdError;
-use std::fmt::Display;
-use std::fmt::Formatter;
-use std::fmt::Result as FmtResult;
+#[cfg(feature = "parser")]
 use std::io::Error as IoError;
-use std::num::ParseIntError;
-use std::str::ParseBoolError;
+#[cfg(feature = "parser")]
 use std::sync::Arc;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::attributes::AttrError;
+#[cfg(feature = "parser")]
 use quick_xml::Error as XmlError;
 #[cfg(feature = "url-links")]
 use url::ParseError as ParseUrlError;
 
+use crate::common::prelude::*;
+
+#[cfg(feature = "parser")]
 #[derive(Debug)]
 /// The main error type for the [`uniprot`] crate.
 ///
+/// The errors yielded by the parsers are wrapped in [`Error::At`] with the
+/// byte position where they were raised, which is kept when the error is
+/// propagated with `?`. Use [`Error::position`] to get the position, and
+/// [`Error::inner`] or [`Error::into_inner`] to match on the underlying
+/// error.
+///
 /// [`uniprot`]: ../index.html
+/// [`Error::At`]: #variant.At
+/// [`Error::position`]: #method.position
+/// [`Error::inner`]: #method.inner
+/// [`Error::into_inner`]: #method.into_inner
 pub enum Error {
     /// The underlying XML parser encountered an error.
     ///
@@ -48,6 +66,13 @@ pub enum Error {
     /// Unexpected root element.
     UnexpectedRoot(String),
 
+    /// Several entries were found with the same primary accession.
+    DuplicateAccession(String),
+
+    #[cfg(feature = "serde")]
+    /// A value could not be serialized to or deserialized from JSON.
+    Json(serde_json::Error),
+
     #[cfg(feature = "url-links")]
     /// A `Url` value could not be parsed successfully.
     ParseUrl(ParseUrlError),
@@ -55,8 +80,33 @@ pub enum Error {
     #[cfg(feature = "threading")]
     /// A communication channel between threads was disconnected early.
     DisconnectedChannel,
+
+    #[cfg(feature = "threading")]
+    /// An entry was larger than the maximum number of bytes allowed.
+    EntryTooLarge(usize),
+
+    #[cfg(feature = "http")]
+    /// An HTTP request could not be performed.
+    Http(Box<ureq::Transport>),
+
+    #[cfg(feature = "http")]
+    /// An HTTP request returned an unexpected status code.
+    HttpStatus(u16, String),
+
+    #[cfg(feature = "http")]
+    /// An HTTP request returned a response with an empty body.
+    EmptyResponse(String),
+
+    /// An error raised at the given byte position of the parsed document.
+    At {
+        /// The byte position, counted from the beginning of the reader.
+        position: u64,
+        /// The underlying error.
+        source: Box<Error>,
+    },
 }
 
+#[cfg(feature = "parser")]
 impl Error {
     pub fn invalid_value<S: Into<String>>(
         name: &'static str,
@@ -65,8 +115,73 @@ impl Error {
     ) -> Self {
         Error::InvalidValue(name, elem, InvalidValue(value.into()))
     }
+
+    /// Attach the byte position where the error was raised.
+    ///
+    /// An error which already has a position is returned unchanged.
+    pub(crate) fn at(self, position: u64) -> Self {
+        match self {
+            Error::At { .. } => self,
+            other => Error::At {
+                position,
+                source: Box::new(other),
+            },
+        }
+    }
+
+    /// Get the byte position of the error in the parsed document, if known.
+    ///
+    /// The position is counted from the beginning of the reader given to
+    /// the parser, so an error raised before anything could be read is
+    /// reported at position `0`.
+    pub fn position(&self) -> Option<u64> {
+        match self {
+            Error::At { position, .. } => Some(*position),
+            _ => None,
+        }
+    }
+
+    /// Get a reference to the underlying error, without its byte position.
+    pub fn inner(&self) -> &Self {
+        match self {
+            Error::At { source, .. } => source,
+            other => other,
+        }
+    }
+
+    /// Get the underlying error, without its byte position.
+    pub fn into_inner(self) -> Self {
+        match self {
+            Error::At { source, .. } => *source,
+            other => other,
+        }
+    }
+
+    /// Get the location of the error in the XML document, if known.
+    ///
+    /// Returns a pair with the name of the faulty attribute or element and
+    /// the name of its parent element, for the [`InvalidValue`],
+    /// [`MissingElement`], [`MissingAttribute`] and [`DuplicateElement`]
+    /// variants.
+    ///
+    /// [`InvalidValue`]: #variant.InvalidValue
+    /// [`MissingElement`]: #variant.MissingElement
+    /// [`MissingAttribute`]: #variant.MissingAttribute
+    /// [`DuplicateElement`]: #variant.DuplicateElement
+    pub fn context(&self) -> Option<(&'static str, &'static str)> {
+        use self::Error::*;
+        match self {
+            MissingElement(x, y) => Some((x, y)),
+            MissingAttribute(x, y) => Some((x, y)),
+            DuplicateElement(x, y) => Some((x, y)),
+            InvalidValue(x, y, _) => Some((x, y)),
+            At { source, .. } => source.context(),
+            _ => None,
+        }
+    }
 }
 
+#[cfg(feature = "parser")]
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         use self::Error::*;
@@ -81,24 +196,39 @@ impl Display for Error {
             DuplicateElement(x, y) => write!(f, "duplicate element `{}` in `{}`", x, y),
             InvalidValue(x, y, _) => write!(f, "invalid value for attribute `{}` in `{}`", x, y),
             UnexpectedRoot(root) => write!(f, "unexpected root element `{}`", root),
+            DuplicateAccession(acc) => write!(f, "duplicate primary accession `{}`", acc),
+            #[cfg(feature = "serde")]
+            Json(e) => write!(f, "json error: {}", e),
             #[cfg(feature = "threading")]
             DisconnectedChannel => write!(f, "unexpected threading channel disconnection"),
+            #[cfg(feature = "threading")]
+            EntryTooLarge(n) => write!(f, "entry larger than the limit of {} bytes", n),
+            #[cfg(feature = "http")]
+            Http(e) => write!(f, "http error: {}", e),
+            #[cfg(feature = "http")]
+            HttpStatus(code, url) => write!(f, "unexpected status code {} for `{}`", code, url),
+            #[cfg(feature = "http")]
+            EmptyResponse(url) => write!(f, "empty response for `{}`", url),
+            At { position, source } => write!(f, "{} at byte {}", source, position),
         }
     }
 }
 
+#[cfg(feature = "parser")]
 impl From<IoError> for Error {
     fn from(e: IoError) -> Self {
         Self::from(XmlError::Io(Arc::new(e)))
     }
 }
 
+#[cfg(feature = "parser")]
 impl From<ParseBoolError> for Error {
     fn from(e: ParseBoolError) -> Self {
         Error::ParseBool(e)
     }
 }
 
+#[cfg(feature = "parser")]
 impl From<ParseIntError> for Error {
     fn from(e: ParseIntError) -> Self {
         Error::ParseInt(e)
@@ -112,18 +242,28 @@ impl From<ParseUrlError> for Error {
     }
 }
 
+#[cfg(all(feature = "parser", feature = "serde"))]
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+#[cfg(feature = "parser")]
 impl From<AttrError> for Error {
     fn from(e: AttrError) -> Self {
         Error::Xml(e.into())
     }
 }
 
+#[cfg(feature = "parser")]
 impl From<XmlError> for Error {
     fn from(e: XmlError) -> Self {
         Error::Xml(e)
     }
 }
 
+#[cfg(feature = "parser")]
 impl StdError for Error {
     fn source(&self) -> Option<&(dyn StdError + 'static)> {
         use self::Error::*;
@@ -134,15 +274,21 @@ impl StdError for Error {
             InvalidValue(_, _, e) => Some(e),
             #[cfg(feature = "url-links")]
             ParseUrl(e) => Some(e),
+            #[cfg(feature = "serde")]
+            Json(e) => Some(e),
+            #[cfg(feature = "http")]
+            Http(e) => Some(e),
+            At { source, .. } => source.source(),
             _ => None,
         }
     }
 }
 
+#[cfg(feature = "parser")]
 /// The main result type for the [`uniprot`] crate.
 ///
 /// [`uniprot`]: ../index.html
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
 
 // ---------------------------------------------------------------------------
 
@@ -156,6 +302,7 @@ impl Display for InvalidValue {
     }
 }
 
+#[cfg(feature = "std")]
 impl StdError for InvalidValue {}
 
 impl<S: Into<String>> From<S> for InvalidValue {
@@ -163,3 +310,41 @@ impl<S: Into<String>> From<S> for InvalidValue {
         InvalidValue(s.into())
     }
 }
+
+#[cfg(all(test, feature = "parser"))]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn context() {
+        let err = Error::invalid_value("type", "feature", "xyz");
+        assert_eq!(err.context(), Some(("type", "feature")));
+        let err = Error::MissingElement("sequence", "entry");
+        assert_eq!(err.context(), Some(("sequence", "entry")));
+        let err = Error::MissingAttribute("type", "dbReference");
+        assert_eq!(err.context(), Some(("type", "dbReference")));
+        let err = Error::DuplicateElement("protein", "entry");
+        assert_eq!(err.context(), Some(("protein", "entry")));
+        let err = Error::UnexpectedRoot(String::from("html"));
+        assert_eq!(err.context(), None);
+        let err = Error::MissingElement("sequence", "entry").at(42);
+        assert_eq!(err.context(), Some(("sequence", "entry")));
+    }
+
+    #[test]
+    fn position() {
+        let err = Error::MissingElement("sequence", "entry");
+        assert_eq!(err.position(), None);
+        let err = err.at(42);
+        assert_eq!(err.position(), Some(42));
+        assert_eq!(
+            err.to_string(),
+            "missing element `sequence` in `entry` at byte 42"
+        );
+        let err = err.at(100);
+        assert_eq!(err.position(), Some(42));
+        assert!(matches!(err.inner(), Error::MissingElement(_, _)));
+        assert!(matches!(err.into_inner(), Error::MissingElement(_, _)));
+    }
+}
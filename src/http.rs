@@ -0,0 +1,35 @@
+//! Helpers to retrieve data over HTTP.
+
+use std::io::BufRead;
+use std::io::BufReader;
+
+use crate::error::Error;
+
+/// The maximum number of redirects to follow for a single request.
+///
+/// The legacy `www.uniprot.org` URLs are redirected to `rest.uniprot.org`
+/// so at least one redirect must be allowed.
+pub(crate) const MAX_REDIRECTS: u32 = 5;
+
+/// Perform a `GET` request for an XML document and return the response body.
+///
+/// Any response with a status code other than `200 OK`, or with an empty
+/// body, is reported as an error.
+pub fn get(url: &str) -> Result<impl BufRead + Send + 'static, Error> {
+    let agent = ureq::AgentBuilder::new().redirects(MAX_REDIRECTS).build();
+    let response = match agent.get(url).set("Accept", "application/xml").call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(code, _)) => return Err(Error::HttpStatus(code, url.into())),
+        Err(ureq::Error::Transport(t)) => return Err(Error::Http(Box::new(t))),
+    };
+    if response.status() != 200 {
+        return Err(Error::HttpStatus(response.status(), url.into()));
+    }
+
+    let mut reader = BufReader::new(response.into_reader());
+    if reader.fill_buf()?.is_empty() {
+        return Err(Error::EmptyResponse(url.into()));
+    }
+
+    Ok(reader)
+}
@@ -82,7 +82,8 @@
 //! [`libflate::gzip::Decoder`] to decode the input stream, and then simply
 //! wrap it in a [`BufferedReader`]. Note that [`flate2`] has slightly better
 //! performance, but binds to C,, while [`libflate`] is a pure Rust
-//! implementation.
+//! implementation. With the `gzip` feature, [`uniprot::uniprot::parse_gzip`]
+//! can also be used to decode the input transparently with [`libflate`].
 //!
 //! ## 📧 Downloading from FTP
 //!
@@ -101,11 +102,80 @@
 //!
 //! # 📝 Features
 //!
+//! ## `parser` - _**enabled** by default_.
+//!
+//! The `parser` feature compiles the XML parsers and writers, along with all
+//! the functions reading entries from a [`BufRead`]. It implies the `std`
+//! feature.
+//!
+//! ## `std` - _**enabled** by default_.
+//!
+//! The `std` feature links the Rust standard library. Without it, the crate
+//! is `no_std` and only provides the data model.
+//!
+//! ## `alloc` - _**enabled** by default_.
+//!
+//! The `alloc` feature enables the `alloc` support of the `chrono` crate.
+//! The crate itself always links the `alloc` crate, so disabling all
+//! default features still gives access to the types of the
+//! [`uniprot::uniprot`], [`uniprot::uniref`] and [`uniprot::uniparc`]
+//! modules in `no_std` environments, so that entries can be built and
+//! inspected without a parser.
+//!
 //! ## `threading` - _**enabled** by default_.
 //!
 //! The `threading` feature compiles the parser module in multi-threaded mode.
 //! This feature greatly improves parsing speed and efficiency, but removes
-//! any guarantee about the order the entries are yielded in.
+//! any guarantee about the order the entries are yielded in, unless the
+//! parser is created with [`ThreadedParser::ordered`].
+//!
+//! ## `http` - _**disabled** by default_.
+//!
+//! The `http` feature adds a [`uniprot::uniprot::fetch`] function to retrieve
+//! a single entry from the UniProt REST API, using [`ureq`] to perform the
+//! HTTP request. Combined with the `gzip` feature, it also adds the
+//! [`uniprot::net::open_release`] function to download a complete release
+//! file, retrying on transient network errors.
+//!
+//! ## `async` - _**disabled** by default_.
+//!
+//! The `async` feature adds a [`uniprot::uniprot::parse_async`] function
+//! (and its equivalents for UniRef and UniParc) to parse entries from a
+//! [`tokio::io::AsyncBufRead`] reader into a [`Stream`]. Only the reading
+//! is asynchronous: each entry is deserialized synchronously once read.
+//!
+//! ## `gzip` - _**disabled** by default_.
+//!
+//! The `gzip` feature adds a [`uniprot::uniprot::parse_gzip`] function
+//! to parse entries from a reader that may or may not be Gzip-compressed,
+//! using [`libflate`] to decode the compressed input.
+//!
+//! ## `xz` and `zstd` - _**disabled** by default_.
+//!
+//! The `xz` and `zstd` features add the [`uniprot::uniprot::parse_xz`] and
+//! [`uniprot::uniprot::parse_zstd`] functions, which work like `parse_gzip`
+//! for XZ and Zstandard-compressed files, using the [`xz2`] and [`zstd`]
+//! crates. With any of the compression features, the
+//! [`uniprot::uniprot::parse_compressed`] function detects the compression
+//! from the magic bytes at the beginning of the file, so any enabled format
+//! can be read regardless of the file extension; the format-specific
+//! functions are shorthands for it.
+//!
+//! ## `rayon` - _**disabled** by default_.
+//!
+//! The `rayon` feature implements [`IntoParallelIterator`] for references to
+//! the [`UniProt`], [`UniRef`] and [`UniParc`] collections, so that entries
+//! parsed in memory can be processed in parallel with [`rayon`].
+//!
+//! ## `serde` - _**disabled** by default_.
+//!
+//! The `serde` feature derives the [`Serialize`] and [`Deserialize`] traits
+//! for the types of the [`uniprot::uniprot`] model, allowing entries to be
+//! converted to other formats such as JSON. Enum variants are (de)serialized
+//! using their UniProt spelling, e.g. `"Swiss-Prot"` or `"journal article"`.
+//! It also enables [`uniprot::uniprot::write_ndjson`] to write entries as
+//! newline-delimited JSON, and [`uniprot::uniprot::parse_json`] to read
+//! entries in the JSON format of the UniProt REST API.
 //!
 //! ## 📋 Changelog
 //!
@@ -122,10 +192,29 @@
 //! [ftp.ebi.ac.uk]: ftp://ftp.ebi.ac.uk
 //! [ftp.uniprot.org]: ftp://ftp.uniprot.org
 //! [`threading`]: #threading
+//! [`ureq`]: https://docs.rs/ureq/
+//! [`uniprot::uniprot::fetch`]: ./uniprot/fn.fetch.html
+//! [`uniprot::uniprot::parse_async`]: ./uniprot/fn.parse_async.html
+//! [`uniprot::uniprot::parse_gzip`]: ./uniprot/fn.parse_gzip.html
+//! [`uniprot::uniprot::parse_xz`]: ./uniprot/fn.parse_xz.html
+//! [`uniprot::uniprot::parse_compressed`]: ./uniprot/fn.parse_compressed.html
+//! [`uniprot::uniprot::parse_zstd`]: ./uniprot/fn.parse_zstd.html
+//! [`uniprot::net::open_release`]: ./net/fn.open_release.html
+//! [`tokio::io::AsyncBufRead`]: https://docs.rs/tokio/latest/tokio/io/trait.AsyncBufRead.html
+//! [`Stream`]: https://docs.rs/futures-core/latest/futures_core/stream/trait.Stream.html
+//! [`Serialize`]: https://docs.rs/serde/latest/serde/trait.Serialize.html
+//! [`Deserialize`]: https://docs.rs/serde/latest/serde/trait.Deserialize.html
+//! [`uniprot::uniprot`]: ./uniprot/index.html
+//! [`uniprot::uniref`]: ./uniref/index.html
+//! [`uniprot::uniparc`]: ./uniparc/index.html
+//! [`uniprot::uniprot::write_ndjson`]: ./uniprot/fn.write_ndjson.html
+//! [`uniprot::uniprot::parse_json`]: ./uniprot/fn.parse_json.html
 //! [`flate2`]: https://docs.rs/flate2/
 //! [`flate2::read::GzDecoder`]: https://docs.rs/flate2/latest/flate2/read/struct.GzDecoder.html
 //! [`libflate`]: https://docs.rs/libflate/
 //! [`libflate::gzip::Decoder`]: https://docs.rs/libflate/latest/libflate/gzip/struct.Decoder.html
+//! [`xz2`]: https://docs.rs/xz2/
+//! [`zstd`]: https://docs.rs/zstd/
 //! [`BufRead`]: https://doc.rust-lang.org/std/io/trait.BufRead.html
 //! [`BufferedReader`]: https://doc.rust-lang.org/std/io/struct.BufReader.html
 //! [`Entry`]: ./model/struct.Entry.html
@@ -138,32 +227,79 @@
 //! [`uniprot::Parser`]: ./type.Parser.html
 //! [`SequentialParser`]: ./parser/struct.SequentialParser.html
 //! [`ThreadedParser`]: ./parser/struct.ThreadedParser.html
+//! [`ThreadedParser::ordered`]: ./parser/struct.ThreadedParser.html#method.ordered
+//! [`rayon`]: https://docs.rs/rayon/
+//! [`IntoParallelIterator`]: https://docs.rs/rayon/latest/rayon/iter/trait.IntoParallelIterator.html
+//! [`UniProt`]: ./uniprot/struct.UniProt.html
+//! [`UniRef`]: ./uniref/struct.UniRef.html
+//! [`UniParc`]: ./uniparc/struct.UniParc.html
 //! [SwissProt]: https://ftp.uniprot.org/pub/databases/uniprot/current_release/knowledgebase/complete/uniprot_sprot.xml.gz
 //! [TrEMBL]: https://ftp.uniprot.org/pub/databases/uniprot/current_release/knowledgebase/complete/uniprot_trembl.xml.gz
 //! [UniRef100]: https://ftp.uniprot.org/pub/databases/uniprot/uniref/uniref100/uniref100.xml.gz
 //! [UniRef90]: https://ftp.uniprot.org/pub/databases/uniprot/uniref/uniref90/uniref90.xml.gz
 //! [UniRef50]: https://ftp.uniprot.org/pub/databases/uniprot/uniref/uniref50/uniref50.xml.gz
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![allow(unused_imports)]
 
+extern crate alloc;
 extern crate chrono;
 #[cfg(feature = "threading")]
 extern crate crossbeam_channel;
+#[cfg(feature = "async")]
+extern crate futures_core;
+#[cfg(feature = "async")]
+extern crate futures_util;
 #[macro_use]
 #[cfg(feature = "threading")]
 extern crate lazy_static;
+#[cfg(feature = "gzip")]
+extern crate libflate;
 #[cfg(feature = "threading")]
 extern crate num_cpus;
+#[cfg(feature = "parser")]
 extern crate quick_xml;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
 #[cfg(feature = "smartstring")]
 extern crate smartstring;
+#[cfg(feature = "async")]
+extern crate tokio;
+#[cfg(feature = "http")]
+extern crate ureq;
 #[cfg(feature = "url-links")]
 extern crate url;
+#[cfg(feature = "xz")]
+extern crate xz2;
+#[cfg(feature = "zstd")]
+extern crate zstd;
 
 #[macro_use]
+#[cfg(feature = "parser")]
 pub mod parser;
+#[cfg(feature = "parser")]
+mod any;
 mod common;
+#[cfg(any(feature = "gzip", feature = "xz", feature = "zstd"))]
+mod compression;
 pub mod error;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(all(feature = "http", feature = "gzip"))]
+pub mod net;
 pub mod uniparc;
 pub mod uniprot;
 pub mod uniref;
+
+#[cfg(feature = "parser")]
+pub use self::any::parse_any;
+#[cfg(feature = "parser")]
+pub use self::any::AnyEntries;
+#[cfg(feature = "parser")]
+pub use self::any::PeekedReader;
+#[cfg(feature = "parser")]
+pub use self::parser::count_entries;
@@ -0,0 +1,165 @@
+//! Helpers to download database releases over HTTP.
+
+use std::io::BufRead;
+use std::thread;
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::http::MAX_REDIRECTS;
+
+/// The delay before the first retry, doubled after every failed attempt.
+const BACKOFF: Duration = Duration::from_secs(1);
+
+/// The timeout for establishing a connection to the server.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Open a database release file over HTTP, ready to be parsed.
+///
+/// The request is retried up to `retries` times, with an exponential
+/// backoff, when the server answers with a `5xx` status code or when the
+/// connection fails or times out. Any other error, such as a `404 Not
+/// Found` status, is returned immediately. The response body is
+/// decompressed transparently if it starts with the magic bytes of a
+/// compression format enabled by the `gzip`, `xz` or `zstd` features.
+///
+/// # Example
+/// ```rust,no_run
+/// let url = "https://ftp.uniprot.org/pub/databases/uniprot/current_release/knowledgebase/complete/uniprot_sprot.xml.gz";
+/// let reader = uniprot::net::open_release(url, 3).unwrap();
+/// for entry in uniprot::uniprot::parse(reader) {
+///     println!("{:?}", entry.unwrap().primary_accession());
+/// }
+/// ```
+pub fn open_release(url: &str, retries: usize) -> Result<Box<dyn BufRead + Send>, Error> {
+    open_release_with_backoff(url, retries, BACKOFF)
+}
+
+fn open_release_with_backoff(
+    url: &str,
+    retries: usize,
+    backoff: Duration,
+) -> Result<Box<dyn BufRead + Send>, Error> {
+    let agent = ureq::AgentBuilder::new()
+        .redirects(MAX_REDIRECTS)
+        .timeout_connect(CONNECT_TIMEOUT)
+        .build();
+
+    let mut delay = backoff;
+    let mut attempt = 0;
+    let response = loop {
+        let error = match agent.get(url).call() {
+            Ok(response) => break response,
+            Err(ureq::Error::Status(code, _)) => Error::HttpStatus(code, url.into()),
+            Err(ureq::Error::Transport(t)) => Error::Http(Box::new(t)),
+        };
+        if attempt >= retries || !is_transient(&error) {
+            return Err(error);
+        }
+        thread::sleep(delay);
+        delay *= 2;
+        attempt += 1;
+    };
+
+    crate::compression::reader(response.into_reader())
+}
+
+/// Check whether a request error may disappear when retrying.
+fn is_transient(error: &Error) -> bool {
+    match error {
+        Error::HttpStatus(code, _) => (500..600).contains(code),
+        Error::Http(t) => matches!(
+            t.kind(),
+            ureq::ErrorKind::Io | ureq::ErrorKind::ConnectionFailed
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Read;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    use super::*;
+
+    /// Serve the given responses in order, and count the requests received.
+    fn serve(responses: Vec<(u16, Vec<u8>)>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buffer).unwrap();
+                    request.extend_from_slice(&buffer[..n]);
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Status\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                )
+                .unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+        (url, count)
+    }
+
+    #[test]
+    fn retry_on_server_error() {
+        let xml = std::fs::read("tests/uniprot.xml").unwrap();
+        let (url, count) = serve(vec![(503, Vec::new()), (200, xml)]);
+        let url = format!("{}/uniprot.xml", url);
+
+        let reader = open_release_with_backoff(&url, 3, Duration::from_millis(10))
+            .expect("request should be retried");
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+        let entries = crate::uniprot::parse(reader)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(entries.len(), 250);
+    }
+
+    #[test]
+    fn retry_exhausted() {
+        let (url, count) = serve(vec![(503, Vec::new()), (502, Vec::new())]);
+        let result = open_release_with_backoff(&url, 1, Duration::from_millis(10));
+        assert!(matches!(result, Err(Error::HttpStatus(502, _))));
+        assert_eq!(count.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn no_retry_on_not_found() {
+        let (url, count) = serve(vec![(404, Vec::new()), (200, Vec::new())]);
+        let result = open_release_with_backoff(&url, 3, Duration::from_millis(10));
+        assert!(matches!(result, Err(Error::HttpStatus(404, _))));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn decompress_gz() {
+        let xml = std::fs::read("tests/uniprot.xml").unwrap();
+        let mut encoder = libflate::gzip::Encoder::new(Vec::new()).unwrap();
+        encoder.write_all(&xml).unwrap();
+        let gz = encoder.finish().into_result().unwrap();
+        let (url, _) = serve(vec![(200, gz)]);
+        let url = format!("{}/uniprot.xml.gz?download=true", url);
+
+        let mut reader = open_release_with_backoff(&url, 0, Duration::from_millis(10))
+            .expect("request should succeed");
+        let mut text = Vec::new();
+        reader.read_to_end(&mut text).unwrap();
+        assert_eq!(text, xml);
+    }
+}
@@ -2,8 +2,6 @@ use std::collections::HashSet;
 use std::io::BufRead;
 use std::io::Cursor;
 use std::io::Error as IoError;
-use std::sync::atomic::AtomicBool;
-use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::thread::JoinHandle;
@@ -18,77 +16,81 @@ use quick_xml::events::Event;
 use quick_xml::Error as XmlError;
 use quick_xml::Reader;
 
+use super::producer::Buffer;
 use super::FromXml;
+use super::Item;
+use super::ParseOptions;
+use super::ParserConfig;
 use super::UniprotDatabase;
 use super::SLEEP_DURATION;
 use crate::error::Error;
 
 pub struct Consumer<D: UniprotDatabase> {
-    r_text: Receiver<Option<Result<Vec<u8>, Error>>>,
-    s_item: Sender<Result<D::Entry, Error>>,
-    alive: Arc<AtomicBool>,
+    r_text: Receiver<Option<Buffer>>,
+    s_item: Sender<Item<D::Entry>>,
     handle: Option<JoinHandle<()>>,
+    config: ParserConfig,
 }
 
 impl<D: UniprotDatabase> Consumer<D> {
     pub(super) fn new(
-        r_text: Receiver<Option<Result<Vec<u8>, Error>>>,
-        s_item: Sender<Result<D::Entry, Error>>,
+        r_text: Receiver<Option<Buffer>>,
+        s_item: Sender<Item<D::Entry>>,
+        config: ParserConfig,
     ) -> Self {
         Self {
             r_text,
             s_item,
+            config,
             handle: None,
-            alive: Arc::new(AtomicBool::new(false)),
         }
     }
 
-    pub fn start(&mut self) {
-        self.alive.store(true, Ordering::SeqCst);
-
+    pub fn start(&mut self, options: ParseOptions) {
         let s_item = self.s_item.clone();
         let r_text = self.r_text.clone();
-        let alive = self.alive.clone();
+        let config = self.config;
 
         self.handle = Some(std::thread::spawn(move || {
             let mut buffer = Vec::new();
-            loop {
+            'entries: loop {
                 // get the buffer containing the XML entry
-                let text = loop {
+                let (index, start, text) = loop {
                     match r_text.recv_timeout(SLEEP_DURATION) {
-                        Ok(Some(Ok(text))) => break text,
-                        Ok(Some(Err(err))) => {
-                            s_item.send(Err(err)).ok();
-                        }
-                        Ok(None) => {
-                            alive.store(false, Ordering::SeqCst);
-                            return;
+                        Ok(Some((index, Ok((start, text))))) => break (index, start, text),
+                        Ok(Some((index, Err((position, err))))) => {
+                            s_item.send((index, position, Err(err))).ok();
                         }
+                        Ok(None) => break 'entries,
                         Err(RecvTimeoutError::Timeout) => (),
-                        Err(RecvTimeoutError::Disconnected) => {
-                            alive.store(false, Ordering::SeqCst);
-                            return;
-                        }
+                        Err(RecvTimeoutError::Disconnected) => break 'entries,
                     }
                 };
 
                 // parse the XML file and send the result to the main thread
                 let mut xml = Reader::from_reader(Cursor::new(&text));
-                xml.expand_empty_elements(true).trim_text(true);
+                config.configure(&mut xml);
                 match xml.read_event_into(&mut buffer) {
                     Err(e) => {
-                        s_item.send(Err(Error::from(e))).ok();
-                        return;
+                        let position = start + xml.buffer_position() as u64;
+                        s_item.send((index, position, Err(Error::from(e)))).ok();
+                        break 'entries;
                     }
                     Ok(Event::Eof) => {
                         let name = String::from("entry");
+                        let position = start + xml.buffer_position() as u64;
                         let err = Error::from(XmlError::UnexpectedEof(name));
-                        s_item.send(Err(err)).ok();
-                        return;
+                        s_item.send((index, position, Err(err))).ok();
+                        break 'entries;
                     }
                     Ok(Event::Start(s)) if s.local_name().as_ref() == b"entry" => {
-                        let e = D::Entry::from_xml(&s.into_owned(), &mut xml, &mut buffer);
-                        s_item.send(e).ok();
+                        let s = s.into_owned();
+                        let e = D::Entry::from_xml(&s, &mut xml, &mut buffer, &options);
+                        let position = match e {
+                            Ok(_) => start + text.len() as u64,
+                            Err(_) => start + xml.buffer_position() as u64,
+                        };
+                        s_item.send((index, position, e)).ok();
                     }
                     e => unreachable!("unexpected XML event: {:?}", e),
                 }
@@ -99,6 +101,11 @@ impl<D: UniprotDatabase> Consumer<D> {
         }));
     }
 
+    /// Check whether the thread is still running, even if it panicked.
+    pub fn is_alive(&self) -> bool {
+        self.handle.as_ref().is_some_and(|h| !h.is_finished())
+    }
+
     pub fn join(&mut self) -> std::thread::Result<()> {
         if let Some(handle) = self.handle.take() {
             handle.join()
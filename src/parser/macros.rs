@@ -1,17 +1,24 @@
 #[allow(unused_macros)]
 macro_rules! parse_inner {
-    ($event:expr, $reader:expr, $buffer:expr, $($rest:tt)*) => ({
+    ($event:expr, $reader:expr, $buffer:expr, $options:expr, $($rest:tt)*) => ({
+        // read the events into the allocation of `$buffer`, moved out so
+        // that the handlers can borrow the start event of a child element
+        // while using `$buffer` to read its content
+        let mut events = std::mem::take($buffer);
         loop {
             use $crate::quick_xml::events::BytesEnd;
             use $crate::quick_xml::events::BytesStart;
             use $crate::quick_xml::events::Event;
             use $crate::quick_xml::Error as XmlError;
 
-            $buffer.clear();
-            match $reader.read_event_into($buffer) {
+            events.clear();
+            match $reader.read_event_into(&mut events) {
+                Ok(Event::Start(ref x)) if $options.is_ignored(x.local_name().as_ref()) => {
+                    $reader.read_to_end_into(x.name(), $buffer)?;
+                }
                 Ok(Event::Start(ref x)) => {
-                    parse_inner_impl!(x, x.name(), $($rest)*);
-                    $reader.read_to_end_into(x.name(), &mut Vec::new())?;
+                    parse_inner_impl!(x, x.local_name(), $($rest)*);
+                    $reader.read_to_end_into(x.name(), $buffer)?;
                     unimplemented!(
                         "`{}` in `{}`",
                         std::string::String::from_utf8_lossy(x.local_name().as_ref()),
@@ -37,6 +44,7 @@ macro_rules! parse_inner {
                 _ => continue,
             }
         }
+        *$buffer = events;
     })
 }
 
@@ -46,7 +54,9 @@ macro_rules! parse_inner_impl {
     ( $x:ident, $name:expr, ) => ();
     ( $x:ident, $name:expr, $e:ident @ $l:expr => $r:expr ) => (
         if $name.as_ref() == $l.as_ref() {
-            let $e = $x.clone().into_owned();
+            // borrowed from the events buffer: a handler needing the event
+            // to outlive the current iteration must call `into_owned`
+            let $e = $x.borrow();
             $r;
             continue;
         }
@@ -69,18 +79,21 @@ macro_rules! parse_inner_impl {
 
 #[allow(unused_macros)]
 macro_rules! parse_comment {
-    ( $event:ident, $reader:ident, $buffer:ident, $comment:ident ) => {
-        parse_comment!{$event, $reader, $buffer, $comment, }
+    ( $event:ident, $reader:ident, $buffer:ident, $options:ident, $comment:ident ) => {
+        parse_comment!{$event, $reader, $buffer, $options, $comment, }
     };
-    ( $event:ident, $reader:ident, $buffer:ident, $comment:ident, $($rest:tt)* ) => {
-        parse_inner!{$event, $reader, $buffer,
+    ( $event:ident, $reader:ident, $buffer:ident, $options:ident, $comment:ident, $($rest:tt)* ) => {
+        parse_inner!{$event, $reader, $buffer, $options,
             t @ b"text" => {
-                $comment.text.push(parse_text!(&t, $reader, $buffer));
+                $comment.text.push(EvidenceText::from_xml(&t, $reader, $buffer, $options)?);
             },
             m @ b"molecule" => {
-                $comment.molecule = Molecule::from_xml(&m, $reader, $buffer)
+                $comment.molecule = Molecule::from_xml(&m, $reader, $buffer, $options)
                     .map(Some)?;
             },
+            l @ b"location" => {
+                $comment.locations.push(FeatureLocation::from_xml(&l, $reader, $buffer, $options)?);
+            },
             $($rest)*
         }
     }
@@ -4,7 +4,7 @@
 //! the input, and another one performing everything in the main thread.
 //! The multithreaded parser is about twice as fast, but does not guarantee
 //! the `Entry` are yielded in the same order as they appear in the source
-//! XML file.
+//! XML file, unless created with [`ThreadedParser::ordered`].
 //!
 //! Some benchmarks results on an i7-8550U CPU running at 1.80GHz, where the
 //! baseline only collect [`quick-xml`] events without deserializing them into
@@ -17,6 +17,7 @@
 //! ```
 //!
 //! [`::uniprot`]: ../uniprot/index.html
+//! [`ThreadedParser::ordered`]: ./struct.ThreadedParser.html#method.ordered
 //! [`quick-xml`]: https://docs.rs/quick-xml
 
 pub(crate) mod utils;
@@ -25,11 +26,21 @@ pub(crate) mod utils;
 mod consumer;
 #[cfg(feature = "threading")]
 mod producer;
+#[cfg(feature = "async")]
+mod stream;
 #[macro_use]
 mod macros;
+mod stats;
 
+use std::collections::BTreeMap;
 use std::collections::HashSet;
 use std::io::BufRead;
+use std::io::Chain;
+use std::io::Cursor;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
 use std::num::NonZeroUsize;
 use std::str::FromStr;
 use std::sync::atomic::AtomicBool;
@@ -52,19 +63,152 @@ use quick_xml::events::BytesStart;
 use quick_xml::events::Event;
 use quick_xml::Error as XmlError;
 use quick_xml::Reader;
+use quick_xml::Writer;
 
+use super::any::PeekedReader;
+use super::common::ShortString;
 use super::error::Error;
+use super::uniprot::Dataset;
+use super::uniprot::Entry;
+use super::uniprot::UniProt;
 
 #[cfg(feature = "threading")]
 use self::consumer::Consumer;
 #[cfg(feature = "threading")]
 use self::producer::Producer;
+use self::utils::is_entry_end;
+
+pub use self::stats::ParseStats;
+pub use self::stats::StatsParser;
+#[cfg(feature = "async")]
+pub use self::stream::AsyncParser;
+pub use self::utils::ParseOptions;
 
 // ---------------------------------------------------------------------------
 
 #[allow(unused)]
 const SLEEP_DURATION: Duration = Duration::from_millis(10);
 
+#[cfg(feature = "threading")]
+/// The default maximum size of an entry read by the `ThreadedParser`.
+const DEFAULT_MAX_ENTRY_BYTES: usize = 64 * 1024 * 1024;
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The configuration of the underlying XML reader used by the parsers.
+///
+/// The same configuration is applied to the [`SequentialParser`] and to
+/// every worker of the [`ThreadedParser`], so that both parsers produce
+/// identical entries from the same input.
+///
+/// [`SequentialParser`]: ./struct.SequentialParser.html
+/// [`ThreadedParser`]: ./struct.ThreadedParser.html
+pub struct ParserConfig {
+    /// Whether to trim leading and trailing whitespace from text nodes.
+    pub trim_text: bool,
+    /// Whether to expand empty elements into a start and an end event.
+    ///
+    /// Entries are deserialized from start events, so disabling this will
+    /// cause empty elements (such as `<ligandPart/>`) to be ignored.
+    pub expand_empty_elements: bool,
+}
+
+impl ParserConfig {
+    /// Apply the configuration to the given XML reader.
+    pub(crate) fn configure<R>(&self, xml: &mut Reader<R>) {
+        xml.trim_text(self.trim_text)
+            .expand_empty_elements(self.expand_empty_elements);
+    }
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self {
+            trim_text: false,
+            expand_empty_elements: true,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// Release metadata found in a database file before the first entry.
+pub struct DatabaseMetadata {
+    /// The release of the database, from the `release` root attribute.
+    pub release: Option<ShortString>,
+    /// The version of the database, from the `version` root attribute.
+    pub version: Option<ShortString>,
+    /// The release date of the database, from the `releaseDate` root attribute.
+    pub release_date: Option<ShortString>,
+    /// The text of a `copyright` element preceding the first entry.
+    pub copyright: Option<ShortString>,
+}
+
+impl DatabaseMetadata {
+    /// Extract the metadata from the attributes of the root element.
+    fn from_root<B: BufRead>(event: &BytesStart, reader: &Reader<B>) -> Result<Self, Error> {
+        let mut metadata = Self::default();
+        for attr in event.attributes() {
+            let attr = attr?;
+            let field = match attr.key.local_name().as_ref() {
+                b"release" => &mut metadata.release,
+                b"version" => &mut metadata.version,
+                b"releaseDate" | b"releasedate" => &mut metadata.release_date,
+                _ => continue,
+            };
+            *field = Some(ShortString::from(attr.decode_and_unescape_value(reader)?));
+        }
+        Ok(metadata)
+    }
+
+    /// Check whether no metadata was found.
+    fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A position in a database file where parsing can be resumed.
+///
+/// Cursors are obtained from [`SequentialParser::cursor`] or
+/// [`ThreadedParser::cursor`], and can be stored to restart an interrupted
+/// job without parsing the entries that were already processed.
+///
+/// [`SequentialParser::cursor`]: ./struct.SequentialParser.html#method.cursor
+/// [`ThreadedParser::cursor`]: ./struct.ThreadedParser.html#method.cursor
+pub struct ParserCursor {
+    /// The byte offset of the position from the start of the reader.
+    pub offset: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+/// The mapping from the positions of a parsed reader to the source file.
+///
+/// A reader created by [`seek_entry`] starts with a synthetic prefix
+/// which is not part of the source file: positions inside the prefix are
+/// mapped to the start of the first entry, and the following positions
+/// are shifted to the offset of that entry in the source file.
+///
+/// [`seek_entry`]: ./fn.seek_entry.html
+pub(crate) struct Origin {
+    /// The length of the synthetic prefix of the reader.
+    prefix: u64,
+    /// The offset in the source file of the end of the synthetic prefix.
+    offset: u64,
+}
+
+impl Origin {
+    /// Get the position in the source file of a position of the reader.
+    fn position(&self, position: u64) -> u64 {
+        self.offset + position.saturating_sub(self.prefix)
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[cfg(feature = "threading")]
@@ -77,13 +221,30 @@ enum State {
     Finished,
 }
 
+#[cfg(feature = "threading")]
+/// An entry parsed by a consumer, tagged with its index in the source file
+/// and with the byte position of its end, or where the error was raised.
+type Item<E> = (usize, u64, Result<E, Error>);
+
 #[cfg(feature = "threading")]
 /// A parser for the Uniprot XML formats that parses entries in parallel.
 pub struct ThreadedParser<B: BufRead, D: UniprotDatabase> {
     state: State,
     producer: Producer<B>,
     consumers: Vec<Consumer<D>>,
-    r_item: Receiver<Result<D::Entry, Error>>,
+    r_item: Receiver<Item<D::Entry>>,
+    ordered: bool,
+    next_index: usize,
+    pending: BTreeMap<usize, (u64, Result<D::Entry, Error>)>,
+    yielded: BTreeMap<usize, u64>,
+    cursor_index: usize,
+    cursor: u64,
+    offset: u64,
+    origin: Origin,
+    error_position: Option<u64>,
+    options: ParseOptions,
+    reviewed_only: bool,
+    max_entry_bytes: usize,
 }
 
 #[cfg(feature = "threading")]
@@ -97,11 +258,20 @@ impl<B: BufRead + Send + 'static, D: UniprotDatabase> ThreadedParser<B, D> {
     ///
     /// [`num_cpus::get`]: https://docs.rs/num_cpus/1.12.0/num_cpus/fn.get.html
     pub fn new(reader: B) -> Self {
+        Self::with_config(reader, ParserConfig::default())
+    }
+
+    /// Create a new `ThreadedParser` using the given XML reader configuration.
+    ///
+    /// The number of threads is selected as in [`ThreadedParser::new`].
+    ///
+    /// [`ThreadedParser::new`]: #method.new
+    pub fn with_config(reader: B, config: ParserConfig) -> Self {
         lazy_static! {
             static ref THREADS: usize = num_cpus::get();
         }
         let threads = unsafe { NonZeroUsize::new_unchecked(*THREADS) };
-        Self::with_threads(reader, threads)
+        Self::with_options(reader, threads, threads, config)
     }
 
     /// Create a new `ThreadedParser` with the requested number of threads.
@@ -114,51 +284,78 @@ impl<B: BufRead + Send + 'static, D: UniprotDatabase> ThreadedParser<B, D> {
     /// [`SequentialParser`](./struct.SequentialParser.html) instead to keep
     /// everything in the main thread.
     pub fn with_threads(reader: B, threads: NonZeroUsize) -> Self {
+        Self::with_options(reader, threads, threads, ParserConfig::default())
+    }
+
+    /// Create a new `ThreadedParser` with the requested number of threads
+    /// and channel capacity.
+    ///
+    /// The capacity bounds the number of entries buffered between the
+    /// reading thread, the parsing threads and the main thread, and
+    /// defaults to the number of threads. A smaller capacity reduces the
+    /// memory used by the parser, while a larger one lets the threads run
+    /// further ahead when entries take uneven times to parse.
+    pub fn with_threads_and_capacity(
+        reader: B,
+        threads: NonZeroUsize,
+        capacity: NonZeroUsize,
+    ) -> Self {
+        Self::with_options(reader, threads, capacity, ParserConfig::default())
+    }
+
+    fn with_options(
+        reader: B,
+        threads: NonZeroUsize,
+        capacity: NonZeroUsize,
+        config: ParserConfig,
+    ) -> Self {
         let threads = threads.get();
         let mut buffer = Vec::new();
         let mut xml = Reader::from_reader(reader);
-        xml.expand_empty_elements(true);
+        config.configure(&mut xml);
 
         // create the communication channels
-        let (s_text, r_text) = crossbeam_channel::bounded(threads);
-        let (s_item, r_item) = crossbeam_channel::bounded(threads);
+        let (s_text, r_text) = crossbeam_channel::bounded(capacity.get());
+        let (s_item, r_item) = crossbeam_channel::bounded(capacity.get());
 
         // read until we enter the root element
-        loop {
+        let error = loop {
             buffer.clear();
             match xml.read_event_into(&mut buffer) {
                 Ok(Event::Start(e)) if D::ROOTS.contains(&e.local_name().as_ref()) => {
-                    break;
+                    break None;
                 }
                 Ok(Event::Start(e)) => {
                     let x = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
-                    s_item
-                        .send(Err(Error::UnexpectedRoot(x)))
-                        .expect("channel should still be connected");
-                    break;
+                    break Some(Error::UnexpectedRoot(x));
                 }
                 Err(e) => {
-                    s_item
-                        .send(Err(Error::from(e)))
-                        .expect("channel should still be connected");
-                    break;
+                    break Some(Error::from(e));
                 }
                 Ok(Event::Eof) => {
                     let e = String::from("xml");
-                    s_item
-                        .send(Err(Error::from(XmlError::UnexpectedEof(e))))
-                        .expect("channel should still be connected");
-                    break;
+                    break Some(Error::from(XmlError::UnexpectedEof(e)));
                 }
                 _ => (),
             }
+        };
+
+        // report the error, if any, as the first item
+        let mut index = 0;
+        if let Some(err) = error {
+            let position = xml.buffer_position() as u64;
+            s_item
+                .send((index, position, Err(err)))
+                .expect("channel should still be connected");
+            index += 1;
         }
 
         // create the worker threads
-        let producer = Producer::new(xml.into_inner(), threads, s_text);
+        let offset = xml.buffer_position() as u64;
+        let producer = Producer::new(xml.into_inner(), threads, index, offset, s_text);
         let mut consumers = Vec::with_capacity(threads);
         for _ in 0..threads {
-            let consumer = Consumer::new(r_text.clone(), s_item.clone());
+            let consumer = Consumer::new(r_text.clone(), s_item.clone(), config);
             consumers.push(consumer);
         }
 
@@ -168,7 +365,235 @@ impl<B: BufRead + Send + 'static, D: UniprotDatabase> ThreadedParser<B, D> {
             producer,
             consumers,
             state: State::Idle,
+            ordered: false,
+            next_index: 0,
+            pending: BTreeMap::new(),
+            yielded: BTreeMap::new(),
+            cursor_index: 0,
+            cursor: offset,
+            offset,
+            origin: Origin::default(),
+            error_position: None,
+            options: ParseOptions::default(),
+            reviewed_only: false,
+            max_entry_bytes: DEFAULT_MAX_ENTRY_BYTES,
+        }
+    }
+
+    /// Set the maximum size of a single entry, in bytes.
+    ///
+    /// The reading thread buffers the text of each entry before sending it
+    /// to the worker threads. When an entry grows beyond this limit without
+    /// being closed, as can happen with a truncated or corrupted file, an
+    /// [`Error::EntryTooLarge`] is yielded instead of buffering the rest of
+    /// the file. Defaults to 64 MiB, which is well above the size of the
+    /// largest UniProt entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the first entry was consumed, since the
+    /// reading thread would not see the new limit.
+    ///
+    /// [`Error::EntryTooLarge`]: ../error/enum.Error.html#variant.EntryTooLarge
+    pub fn max_entry_bytes(&mut self, max_entry_bytes: NonZeroUsize) -> &mut Self {
+        self.assert_idle("the entry size cannot be limited");
+        self.max_entry_bytes = max_entry_bytes.get();
+        self
+    }
+
+    /// Get the number of bytes read from the underlying reader so far.
+    ///
+    /// Since entries are read ahead of time by a dedicated thread, this
+    /// may be ahead of the position of the last entry yielded by the parser.
+    pub fn bytes_read(&self) -> u64 {
+        self.origin
+            .position(self.offset + self.producer.bytes_read())
+    }
+
+    /// Get a cursor to a position preceded only by entries already yielded.
+    ///
+    /// Since entries may be yielded out of order, the cursor follows the
+    /// last entry such that all the entries before it were yielded, which
+    /// may be behind the last entry yielded. Use [`ThreadedParser::ordered`]
+    /// to keep the cursor up to date with the entries yielded. See
+    /// [`SequentialParser::cursor`] for details.
+    ///
+    /// [`ThreadedParser::ordered`]: #method.ordered
+    /// [`SequentialParser::cursor`]: ./struct.SequentialParser.html#method.cursor
+    pub fn cursor(&self) -> ParserCursor {
+        ParserCursor {
+            offset: self.origin.position(self.cursor),
+        }
+    }
+
+    /// Use the given origin to report positions in the source file.
+    #[allow(unused)]
+    pub(crate) fn with_origin(mut self, origin: Origin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Wrap the parser to record statistics about the parsed entries.
+    ///
+    /// See [`StatsParser`] for details.
+    ///
+    /// [`StatsParser`]: ./struct.StatsParser.html
+    pub fn with_stats(self) -> StatsParser<Self> {
+        StatsParser::new(self)
+    }
+
+    /// Make the parser yield entries in the order of the source file.
+    ///
+    /// Entries parsed ahead of time by the worker threads are kept in a
+    /// reorder buffer until all the preceding entries have been yielded,
+    /// which costs some additional memory and latency.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the first entry was consumed, since the
+    /// entries already yielded out of order could not be reordered.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "threading")] {
+    /// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+    /// let mut parser = uniprot::uniprot::ThreadedParser::new(std::io::BufReader::new(f));
+    /// for entry in parser.ordered() {
+    ///     println!("{:?}", entry.unwrap().primary_accession());
+    /// }
+    /// # }
+    /// ```
+    pub fn ordered(&mut self) -> &mut Self {
+        self.assert_idle("entries cannot be ordered");
+        self.ordered = true;
+        self
+    }
+
+    /// Skip the elements with the given name when parsing entries.
+    ///
+    /// See [`SequentialParser::ignore`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the first entry was consumed, since the
+    /// worker threads would not see the new element.
+    ///
+    /// [`SequentialParser::ignore`]: ./struct.SequentialParser.html#method.ignore
+    pub fn ignore<K: Into<Vec<u8>>>(&mut self, element: K) -> &mut Self {
+        let options = self.options_mut("elements cannot be ignored");
+        Arc::make_mut(&mut options.ignored).insert(element.into());
+        self
+    }
+
+    /// Accept feature and comment types unknown to this version of the crate.
+    ///
+    /// See [`SequentialParser::lenient`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the first entry was consumed, since the
+    /// worker threads would not see the new mode.
+    ///
+    /// [`SequentialParser::lenient`]: ./struct.SequentialParser.html#method.lenient
+    pub fn lenient(&mut self) -> &mut Self {
+        self.options_mut("lenient mode cannot be enabled").lenient = true;
+        self
+    }
+
+    /// Get the options given to the worker threads, before they are started.
+    fn options_mut(&mut self, action: &str) -> &mut ParseOptions {
+        self.assert_idle(action);
+        &mut self.options
+    }
+
+    /// Check that the threads were not started, to configure the parser.
+    fn assert_idle(&self, action: &str) {
+        assert_eq!(
+            self.state,
+            State::Idle,
+            "{} once the parser has started",
+            action
+        );
+    }
+
+    /// Yield an item received from the consumers, unless it must wait
+    /// for the items preceding it to be yielded first.
+    fn receive(
+        &mut self,
+        index: usize,
+        position: u64,
+        item: Result<D::Entry, Error>,
+    ) -> Option<Result<D::Entry, Error>> {
+        if !self.ordered || index == self.next_index {
+            self.next_index += 1;
+            Some(self.record(index, position, item))
+        } else {
+            self.pending.insert(index, (position, item));
+            None
+        }
+    }
+
+    /// Record the position of an item before it is yielded.
+    fn record(
+        &mut self,
+        index: usize,
+        position: u64,
+        item: Result<D::Entry, Error>,
+    ) -> Result<D::Entry, Error> {
+        let item = item.map_err(|e| e.at(self.origin.position(position)));
+        if let Err(e) = &item {
+            self.error_position = e.position();
         }
+        // advance the cursor past all the items yielded without a gap
+        self.yielded.insert(index, position);
+        while let Some(position) = self.yielded.remove(&self.cursor_index) {
+            self.cursor = position;
+            self.cursor_index += 1;
+        }
+        item
+    }
+
+    /// Get the byte position of the error returned by the last call to `next`.
+    ///
+    /// The position is counted from the beginning of the reader given to
+    /// the parser, so an error raised before anything could be read is
+    /// reported at position `0`. Returns `None` if the last item was not
+    /// an error, or if the error is not tied to a position in the input,
+    /// such as an [`Error::DisconnectedChannel`].
+    ///
+    /// [`Error::DisconnectedChannel`]: ../error/enum.Error.html#variant.DisconnectedChannel
+    pub fn error_position(&self) -> Option<u64> {
+        self.error_position
+    }
+
+    /// Join all the threads, reporting an error if any of them panicked.
+    fn join(&mut self) -> Result<(), Error> {
+        let mut result = self.producer.join();
+        for consumer in &mut self.consumers {
+            result = result.and(consumer.join());
+        }
+        result.map_err(|_| Error::DisconnectedChannel)
+    }
+}
+
+#[cfg(feature = "threading")]
+impl<B: BufRead + Send + 'static> ThreadedParser<B, UniProt> {
+    /// Only yield the reviewed entries, skipping the TrEMBL ones.
+    ///
+    /// See [`SequentialParser::reviewed_only`] for details. Unreviewed
+    /// entries are skipped by the reading thread, before being sent to the
+    /// worker threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after the first entry was consumed, since the
+    /// reading thread would not see the new mode.
+    ///
+    /// [`SequentialParser::reviewed_only`]: ./struct.SequentialParser.html#method.reviewed_only
+    pub fn reviewed_only(&mut self) -> &mut Self {
+        self.assert_idle("unreviewed entries cannot be skipped");
+        self.reviewed_only = true;
+        self
     }
 }
 
@@ -176,28 +601,44 @@ impl<B: BufRead + Send + 'static, D: UniprotDatabase> ThreadedParser<B, D> {
 impl<B: BufRead + Send + 'static, D: UniprotDatabase> Iterator for ThreadedParser<B, D> {
     type Item = Result<D::Entry, Error>;
     fn next(&mut self) -> Option<Self::Item> {
+        self.error_position = None;
         loop {
+            // yield the next item if it was already received
+            let index = self.next_index;
+            if let Some((position, item)) = self.pending.remove(&index) {
+                self.next_index += 1;
+                return Some(self.record(index, position, item));
+            }
+
             match self.state {
                 State::Idle => {
                     self.state = State::Started;
-                    self.producer.start();
+                    self.producer
+                        .start(self.reviewed_only, self.max_entry_bytes);
                     for consumer in &mut self.consumers {
-                        consumer.start();
+                        consumer.start(self.options.clone());
                     }
                 }
                 State::Finished => return None,
                 State::Waiting => {
-                    self.producer.join().unwrap();
-                    for consumer in &mut self.consumers {
-                        consumer.join().unwrap();
-                    }
                     match self.r_item.try_recv() {
-                        // item is found: simply return it
-                        Ok(item) => return Some(item),
-                        // empty queue: check if the producer is finished
+                        // item is found: return it if it is not out of order
+                        Ok((index, position, item)) => {
+                            if let Some(item) = self.receive(index, position, item) {
+                                return Some(item);
+                            }
+                        }
+                        // empty queue: flush the remaining items, if any, but
+                        // report an error for the entries that were never
+                        // received since the threads are all finished
                         Err(TryRecvError::Empty) => {
-                            self.state = State::Finished;
-                            return None;
+                            if let Some(&index) = self.pending.keys().next() {
+                                self.next_index = index;
+                                return Some(Err(Error::DisconnectedChannel));
+                            } else {
+                                self.state = State::Finished;
+                                return None;
+                            }
                         }
                         // queue was disconnected: stop and return an error
                         Err(TryRecvError::Disconnected) => {
@@ -209,12 +650,23 @@ impl<B: BufRead + Send + 'static, D: UniprotDatabase> Iterator for ThreadedParse
                 State::Started => {
                     // poll for parsed entries to return
                     match self.r_item.recv_timeout(SLEEP_DURATION) {
-                        // item is found: simply return it
-                        Ok(item) => return Some(item),
-                        // empty queue: check if the producer is finished
+                        // item is found: return it if it is not out of order
+                        Ok((index, position, item)) => {
+                            if let Some(item) = self.receive(index, position, item) {
+                                return Some(item);
+                            }
+                        }
+                        // empty queue: check if all the threads are finished,
+                        // since consumers may still be blocked on sending
                         Err(RecvTimeoutError::Timeout) => {
-                            if !self.producer.is_alive() {
-                                self.state = State::Waiting
+                            if !self.producer.is_alive()
+                                && self.consumers.iter().all(|c| !c.is_alive())
+                            {
+                                self.state = State::Waiting;
+                                // a thread that panicked may have lost entries
+                                if let Err(e) = self.join() {
+                                    return Some(Err(e));
+                                }
                             }
                         }
                         // queue was disconnected: stop and return an error
@@ -242,23 +694,42 @@ pub struct SequentialParser<B: BufRead, D: UniprotDatabase> {
     cache: Option<<Self as Iterator>::Item>,
     finished: bool,
     root: Vec<u8>,
+    skip_errors: bool,
+    failure_position: Option<usize>,
+    error_position: Option<u64>,
+    metadata: Option<DatabaseMetadata>,
+    start: Option<BytesStart<'static>>,
+    start_offset: usize,
+    origin: Origin,
+    options: ParseOptions,
+    reviewed_only: bool,
 }
 
 impl<B: BufRead, D: UniprotDatabase> SequentialParser<B, D> {
     /// Create a new `SequentialParser` wrapping the given reader.
     pub fn new(reader: B) -> Self {
+        Self::with_config(reader, ParserConfig::default())
+    }
+
+    /// Create a new `SequentialParser` using the given XML reader configuration.
+    pub fn with_config(reader: B, config: ParserConfig) -> Self {
         let mut root = Vec::new();
         let mut buffer = Vec::new();
         let mut xml = Reader::from_reader(reader);
-        xml.expand_empty_elements(true);
+        config.configure(&mut xml);
 
         // read until we enter the `uniprot` element
-        let cache = loop {
+        let mut metadata = DatabaseMetadata::default();
+        let mut cache = loop {
             buffer.clear();
             match xml.read_event_into(&mut buffer) {
                 Err(e) => break Some(Err(Error::from(e))),
                 Ok(Event::Start(e)) if D::ROOTS.contains(&e.local_name().as_ref()) => {
                     root.extend(e.local_name().as_ref());
+                    match DatabaseMetadata::from_root(&e, &xml) {
+                        Ok(m) => metadata = m,
+                        Err(err) => break Some(Err(err)),
+                    }
                     break None;
                 }
                 Ok(Event::Start(e)) => {
@@ -273,25 +744,288 @@ impl<B: BufRead, D: UniprotDatabase> SequentialParser<B, D> {
             }
         };
 
+        // read until the first `entry` element, extracting the copyright
+        let mut start = None;
+        let mut start_offset = 0;
+        let mut finished = false;
+        while cache.is_none() && start.is_none() && !finished {
+            buffer.clear();
+            let offset = xml.buffer_position();
+            match xml.read_event_into(&mut buffer) {
+                Err(e) => cache = Some(Err(Error::from(e))),
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"entry" => {
+                    start = Some(e.into_owned());
+                    start_offset = offset;
+                }
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"copyright" => {
+                    let end = e.to_end().into_owned();
+                    match Self::read_text(&mut xml, &end, &mut buffer) {
+                        Ok(text) => metadata.copyright = Some(text),
+                        Err(err) => cache = Some(Err(err)),
+                    }
+                }
+                Ok(Event::End(e)) if e.local_name().as_ref() == root => {
+                    finished = true;
+                }
+                Ok(Event::Eof) => {
+                    let e = String::from("entry");
+                    cache = Some(Err(Error::from(XmlError::UnexpectedEof(e))));
+                }
+                _ => (),
+            }
+        }
+
         Self {
             xml,
             buffer,
             cache,
-            finished: false,
+            finished,
             root,
+            skip_errors: false,
+            failure_position: None,
+            error_position: None,
+            metadata: Some(metadata).filter(|m| !m.is_empty()),
+            start,
+            start_offset,
+            origin: Origin::default(),
+            options: ParseOptions::default(),
+            reviewed_only: false,
         }
     }
 
+    /// Create a `SequentialParser` that yields no entry from the given reader.
+    pub(crate) fn empty(reader: B) -> Self {
+        Self {
+            xml: Reader::from_reader(reader),
+            buffer: Vec::new(),
+            cache: None,
+            finished: true,
+            root: Vec::new(),
+            skip_errors: false,
+            failure_position: None,
+            error_position: None,
+            metadata: None,
+            start: None,
+            start_offset: 0,
+            origin: Origin::default(),
+            options: ParseOptions::default(),
+            reviewed_only: false,
+        }
+    }
+
+    /// Read the trimmed text content of an element until its end tag.
+    fn read_text(
+        xml: &mut Reader<B>,
+        end: &BytesEnd,
+        buffer: &mut Vec<u8>,
+    ) -> Result<ShortString, Error> {
+        let mut text = String::new();
+        loop {
+            buffer.clear();
+            match xml.read_event_into(buffer)? {
+                Event::Text(t) => text.push_str(&t.unescape()?),
+                Event::CData(t) => text.push_str(&String::from_utf8_lossy(&t)),
+                Event::End(e) if e.name() == end.name() => break,
+                Event::Eof => {
+                    let e = String::from_utf8_lossy(end.local_name().as_ref()).into_owned();
+                    return Err(Error::from(XmlError::UnexpectedEof(e)));
+                }
+                _ => (),
+            }
+        }
+        Ok(ShortString::from(text.trim()))
+    }
+
+    /// Get the release metadata found before the first entry, if any.
+    ///
+    /// The metadata is extracted from the attributes of the root element
+    /// and from a `copyright` element preceding the first entry. Files
+    /// without any of these, such as the responses of the UniProt REST API,
+    /// have no metadata.
+    pub fn metadata(&self) -> Option<&DatabaseMetadata> {
+        self.metadata.as_ref()
+    }
+
+    /// Recover from malformed entries instead of stopping at the first error.
+    ///
+    /// By default, the parser stops after yielding the first error it
+    /// encounters. In this mode, the error is still yielded once, but the
+    /// parser then resynchronizes on the next top-level `entry` element and
+    /// carries on, so that a single malformed entry does not prevent the
+    /// following ones from being parsed. Note that recovery skips anything
+    /// remaining between the point of failure and the next `entry` element.
+    ///
+    /// # Example
+    /// ```rust
+    /// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+    /// let mut parser = uniprot::uniprot::SequentialParser::new(std::io::BufReader::new(f));
+    /// for entry in parser.skip_errors().filter_map(Result::ok) {
+    ///     println!("{:?}", entry.primary_accession());
+    /// }
+    /// ```
+    pub fn skip_errors(&mut self) -> &mut Self {
+        self.skip_errors = true;
+        // the end tags of a malformed entry may be unbalanced, so they are
+        // only checked by the entry parsers themselves
+        self.xml.check_end_names(false);
+        self
+    }
+
+    /// Skip the elements with the given name when parsing entries.
+    ///
+    /// Ignored elements are skipped wherever they appear inside an entry,
+    /// without being deserialized, which can speed up parsing when only
+    /// some fields are needed. Ignoring an element required by its parent,
+    /// such as the `location` of a `feature`, causes the parent to fail
+    /// to parse.
+    ///
+    /// # Example
+    /// ```rust
+    /// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+    /// let mut parser = uniprot::uniprot::SequentialParser::new(std::io::BufReader::new(f));
+    /// parser.ignore("comment").ignore("reference");
+    /// for entry in parser {
+    ///     assert!(entry.unwrap().references.is_empty());
+    /// }
+    /// ```
+    pub fn ignore<K: Into<Vec<u8>>>(&mut self, element: K) -> &mut Self {
+        Arc::make_mut(&mut self.options.ignored).insert(element.into());
+        self
+    }
+
+    /// Accept feature and comment types unknown to this version of the crate.
+    ///
+    /// By default, an entry with a feature or a comment of an unknown type
+    /// fails to parse. In lenient mode, the type is instead stored in the
+    /// `Unknown` variant of `FeatureType` or `CommentType`, so that files
+    /// from newer UniProt releases can still be processed.
+    ///
+    /// # Example
+    /// ```rust
+    /// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+    /// let mut parser = uniprot::uniprot::SequentialParser::new(std::io::BufReader::new(f));
+    /// for entry in parser.lenient() {
+    ///     println!("{:?}", entry.unwrap().primary_accession());
+    /// }
+    /// ```
+    pub fn lenient(&mut self) -> &mut Self {
+        self.options.lenient = true;
+        self
+    }
+
+    /// Get the number of bytes read from the underlying reader so far.
+    ///
+    /// This can be compared to the total size of the input to report the
+    /// progress of the parser.
+    pub fn bytes_read(&self) -> u64 {
+        self.origin.position(self.xml.buffer_position() as u64)
+    }
+
+    /// Get the byte position of the error returned by the last call to `next`.
+    ///
+    /// The position is counted from the beginning of the reader given to
+    /// the parser, so an error raised before anything could be read is
+    /// reported at position `0`. Returns `None` if the last item was not
+    /// an error.
+    pub fn error_position(&self) -> Option<u64> {
+        self.error_position
+    }
+
+    /// Record the position of the error of a result, if any.
+    fn record<T>(&mut self, result: Result<T, Error>) -> Result<T, Error> {
+        let result = result.map_err(|e| e.at(self.bytes_read()));
+        self.error_position = result.as_ref().err().and_then(Error::position);
+        result
+    }
+
+    /// Wrap the parser to record statistics about the parsed entries.
+    ///
+    /// See [`StatsParser`] for details.
+    ///
+    /// [`StatsParser`]: ./struct.StatsParser.html
+    pub fn with_stats(self) -> StatsParser<Self> {
+        StatsParser::new(self)
+    }
+
+    /// Get a cursor to the position following the last entry yielded.
+    ///
+    /// The cursor can be saved, and later given to a `resume_from` function
+    /// such as [`uniprot::resume_from`] to continue parsing the same file
+    /// after the entries already yielded.
+    ///
+    /// [`uniprot::resume_from`]: ../uniprot/fn.resume_from.html
+    pub fn cursor(&self) -> ParserCursor {
+        let offset = match self.start {
+            Some(_) => self.start_offset,
+            None => self.xml.buffer_position(),
+        };
+        ParserCursor {
+            offset: self.origin.position(offset as u64),
+        }
+    }
+
+    /// Use the given origin to report positions in the source file.
+    #[allow(unused)]
+    pub(crate) fn with_origin(mut self, origin: Origin) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Skip the entry starting with the given event if it should not be parsed.
+    ///
+    /// Returns `true` if the entry was skipped.
+    fn skip_entry(&mut self, event: &BytesStart) -> Result<bool, Error> {
+        if self.reviewed_only && utils::is_unreviewed(event) {
+            let end = event.to_end().into_owned();
+            self.xml.read_to_end_into(end.name(), &mut self.buffer)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Parse an entry starting with the given event, unless it is skipped.
+    fn read_entry(&mut self, event: &BytesStart) -> Option<<Self as Iterator>::Item> {
+        match self.skip_entry(event) {
+            Ok(true) => return None,
+            Ok(false) => (),
+            Err(e) => return Some(self.fail(e)),
+        }
+        let (xml, buffer, options) = (&mut self.xml, &mut self.buffer, &self.options);
+        let result = D::Entry::from_xml(event, xml, buffer, options);
+        Some(result.or_else(|e| self.fail(e)))
+    }
+
+    /// Record an error, and stop the parser unless it can recover from it.
+    fn fail<T>(&mut self, error: Error) -> Result<T, Error> {
+        // stop if the reader did not move since the last error, since
+        // recovering would yield the same error indefinitely
+        let position = self.xml.buffer_position();
+        if !self.skip_errors || self.failure_position == Some(position) {
+            self.finished = true;
+        }
+        self.failure_position = Some(position);
+        Err(error)
+    }
+
     /// Parse a single entry from the given reader.
     pub fn parse_entry(reader: B) -> <Self as Iterator>::Item {
         let mut xml = Reader::from_reader(reader);
-        xml.expand_empty_elements(true);
+        ParserConfig::default().configure(&mut xml);
         let mut parser = Self {
             xml,
             buffer: Vec::new(),
             cache: None,
             finished: false,
             root: Vec::new(),
+            skip_errors: false,
+            failure_position: None,
+            error_position: None,
+            metadata: None,
+            start: None,
+            start_offset: 0,
+            origin: Origin::default(),
+            options: ParseOptions::default(),
+            reviewed_only: false,
         };
 
         parser.next().unwrap_or_else(|| {
@@ -301,25 +1035,121 @@ impl<B: BufRead, D: UniprotDatabase> SequentialParser<B, D> {
     }
 }
 
-impl<B: BufRead, D: UniprotDatabase> Iterator for SequentialParser<B, D> {
-    type Item = Result<D::Entry, Error>;
-    fn next(&mut self) -> Option<Self::Item> {
+impl<B: BufRead> SequentialParser<B, UniProt> {
+    /// Only yield the reviewed entries, skipping the TrEMBL ones.
+    ///
+    /// The `dataset` attribute of each entry is checked before the entry
+    /// is deserialized, so that unreviewed entries are skipped cheaply in
+    /// files mixing both datasets. Entries without a `dataset` attribute
+    /// are not skipped, and fail to parse as usual.
+    ///
+    /// # Example
+    /// ```rust
+    /// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+    /// let mut parser = uniprot::uniprot::SequentialParser::new(std::io::BufReader::new(f));
+    /// for entry in parser.reviewed_only() {
+    ///     let entry = entry.unwrap();
+    ///     assert!(matches!(entry.dataset, uniprot::uniprot::Dataset::SwissProt));
+    /// }
+    /// ```
+    pub fn reviewed_only(&mut self) -> &mut Self {
+        self.reviewed_only = true;
+        self
+    }
+
+    /// Call a function on every entry, reusing a single `Entry` value.
+    ///
+    /// Contrary to iterating over the parser, which yields a new `Entry`
+    /// for every element, this method parses each element into the same
+    /// `Entry` and only lends it to the callback, so that the vectors of
+    /// the entry are cleared rather than reallocated between iterations.
+    /// Iteration stops at the first error, either from the parser or from
+    /// the callback, which is then returned; the position of a parser error
+    /// is then available from [`SequentialParser::error_position`].
+    ///
+    /// The parser is borrowed rather than consumed, so that it can still be
+    /// inspected after an error. A parser error stops the parser as when
+    /// iterating, unless [`SequentialParser::skip_errors`] was called, in
+    /// which case calling `for_each_ref` again resumes at the next entry.
+    ///
+    /// [`SequentialParser::error_position`]: #method.error_position
+    /// [`SequentialParser::skip_errors`]: #method.skip_errors
+    ///
+    /// # Example
+    /// ```rust
+    /// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+    /// let mut parser = uniprot::uniprot::SequentialParser::new(std::io::BufReader::new(f));
+    /// let mut features = 0;
+    /// parser
+    ///     .for_each_ref(|entry| {
+    ///         features += entry.features.len();
+    ///         Ok(())
+    ///     })
+    ///     .unwrap();
+    /// assert!(features > 0);
+    /// ```
+    pub fn for_each_ref<F>(&mut self, mut f: F) -> Result<(), Error>
+    where
+        F: FnMut(&Entry) -> Result<(), Error>,
+    {
+        let mut entry = Entry::new(Dataset::SwissProt);
+        if let Some(item) = self.cache.take() {
+            f(&self.record(item)?)?;
+        }
+        while let Some(event) = self.next_start() {
+            let event = self.record(event)?;
+            let skip = self.skip_entry(&event).or_else(|e| self.fail(e));
+            if self.record(skip)? {
+                continue;
+            }
+            let (xml, buffer, options) = (&mut self.xml, &mut self.buffer, &self.options);
+            let result = entry.read_xml(&event, xml, buffer, options);
+            let result = result.or_else(|e| self.fail(e));
+            self.record(result)?;
+            f(&entry)?;
+        }
+        Ok(())
+    }
+}
+
+impl<B: BufRead, D: UniprotDatabase> SequentialParser<B, D> {
+    /// Get the next item, without recording the position of an error.
+    fn next_item(&mut self) -> Option<<Self as Iterator>::Item> {
         // return cached item if any
         if let Some(item) = self.cache.take() {
             return Some(item);
         }
 
+        // parse entries until one is not skipped
+        loop {
+            let event = match self.next_start()? {
+                Ok(event) => event,
+                Err(e) => return Some(Err(e)),
+            };
+            if let Some(item) = self.read_entry(&event) {
+                return Some(item);
+            }
+        }
+    }
+
+    /// Enter the next `entry` element, and get its start event.
+    fn next_start(&mut self) -> Option<Result<BytesStart<'static>, Error>> {
         // if finished, simply return `None`
         if self.finished {
             return None;
         }
 
+        // use the first entry if it was entered during construction
+        if let Some(event) = self.start.take() {
+            return Some(Ok(event));
+        }
+
         // enter the next `entry` element
         loop {
             self.buffer.clear();
             match self.xml.read_event_into(&mut self.buffer) {
                 // if an error is raised, return it
-                Err(e) => return Some(Err(Error::from(e))),
+                Err(e) => return Some(self.fail(Error::from(e))),
                 // error if reaching EOF
                 Ok(Event::Eof) => {
                     let e = String::from("entry");
@@ -327,17 +1157,13 @@ impl<B: BufRead, D: UniprotDatabase> Iterator for SequentialParser<B, D> {
                     return Some(Err(Error::from(XmlError::UnexpectedEof(e))));
                 }
                 // if end of `uniprot` is reached, return no further item
-                Ok(Event::End(ref e)) if e.local_name().as_ref() == &self.root => {
+                Ok(Event::End(ref e)) if e.local_name().as_ref() == self.root => {
                     self.finished = true;
                     return None;
                 }
-                // create a new Entry
+                // return the start of the entry
                 Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"entry" => {
-                    return Some(D::Entry::from_xml(
-                        &e.clone().into_owned(),
-                        &mut self.xml,
-                        &mut self.buffer,
-                    ));
+                    return Some(Ok(e.clone().into_owned()));
                 }
                 _ => (),
             }
@@ -345,21 +1171,241 @@ impl<B: BufRead, D: UniprotDatabase> Iterator for SequentialParser<B, D> {
     }
 }
 
+impl<B: BufRead, D: UniprotDatabase> Iterator for SequentialParser<B, D> {
+    type Item = Result<D::Entry, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.next_item()?;
+        Some(self.record(item))
+    }
+}
+
 #[cfg(not(feature = "threading"))]
 /// The parser type for the crate, used by `uniprot::parse`.
 pub type Parser<B, D> = SequentialParser<B, D>;
 
 // ---------------------------------------------------------------------------
 
+/// A parser reading entries from several XML files in sequence.
+///
+/// Each reader is parsed with its own [`Parser`], created only once the
+/// previous reader is exhausted, so that entries from all the files are
+/// yielded as a single stream. Readers that contain no data at all are
+/// skipped.
+///
+/// [`Parser`]: ./type.Parser.html
+pub struct ChainParser<I, D>
+where
+    I: Iterator,
+    I::Item: BufRead + Send + 'static,
+    D: UniprotDatabase,
+{
+    readers: I,
+    current: Option<Parser<I::Item, D>>,
+}
+
+impl<I, D> ChainParser<I, D>
+where
+    I: Iterator,
+    I::Item: BufRead + Send + 'static,
+    D: UniprotDatabase,
+{
+    /// Create a new `ChainParser` over the given readers.
+    pub fn new<R: IntoIterator<IntoIter = I>>(readers: R) -> Self {
+        Self {
+            readers: readers.into_iter(),
+            current: None,
+        }
+    }
+}
+
+impl<I, D> Iterator for ChainParser<I, D>
+where
+    I: Iterator,
+    I::Item: BufRead + Send + 'static,
+    D: UniprotDatabase,
+{
+    type Item = Result<D::Entry, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(parser) = self.current.as_mut() {
+                match parser.next() {
+                    Some(item) => return Some(item),
+                    None => self.current = None,
+                }
+            }
+
+            let mut reader = self.readers.next()?;
+            match reader.fill_buf() {
+                Ok([]) => (),
+                Ok(_) => self.current = Some(Parser::new(reader)),
+                Err(e) => return Some(Err(Error::from(e))),
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// Count the entries of a database file without deserializing them.
+///
+/// This only counts the occurrences of the `</entry>` closing tag, with or
+/// without a namespace prefix, in the raw bytes, which is much faster than
+/// parsing the file, and can be used to get the total number of entries
+/// before processing a file, for instance to display a progress bar. It
+/// works with any of the UniProt XML formats.
+///
+/// Since the XML is not actually parsed, a `</entry>` string inside a
+/// comment, a `CDATA` section or an escaped text would be counted as well;
+/// this does not happen in files distributed by UniProt.
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let count = uniprot::count_entries(std::io::BufReader::new(f)).unwrap();
+/// assert_eq!(count, 250);
+/// ```
+pub fn count_entries<B: BufRead>(mut reader: B) -> Result<usize, Error> {
+    // the local name of the closing tag, and the number of bytes kept
+    // from the previous chunk to check the tags split between chunks
+    const END: &[u8] = b"entry>";
+    const TAIL: usize = 64;
+
+    let finder = memchr::memmem::Finder::new(END);
+    let mut count = 0;
+    let mut window = Vec::with_capacity(TAIL);
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            return Ok(count);
+        }
+
+        // only count the tags ending in this chunk, since the others were
+        // already counted with the previous chunk
+        let n = window.len();
+        window.extend_from_slice(chunk);
+        count += finder
+            .find_iter(&window)
+            .filter(|&i| i + END.len() > n && is_entry_end(&window[..i]))
+            .count();
+
+        // keep the end of the data read so far for the next chunk
+        window.drain(..window.len().saturating_sub(TAIL));
+
+        let length = chunk.len();
+        reader.consume(length);
+    }
+}
+
+/// Seek a reader to the first entry starting at or after the given cursor.
+///
+/// The returned reader starts with a copy of the opening tag of the root
+/// element of the file, followed by the first entry found, so that it can
+/// be given to a parser as if it was a complete database file. If no entry
+/// is found, the returned reader only contains an empty root element. The
+/// returned [`Origin`] maps the positions of the returned reader to the
+/// positions of the source file.
+///
+/// [`Origin`]: ./struct.Origin.html
+pub(crate) fn seek_entry<B: BufRead + Seek>(
+    mut reader: B,
+    cursor: ParserCursor,
+    root: &str,
+) -> Result<(PeekedReader<B>, Origin), Error> {
+    let (root_start, root_end) = root_tags(&mut reader, root)?;
+    reader.seek(SeekFrom::Start(cursor.offset))?;
+
+    let mut position = cursor.offset;
+    let mut tag = Vec::new();
+    let (prefix, origin) = loop {
+        // skip everything until the beginning of a tag
+        tag.clear();
+        position += reader.read_until(b'<', &mut tag)? as u64;
+        if !tag.ends_with(b"<") {
+            break empty_root(root_start, &root_end, position);
+        }
+
+        // read the tag, and stop if it opens an entry
+        let start = position - 1;
+        tag.clear();
+        tag.push(b'<');
+        let n = reader.read_until(b'>', &mut tag)?;
+        position += n as u64;
+        if n == 0 {
+            break empty_root(root_start, &root_end, position);
+        }
+        if utils::entry_end_tag(&tag).is_some() {
+            let mut prefix = root_start;
+            let origin = Origin {
+                prefix: prefix.len() as u64,
+                offset: start,
+            };
+            prefix.extend_from_slice(&tag);
+            break (prefix, origin);
+        }
+    };
+
+    Ok((Cursor::new(prefix).chain(reader), origin))
+}
+
+/// Get the raw opening and closing tags of the root element of a file.
+///
+/// The opening tag is copied with its namespace prefix and its attributes,
+/// so that the namespace declarations still apply to the entries following
+/// it. A `root` element is used instead if the file has no root element.
+fn root_tags<B: BufRead + Seek>(reader: &mut B, root: &str) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    reader.seek(SeekFrom::Start(0))?;
+    let mut xml = Reader::from_reader(reader);
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        match xml.read_event_into(&mut buffer)? {
+            Event::Start(e) | Event::Empty(e) => {
+                let mut start = Vec::with_capacity(e.len() + 2);
+                start.push(b'<');
+                start.extend_from_slice(&e);
+                start.push(b'>');
+                let mut end = Vec::with_capacity(e.name().as_ref().len() + 3);
+                end.extend_from_slice(b"</");
+                end.extend_from_slice(e.name().as_ref());
+                end.push(b'>');
+                return Ok((start, end));
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+    let start = format!("<{}>", root).into_bytes();
+    let end = format!("</{}>", root).into_bytes();
+    Ok((start, end))
+}
+
+/// Get an empty root element standing for the end of a file at `position`.
+fn empty_root(mut prefix: Vec<u8>, end: &[u8], position: u64) -> (Vec<u8>, Origin) {
+    prefix.extend_from_slice(end);
+    let origin = Origin {
+        prefix: prefix.len() as u64,
+        offset: position,
+    };
+    (prefix, origin)
+}
+
+// ---------------------------------------------------------------------------
+
 /// A trait for types that can be parsed from an XML element.
 pub trait FromXml: Sized {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error>;
 }
 
+/// A trait for types that can be written as an XML element.
+pub trait ToXml {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error>;
+}
+
 /// A trait for UniProt databases.
 pub trait UniprotDatabase {
     type Entry: FromXml + Send + 'static;
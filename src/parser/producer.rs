@@ -1,8 +1,9 @@
 use std::collections::HashSet;
 use std::io::BufRead;
+use std::io::Read;
 use std::num::NonZeroUsize;
 use std::str::FromStr;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -11,8 +12,15 @@ use std::time::Duration;
 use crossbeam_channel::Sender;
 use quick_xml::Error as XmlError;
 
+use super::utils::entry_end_tag;
+use super::utils::is_unreviewed_tag;
 use crate::error::Error;
 
+/// The text of an entry, tagged with its index and byte position in the source file.
+///
+/// Errors are tagged with the byte position where they were raised.
+pub(super) type Buffer = (usize, Result<(u64, Vec<u8>), (u64, Error)>);
+
 #[cfg(feature = "threading")]
 #[derive(Debug, PartialEq, Eq)]
 /// The state of the `Producer`.
@@ -26,8 +34,10 @@ enum State {
 pub struct Producer<B> {
     reader: Option<B>,
     threads: usize,
-    s_text: Sender<Option<Result<Vec<u8>, Error>>>,
-    alive: Arc<AtomicBool>,
+    index: usize,
+    offset: u64,
+    s_text: Sender<Option<Buffer>>,
+    bytes_read: Arc<AtomicU64>,
     handle: Option<std::thread::JoinHandle<()>>,
 }
 
@@ -35,31 +45,45 @@ impl<B: BufRead + Send + 'static> Producer<B> {
     pub(super) fn new(
         reader: B,
         threads: usize,
-        s_text: Sender<Option<Result<Vec<u8>, Error>>>,
+        index: usize,
+        offset: u64,
+        s_text: Sender<Option<Buffer>>,
     ) -> Self {
         Self {
             reader: Some(reader),
             s_text,
             threads,
+            index,
+            offset,
             handle: None,
-            alive: Arc::new(AtomicBool::new(false)),
+            bytes_read: Arc::new(AtomicU64::new(0)),
         }
     }
 
-    pub fn start(&mut self) {
-        self.alive.store(true, Ordering::SeqCst);
-
-        let alive = self.alive.clone();
+    pub fn start(&mut self, reviewed_only: bool, max_entry_bytes: usize) {
+        let bytes_read = self.bytes_read.clone();
         let threads = self.threads;
+        let mut index = self.index;
+        let mut position = self.offset;
         let s_text = self.s_text.clone();
         let mut reader = self.reader.take().unwrap();
 
         self.handle = Some(std::thread::spawn(move || {
             let mut buffer = Vec::new();
+            let mut end_tag = Vec::new();
+            let mut start = position;
+            let mut skip = false;
             let mut state = State::Started;
             loop {
                 match state {
-                    State::Started => match reader.read_until(b'>', &mut buffer) {
+                    // fail rather than growing the buffer indefinitely
+                    // if an entry or a tag is never closed
+                    State::Started | State::Reading if buffer.len() >= max_entry_bytes => {
+                        let err = (start, Error::EntryTooLarge(max_entry_bytes));
+                        s_text.send(Some((index, Err(err)))).ok();
+                        state = State::Finished;
+                    }
+                    State::Started => match read_tag(&mut reader, &mut buffer, max_entry_bytes) {
                         // we reached EOF, but that's okay, we were not
                         // reading an entry;
                         Ok(0) => {
@@ -67,51 +91,76 @@ impl<B: BufRead + Send + 'static> Producer<B> {
                         }
                         // we found the beginning of an entry, now we
                         // must read the entire entry until the end.
-                        Ok(_) => {
-                            let i = memchr::memrchr(b'<', &buffer).unwrap();
-                            if buffer[i..].starts_with(b"<entry") {
-                                state = State::Reading;
+                        Ok(n) => {
+                            bytes_read.fetch_add(n as u64, Ordering::Relaxed);
+                            position += n as u64;
+                            match memchr::memrchr(b'<', &buffer) {
+                                Some(i) => {
+                                    start = position - (buffer.len() - i) as u64;
+                                    if let Some(end) = entry_end_tag(&buffer[i..]) {
+                                        end_tag = end;
+                                        skip = reviewed_only && is_unreviewed_tag(&buffer[i..]);
+                                        buffer.drain(..i);
+                                        state = State::Reading;
+                                    } else if buffer.ends_with(b">") {
+                                        buffer.clear();
+                                    }
+                                }
+                                None => buffer.clear(),
                             }
                         }
                         // if an error is encountered, send it and bail out
                         Err(e) => {
-                            s_text.send(Some(Err(Error::from(e)))).ok();
+                            let err = (position, Error::from(e));
+                            s_text.send(Some((index, Err(err)))).ok();
                             state = State::Finished;
                         }
                     },
                     State::Reading => {
                         // read until the end of the entry.
-                        match reader.read_until(b'>', &mut buffer) {
-                            // if a full entry is found, send it
-                            Ok(_) if buffer.ends_with(&b"</entry>"[..]) => {
-                                s_text.send(Some(Ok(buffer.as_slice().to_vec()))).ok();
+                        match read_tag(&mut reader, &mut buffer, max_entry_bytes) {
+                            // if a full entry is found, send it unless it
+                            // must be skipped
+                            Ok(n) if buffer.ends_with(&end_tag) => {
+                                bytes_read.fetch_add(n as u64, Ordering::Relaxed);
+                                position += n as u64;
+                                if !skip {
+                                    s_text
+                                        .send(Some((
+                                            index,
+                                            Ok((start, buffer.as_slice().to_vec())),
+                                        )))
+                                        .ok();
+                                    index += 1;
+                                }
                                 state = State::Started;
                                 buffer.clear();
                             }
                             // if we reach EOF before finding the end of the
                             // entry, that's an issue, we report an error.
                             Ok(0) => {
-                                s_text
-                                    .send(Some(Err(Error::from(XmlError::UnexpectedEof(
-                                        String::from("entry"),
-                                    )))))
-                                    .ok();
+                                let e = String::from("entry");
+                                let err = (position, Error::from(XmlError::UnexpectedEof(e)));
+                                s_text.send(Some((index, Err(err)))).ok();
                                 state = State::Finished;
                             }
                             // if an error is encountered, send it and bail out
                             Err(e) => {
-                                s_text.send(Some(Err(Error::from(e)))).ok();
+                                let err = (position, Error::from(e));
+                                s_text.send(Some((index, Err(err)))).ok();
                                 state = State::Finished;
                             }
                             // otherwise just keep iterating.
-                            _ => (),
+                            Ok(n) => {
+                                bytes_read.fetch_add(n as u64, Ordering::Relaxed);
+                                position += n as u64;
+                            }
                         }
                     }
                     State::Finished => {
                         for _ in 0..threads {
                             s_text.send(None).ok();
                         }
-                        alive.store(false, Ordering::SeqCst);
                         break;
                     }
                 }
@@ -127,7 +176,23 @@ impl<B: BufRead + Send + 'static> Producer<B> {
         }
     }
 
+    /// Check whether the thread is still running, even if it panicked.
     pub fn is_alive(&self) -> bool {
-        self.alive.load(Ordering::SeqCst)
+        self.handle.as_ref().is_some_and(|h| !h.is_finished())
     }
+
+    /// Get the number of bytes read from the reader so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read.load(Ordering::Relaxed)
+    }
+}
+
+/// Read until the end of the next tag, without exceeding `max` bytes in total.
+fn read_tag<B: BufRead>(
+    reader: &mut B,
+    buffer: &mut Vec<u8>,
+    max: usize,
+) -> std::io::Result<usize> {
+    let limit = max.saturating_sub(buffer.len()) as u64;
+    reader.by_ref().take(limit).read_until(b'>', buffer)
 }
@@ -0,0 +1,142 @@
+//! Runtime statistics of a parser.
+
+use std::io::BufRead;
+use std::time::Duration;
+use std::time::Instant;
+
+use super::SequentialParser;
+#[cfg(feature = "threading")]
+use super::ThreadedParser;
+use super::UniprotDatabase;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Statistics about the entries parsed by a [`StatsParser`].
+///
+/// [`StatsParser`]: ./struct.StatsParser.html
+pub struct ParseStats {
+    /// The number of entries successfully parsed.
+    pub entries: usize,
+    /// The number of bytes read from the underlying reader.
+    pub bytes: u64,
+    /// The time elapsed since the first entry was requested.
+    pub elapsed: Duration,
+}
+
+impl ParseStats {
+    /// Get the number of entries parsed per second.
+    ///
+    /// Returns `0.0` if no time has elapsed yet.
+    pub fn entries_per_second(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            secs if secs > 0.0 => self.entries as f64 / secs,
+            _ => 0.0,
+        }
+    }
+
+    /// Get the number of bytes read per second.
+    ///
+    /// Returns `0.0` if no time has elapsed yet.
+    pub fn bytes_per_second(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            secs if secs > 0.0 => self.bytes as f64 / secs,
+            _ => 0.0,
+        }
+    }
+}
+
+/// A parser wrapper recording statistics about the parsed entries.
+///
+/// The timer starts when the first entry is requested, and stops once the
+/// wrapped parser is exhausted, so that the statistics can be compared
+/// between parser configurations.
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let mut parser = uniprot::uniprot::parse(std::io::BufReader::new(f)).with_stats();
+/// for entry in &mut parser {
+///     entry.unwrap();
+/// }
+/// let stats = parser.stats();
+/// println!("{} entries/s, {} B/s", stats.entries_per_second(), stats.bytes_per_second());
+/// ```
+pub struct StatsParser<P> {
+    parser: P,
+    entries: usize,
+    start: Option<Instant>,
+    elapsed: Option<Duration>,
+}
+
+impl<P> StatsParser<P> {
+    /// Wrap the given parser.
+    pub(super) fn new(parser: P) -> Self {
+        Self {
+            parser,
+            entries: 0,
+            start: None,
+            elapsed: None,
+        }
+    }
+
+    /// Get a reference to the wrapped parser.
+    pub fn get_ref(&self) -> &P {
+        &self.parser
+    }
+
+    /// Unwrap the parser, discarding the statistics.
+    pub fn into_inner(self) -> P {
+        self.parser
+    }
+
+    /// Build the statistics with the given number of bytes read.
+    fn stats_with_bytes(&self, bytes: u64) -> ParseStats {
+        let elapsed = match (self.elapsed, self.start) {
+            (Some(elapsed), _) => elapsed,
+            (None, Some(start)) => start.elapsed(),
+            (None, None) => Duration::default(),
+        };
+        ParseStats {
+            entries: self.entries,
+            bytes,
+            elapsed,
+        }
+    }
+}
+
+impl<B: BufRead, D: UniprotDatabase> StatsParser<SequentialParser<B, D>> {
+    /// Get the statistics of the parser so far.
+    pub fn stats(&self) -> ParseStats {
+        self.stats_with_bytes(self.parser.bytes_read())
+    }
+}
+
+#[cfg(feature = "threading")]
+impl<B: BufRead + Send + 'static, D: UniprotDatabase> StatsParser<ThreadedParser<B, D>> {
+    /// Get the statistics of the parser so far.
+    ///
+    /// The number of bytes may be ahead of the entries yielded so far, see
+    /// [`ThreadedParser::bytes_read`].
+    ///
+    /// [`ThreadedParser::bytes_read`]: ./struct.ThreadedParser.html#method.bytes_read
+    pub fn stats(&self) -> ParseStats {
+        self.stats_with_bytes(self.parser.bytes_read())
+    }
+}
+
+impl<T, E, P: Iterator<Item = Result<T, E>>> Iterator for StatsParser<P> {
+    type Item = Result<T, E>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.start.get_or_insert_with(Instant::now);
+        let item = self.parser.next();
+        match &item {
+            Some(Ok(_)) => self.entries += 1,
+            Some(Err(_)) => (),
+            None => {
+                if self.elapsed.is_none() {
+                    self.elapsed = self.start.map(|start| start.elapsed());
+                }
+            }
+        }
+        item
+    }
+}
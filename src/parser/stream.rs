@@ -0,0 +1,185 @@
+//! Asynchronous parser implementation, using [`tokio`] for I/O.
+//!
+//! [`tokio`]: https://docs.rs/tokio
+
+use std::io::Cursor;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures_core::Stream;
+use quick_xml::events::Event;
+use quick_xml::Error as XmlError;
+use quick_xml::Reader;
+use tokio::io::AsyncBufRead;
+
+use super::utils::entry_end_tag;
+use super::FromXml;
+use super::ParseOptions;
+use super::ParserConfig;
+use super::UniprotDatabase;
+use crate::error::Error;
+
+/// A parser for the Uniprot XML formats that reads entries asynchronously.
+///
+/// The reader is consumed asynchronously, one `entry` element at a time,
+/// and each complete element is then deserialized synchronously with the
+/// same [`FromXml`] implementation as the blocking parsers. Like them, the
+/// stream yields [`Error::UnexpectedRoot`] if the root element does not
+/// belong to the database.
+pub struct AsyncParser<B, D> {
+    reader: B,
+    buffer: Vec<u8>,
+    root: bool,
+    end_tag: Option<Vec<u8>>,
+    position: u64,
+    finished: bool,
+    error_position: Option<u64>,
+    database: PhantomData<fn() -> D>,
+}
+
+impl<B: AsyncBufRead + Unpin, D: UniprotDatabase> AsyncParser<B, D> {
+    /// Create a new `AsyncParser` wrapping the given reader.
+    pub fn new(reader: B) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            root: false,
+            end_tag: None,
+            position: 0,
+            finished: false,
+            error_position: None,
+            database: PhantomData,
+        }
+    }
+
+    /// Get the byte position of the error yielded by the last polled item.
+    ///
+    /// The position is counted from the beginning of the reader given to
+    /// the parser. Returns `None` if the last item was not an error.
+    pub fn error_position(&self) -> Option<u64> {
+        self.error_position
+    }
+
+    /// Record an error that prevents reading any further entry.
+    fn fail(&mut self, position: u64, error: Error) -> Poll<Option<Result<D::Entry, Error>>> {
+        self.finished = true;
+        self.error_position = Some(position);
+        Poll::Ready(Some(Err(error.at(position))))
+    }
+}
+
+impl<B: AsyncBufRead + Unpin, D: UniprotDatabase> Stream for AsyncParser<B, D> {
+    type Item = Result<D::Entry, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.finished {
+            return Poll::Ready(None);
+        }
+
+        this.error_position = None;
+        loop {
+            // read until the end of the next tag
+            let available = match Pin::new(&mut this.reader).poll_fill_buf(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(e)) => return this.fail(this.position, Error::from(e)),
+                Poll::Ready(Ok(available)) => available,
+            };
+            if available.is_empty() {
+                let e = match this.end_tag {
+                    None if this.root => {
+                        this.finished = true;
+                        return Poll::Ready(None);
+                    }
+                    None => String::from("xml"),
+                    Some(_) => String::from("entry"),
+                };
+                return this.fail(this.position, Error::from(XmlError::UnexpectedEof(e)));
+            }
+            let (n, complete) = match memchr::memchr(b'>', available) {
+                Some(i) => (i + 1, true),
+                None => (available.len(), false),
+            };
+            this.buffer.extend_from_slice(&available[..n]);
+            Pin::new(&mut this.reader).consume(n);
+            this.position += n as u64;
+            if !complete {
+                continue;
+            }
+
+            match &this.end_tag {
+                // skip everything until the beginning of an entry
+                None => {
+                    if let Some(i) = memchr::memrchr(b'<', &this.buffer) {
+                        let tag = &this.buffer[i..];
+                        if !this.root {
+                            // check the root element like the blocking parsers
+                            match start_tag_name(tag) {
+                                Some(name) if D::ROOTS.contains(&name) => this.root = true,
+                                Some(name) => {
+                                    let x = String::from_utf8_lossy(name).into_owned();
+                                    let start = this.position - tag.len() as u64;
+                                    return this.fail(start, Error::UnexpectedRoot(x));
+                                }
+                                None => (),
+                            }
+                        } else if let Some(end) = entry_end_tag(tag) {
+                            this.buffer.drain(..i);
+                            this.end_tag = Some(end);
+                            continue;
+                        }
+                    }
+                    this.buffer.clear();
+                }
+                // parse the entry once it was read entirely
+                Some(end) if this.buffer.ends_with(end) => {
+                    let start = this.position - this.buffer.len() as u64;
+                    let item = parse_entry::<D>(&this.buffer);
+                    this.buffer.clear();
+                    this.end_tag = None;
+                    return Poll::Ready(Some(item.map_err(|(offset, e)| {
+                        this.error_position = Some(start + offset);
+                        e.at(start + offset)
+                    })));
+                }
+                Some(_) => (),
+            }
+        }
+    }
+}
+
+/// Get the local name of an opening tag, or `None` for any other markup.
+fn start_tag_name(tag: &[u8]) -> Option<&[u8]> {
+    let tag = tag.strip_prefix(b"<")?;
+    if matches!(tag.first(), Some(b'?' | b'!' | b'/')) {
+        return None;
+    }
+    let n = tag
+        .iter()
+        .position(|c| matches!(c, b' ' | b'\t' | b'\r' | b'\n' | b'/' | b'>'))?;
+    let name = &tag[..n];
+    name.rsplit(|&c| c == b':').next()
+}
+
+/// Parse a single entry from the text of an `entry` element.
+///
+/// Errors are returned along with their byte position in the text.
+fn parse_entry<D: UniprotDatabase>(text: &[u8]) -> Result<D::Entry, (u64, Error)> {
+    let mut buffer = Vec::new();
+    let mut xml = Reader::from_reader(Cursor::new(text));
+    ParserConfig::default().configure(&mut xml);
+    let result = match xml.read_event_into(&mut buffer) {
+        Ok(Event::Start(s)) if s.local_name().as_ref() == b"entry" => {
+            let options = ParseOptions::default();
+            D::Entry::from_xml(&s.into_owned(), &mut xml, &mut buffer, &options)
+        }
+        Ok(_) => {
+            let e = String::from("entry");
+            Err(Error::from(XmlError::UnexpectedEof(e)))
+        }
+        Err(e) => Err(Error::from(e)),
+    };
+    result.map_err(|e| (xml.buffer_position() as u64, e))
+}
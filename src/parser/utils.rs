@@ -1,15 +1,121 @@
+use std::collections::HashSet;
 use std::io::BufRead;
+use std::io::Write;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use quick_xml::events::attributes::Attribute;
 use quick_xml::events::BytesStart;
+use quick_xml::events::BytesText;
+use quick_xml::events::Event;
 use quick_xml::Error as XmlError;
 use quick_xml::Reader;
+use quick_xml::Writer;
 
 use super::Error;
 
 // -----------------------------------------------------------------------
 
+#[derive(Debug, Default, Clone)]
+/// The options of a parser changing how entries are deserialized.
+///
+/// The options are owned by each parser, and given to its worker threads
+/// if any. They are passed to [`FromXml::from_xml`] along with the reader
+/// and the event buffer, and read by the types that depend on them.
+///
+/// [`FromXml::from_xml`]: ./trait.FromXml.html#tymethod.from_xml
+pub struct ParseOptions {
+    /// The local names of the elements to skip.
+    pub(crate) ignored: Arc<HashSet<Vec<u8>>>,
+    /// Whether to accept feature and comment types unknown to the crate.
+    pub(crate) lenient: bool,
+}
+
+impl ParseOptions {
+    /// Check whether elements with the given local name should be skipped.
+    pub fn is_ignored(&self, name: &[u8]) -> bool {
+        !self.ignored.is_empty() && self.ignored.contains(name)
+    }
+
+    /// Check whether unknown feature or comment types should be accepted.
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+}
+
+// -----------------------------------------------------------------------
+
+/// Get the raw closing tag matching a raw `entry` opening tag.
+///
+/// The opening tag may have a namespace prefix (e.g. `<up:entry ...>`), in
+/// which case the closing tag has the same prefix. Returns `None` if the
+/// tag does not open an `entry` element.
+pub fn entry_end_tag(tag: &[u8]) -> Option<Vec<u8>> {
+    let tag = tag.strip_prefix(b"<")?;
+    let n = tag
+        .iter()
+        .position(|c| matches!(c, b' ' | b'\t' | b'\r' | b'\n' | b'>'))?;
+    let name = &tag[..n];
+    let local = name.rsplit(|&c| c == b':').next().unwrap_or(name);
+    if local != b"entry" {
+        return None;
+    }
+    let mut end = Vec::with_capacity(name.len() + 3);
+    end.extend_from_slice(b"</");
+    end.extend_from_slice(name);
+    end.push(b'>');
+    Some(end)
+}
+
+/// Check whether an `entry` opening tag has the unreviewed `TrEMBL` dataset.
+///
+/// Entries without a `dataset` attribute are not considered unreviewed, so
+/// that they still fail to parse afterwards.
+pub fn is_unreviewed(event: &BytesStart) -> bool {
+    matches!(
+        extract_attribute(event, "dataset"),
+        Ok(Some(attr)) if attr.value.as_ref() == b"TrEMBL"
+    )
+}
+
+/// Check whether a raw `entry` opening tag has the unreviewed `TrEMBL` dataset.
+#[cfg(feature = "threading")]
+pub fn is_unreviewed_tag(tag: &[u8]) -> bool {
+    let content = match tag.strip_prefix(b"<").and_then(|t| t.strip_suffix(b">")) {
+        Some(content) => content,
+        None => return false,
+    };
+    let name_len = content
+        .iter()
+        .position(|c| matches!(c, b' ' | b'\t' | b'\r' | b'\n'))
+        .unwrap_or(content.len());
+    match std::str::from_utf8(content) {
+        Ok(content) => is_unreviewed(&BytesStart::from_content(content, name_len)),
+        Err(_) => false,
+    }
+}
+
+/// Check whether the raw text before an `entry>` string ends a closing tag.
+///
+/// This accepts both `</` and a namespace prefix such as `</up:`.
+pub fn is_entry_end(before: &[u8]) -> bool {
+    if before.ends_with(b"</") {
+        return true;
+    }
+    let before = match before.strip_suffix(b":") {
+        Some(before) => before,
+        None => return false,
+    };
+    let n = before
+        .iter()
+        .rev()
+        .take_while(|c| !matches!(c, b'<' | b'>' | b'/' | b':' | b' ' | b'\t' | b'\r' | b'\n'))
+        .count();
+    n > 0 && before[..before.len() - n].ends_with(b"</")
+}
+
+// -----------------------------------------------------------------------
+
 pub fn extract_attribute<'a>(
     event: &'a BytesStart<'a>,
     name: &str,
@@ -21,7 +127,7 @@ pub fn extract_attribute<'a>(
             r.is_err()
                 || r.as_ref()
                     .ok()
-                    .map_or(false, |a| a.key.as_ref() == name.as_bytes())
+                    .is_some_and(|a| a.key.as_ref() == name.as_bytes())
         })
         .transpose()
         .map_err(Error::from)
@@ -59,7 +165,7 @@ pub fn decode_attribute<'a, B: BufRead, T: FromStr>(
 
         // perform decoding only on error, since valid enum variants
         // can only be obtained from valid UTF-8 anyway.
-        let s = std::str::from_utf8_unchecked(&*a.value);
+        let s = std::str::from_utf8_unchecked(&a.value);
         T::from_str(s).map_err(|_| match a.decode_and_unescape_value(reader) {
             Ok(s) => Error::invalid_value(name, element, s),
             Err(e) => Error::from(e),
@@ -78,7 +184,7 @@ pub fn decode_opt_attribute<'a, B: BufRead, T: FromStr>(
         unsafe {
             // perform decoding only on error, since valid enum variants
             // can only be obtained from valid UTF-8 anyway.
-            let s = std::str::from_utf8_unchecked(&*a.value);
+            let s = std::str::from_utf8_unchecked(&a.value);
             match T::from_str(s) {
                 Ok(x) => Ok(Some(x)),
                 Err(_) => match a.decode_and_unescape_value(reader) {
@@ -91,3 +197,50 @@ pub fn decode_opt_attribute<'a, B: BufRead, T: FromStr>(
         Ok(None)
     }
 }
+
+// -----------------------------------------------------------------------
+
+/// Add the `evidence` attribute to `event` if any evidence is given.
+pub fn push_evidences(event: &mut BytesStart, evidences: &[usize]) {
+    if !evidences.is_empty() {
+        let keys = evidences
+            .iter()
+            .map(usize::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        event.push_attribute(("evidence", keys.as_str()));
+    }
+}
+
+/// Write an element with the given start tag, using `inner` for the content.
+pub fn write_element<W, F>(writer: &mut Writer<W>, event: BytesStart, inner: F) -> Result<(), Error>
+where
+    W: Write,
+    F: FnOnce(&mut Writer<W>) -> Result<(), Error>,
+{
+    writer.write_event(Event::Start(event.borrow()))?;
+    inner(writer)?;
+    writer.write_event(Event::End(event.to_end()))?;
+    Ok(())
+}
+
+/// Write an element with the given start tag containing only `text`.
+pub fn write_text_element<W: Write>(
+    writer: &mut Writer<W>,
+    event: BytesStart,
+    text: &str,
+) -> Result<(), Error> {
+    write_element(writer, event, |w| {
+        w.write_event(Event::Text(BytesText::new(text)))?;
+        Ok(())
+    })
+}
+
+/// Write an empty element with the given start tag.
+pub fn write_empty_element<W: Write>(
+    writer: &mut Writer<W>,
+    event: BytesStart,
+) -> Result<(), Error> {
+    writer.write_event(Event::Empty(event))?;
+    Ok(())
+}
@@ -1,20 +1,30 @@
 //! Data types for the UniParc database.
 
+#[cfg(feature = "parser")]
 use std::io::BufRead;
 
+#[cfg(feature = "parser")]
+use crate::error::Error;
+
 mod model;
 
 #[doc(inline)]
 pub use self::model::*;
 
 /// The sequential parser type for UniParc entries.
+#[cfg(feature = "parser")]
 pub type SequentialParser<B> = super::parser::SequentialParser<B, UniParc>;
 
 #[cfg(feature = "threading")]
 /// The threaded parser type for UniParc entries.
 pub type ThreadedParser<B> = super::parser::ThreadedParser<B, UniParc>;
 
+#[cfg(feature = "async")]
+/// The asynchronous parser type for UniParc entries.
+pub type AsyncParser<B> = super::parser::AsyncParser<B, UniParc>;
+
 /// The parser type for UniParc entries.
+#[cfg(feature = "parser")]
 pub type Parser<B> = super::parser::Parser<B, UniParc>;
 
 /// Parse a UniParc database XML file.
@@ -30,10 +40,36 @@ pub type Parser<B> = super::parser::Parser<B, UniParc>;
 ///
 /// println!("{:#?}", parser.next())
 /// ```
+#[cfg(feature = "parser")]
 pub fn parse<B: BufRead + Send + 'static>(reader: B) -> Parser<B> {
     Parser::new(reader)
 }
 
+/// Parse a UniParc database XML file from an asynchronous reader.
+///
+/// The returned stream yields the sequence archive entries of a UniParc
+/// file with a `uniparc` root element, along with their cross-references
+/// and signature sequence matches, as soon as each of them was received.
+///
+/// # Example
+/// ```rust
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use futures_util::StreamExt;
+///
+/// let f = tokio::fs::File::open("tests/uniparc.xml").await.unwrap();
+/// let stream = uniprot::uniparc::parse_async(tokio::io::BufReader::new(f));
+/// let entries = stream.collect::<Vec<_>>().await;
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub fn parse_async<B>(reader: B) -> AsyncParser<B>
+where
+    B: tokio::io::AsyncBufRead + Unpin,
+{
+    AsyncParser::new(reader)
+}
+
 /// Parse a single UniParc entry.
 ///
 /// This method is compatible with responses from the
@@ -49,14 +85,18 @@ pub fn parse<B: BufRead + Send + 'static>(reader: B) -> Parser<B> {
 ///
 /// println!("{:?}", entry);
 /// ```
+#[cfg(feature = "parser")]
 pub fn parse_entry<B: BufRead>(reader: B) -> <SequentialParser<B> as Iterator>::Item {
     SequentialParser::parse_entry(reader)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "parser"))]
 mod tests {
 
+    use std::collections::HashSet;
+
     use super::*;
+    use crate::common::ShortString;
     use crate::error::Error;
     use quick_xml::Error as XmlError;
 
@@ -69,6 +109,188 @@ mod tests {
         assert_eq!(entries.len(), 64);
     }
 
+    #[test]
+    fn sequence_whitespace() {
+        let f = std::fs::File::open("tests/uniparc.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        for entry in &entries {
+            assert_eq!(entry.sequence.sequence.len(), entry.sequence.length);
+            assert!(!entry.sequence.sequence.contains(char::is_whitespace));
+        }
+
+        let txt = "<uniparc><entry dataset=\"uniparc\"><accession>UPI0000000001</accession><sequence length=\"4\" checksum=\"76F4826B7009DFAF\">\n  MK\n  LV \n</sequence></entry></uniparc>";
+        let entry = super::parse_entry(std::io::Cursor::new(txt)).unwrap();
+        assert_eq!(entry.sequence.sequence, "MKLV");
+        assert_eq!(entry.sequence.length, 4);
+
+        let entry =
+            super::parse_entry(std::io::Cursor::new(txt.replace("\"4\"", "\"5\""))).unwrap();
+        assert_eq!(entry.sequence.length, 4);
+        let err =
+            super::parse_entry(std::io::Cursor::new(txt.replace("length=\"4\" ", ""))).unwrap_err();
+        match err.into_inner() {
+            Error::MissingAttribute("length", "sequence") => (),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parse_db_references() {
+        let f = std::fs::File::open("tests/uniparc.xml").unwrap();
+        let entries = super::parse(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+
+        let db_references = entries
+            .iter()
+            .flat_map(|entry| entry.db_references.iter())
+            .collect::<Vec<_>>();
+        let active = db_references.iter().filter(|db| db.active).count();
+        assert_eq!(active, 14675);
+        assert_eq!(db_references.len() - active, 4515);
+
+        let properties = db_references
+            .iter()
+            .map(|db| db.properties.len())
+            .sum::<usize>();
+        assert_eq!(properties, 59928);
+    }
+
+    #[test]
+    fn db_reference_database() {
+        let f = std::fs::File::open("tests/uniparc.xml").unwrap();
+        let entries = super::parse(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+
+        let databases = entries
+            .iter()
+            .flat_map(|entry| entry.db_references.iter())
+            .map(|db| db.database())
+            .collect::<HashSet<_>>();
+        let expected = [
+            Database::Embl,
+            Database::EmblCon,
+            Database::EmblTpa,
+            Database::EmblTsa,
+            Database::EmblWgs,
+            Database::Ensembl,
+            Database::EnsemblBacteria,
+            Database::EnsemblFungi,
+            Database::EnsemblMetazoa,
+            Database::EnsemblPlants,
+            Database::Epo,
+            Database::FlyBase,
+            Database::HInvDb,
+            Database::Ipi,
+            Database::Jpo,
+            Database::Kipo,
+            Database::Patric,
+            Database::Pdb,
+            Database::Pir,
+            Database::PirArc,
+            Database::Prf,
+            Database::RefSeq,
+            Database::RemTrEmbl,
+            Database::Seed,
+            Database::Sgd,
+            Database::SwissProt,
+            Database::SwissProtIsoforms,
+            Database::Tair,
+            Database::TrEmbl,
+            Database::TrEmblNew,
+            Database::Trome,
+            Database::Uspto,
+            Database::Vega,
+        ];
+        assert_eq!(databases, expected.iter().cloned().collect());
+        assert!(databases
+            .iter()
+            .all(|db| Database::from(db.as_str()) == *db));
+
+        let db = Database::from("NewDB");
+        assert_eq!(db, Database::Other(ShortString::from("NewDB")));
+        assert_eq!(db.as_str(), "NewDB");
+    }
+
+    #[test]
+    fn parse_signature_sequence_matches() {
+        let f = std::fs::File::open("tests/uniparc.xml").unwrap();
+        let entries = super::parse(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+
+        let matches = entries
+            .iter()
+            .flat_map(|entry| entry.signature_sequence_matches.iter())
+            .collect::<Vec<_>>();
+        assert_eq!(matches.len(), 287);
+
+        let first = &matches[0];
+        assert_eq!(first.database, "Pfam");
+        assert_eq!(first.id, "PF04713");
+        let interpro = first.interpro().expect("match should have an ipr");
+        assert_eq!(interpro.id, "IPR006803");
+        assert_eq!(interpro.name, "Poxvirus I5");
+        assert_eq!(first.locations.len(), 1);
+        assert_eq!(first.locations[0].start, 3);
+        assert_eq!(first.locations[0].end, 75);
+        assert_eq!(first.locations[0].alignment, None);
+    }
+
+    #[test]
+    fn parse_signature_sequence_match_locations() {
+        let txt = &b"<uniparc><entry dataset=\"uniparc\"><accession>UPI0000000001</accession><signatureSequenceMatch database=\"Pfam\" id=\"PF00001\"><lcn start=\"1\" end=\"10\"/><lcn start=\"20\" end=\"30\" alignment=\"10M\"/></signatureSequenceMatch><sequence length=\"4\" checksum=\"76F4826B7009DFAF\">MKLV</sequence></entry></uniparc>"[..];
+        let entry = SequentialParser::new(std::io::Cursor::new(txt))
+            .next()
+            .expect("an entry should be parsed")
+            .expect("the entry should be parsed successfully");
+
+        let sigseq = &entry.signature_sequence_matches[0];
+        assert_eq!(sigseq.database, "Pfam");
+        assert_eq!(sigseq.id, "PF00001");
+        assert!(sigseq.interpro().is_none());
+        assert_eq!(sigseq.locations.len(), 2);
+        assert_eq!(sigseq.locations[1].start, 20);
+        assert_eq!(sigseq.locations[1].end, 30);
+        assert_eq!(sigseq.locations[1].alignment.as_deref(), Some("10M"));
+    }
+
+    #[test]
+    fn fail_invalid_active() {
+        let txt = &b"<uniparc><entry dataset=\"uniparc\"><accession>UPI0000000001</accession><dbReference type=\"EMBL\" id=\"CAA00001\" version_i=\"1\" active=\"maybe\"/>"[..];
+        let err = SequentialParser::new(std::io::Cursor::new(txt))
+            .next()
+            .expect("should raise an error")
+            .unwrap_err();
+        match err.into_inner() {
+            Error::InvalidValue("active", "dbReference", _) => (),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn dataset() {
+        let f = std::fs::File::open("tests/uniparc.xml").unwrap();
+        let entries = super::parse(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert!(entries.iter().all(|e| e.dataset == "uniparc"));
+
+        let txt = "<uniparc><entry dataset=\"UniParc\"><accession>UPI0000000001</accession><sequence length=\"4\" checksum=\"76F4826B7009DFAF\">MKLV</sequence></entry></uniparc>";
+        let entry = super::parse_entry(std::io::Cursor::new(txt)).unwrap();
+        assert_eq!(entry.dataset, "uniparc");
+
+        let txt = txt.replace("UniParc", "uniprot");
+        let err = super::parse_entry(std::io::Cursor::new(txt)).unwrap_err();
+        match err.into_inner() {
+            Error::InvalidValue("dataset", "entry", v) => assert_eq!(v.0, "uniprot"),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
     mod sequential {
         use super::*;
 
@@ -88,7 +310,7 @@ mod tests {
                 .next()
                 .expect("should raise an error")
                 .unwrap_err();
-            match err {
+            match err.into_inner() {
                 Error::Xml(XmlError::UnexpectedEof(_)) => (),
                 other => panic!("unexpected error: {:?}", other),
             }
@@ -101,7 +323,7 @@ mod tests {
                 .next()
                 .expect("should raise an error")
                 .unwrap_err();
-            match err {
+            match err.into_inner() {
                 Error::UnexpectedRoot(r) => assert_eq!(r, "something"),
                 other => panic!("unexpected error: {:?}", other),
             }
@@ -128,7 +350,7 @@ mod tests {
                 .next()
                 .expect("should raise an error")
                 .unwrap_err();
-            match err {
+            match err.into_inner() {
                 Error::Xml(XmlError::UnexpectedEof(_)) => (),
                 other => panic!("unexpected error: {:?}", other),
             }
@@ -141,7 +363,7 @@ mod tests {
                 .next()
                 .expect("should raise an error")
                 .unwrap_err();
-            match err {
+            match err.into_inner() {
                 Error::UnexpectedRoot(r) => assert_eq!(r, "something"),
                 other => panic!("unexpected error: {:?}", other),
             }
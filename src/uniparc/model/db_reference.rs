@@ -1,39 +1,71 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_opt_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
 
 use super::Date;
 use super::Property;
 
 #[derive(Debug, Clone)]
+/// A cross-reference to a source database of a UniParc sequence.
 pub struct DbReference {
     // attributes
     pub ty: ShortString,
     pub id: ShortString,
+    /// The version of the sequence in the source database, as tracked by UniParc.
     pub version_i: usize,
-    pub active: ShortString,
+    /// Whether the sequence is still present in the source database.
+    pub active: bool,
+    /// The version of the sequence in the source database, if any.
     pub version: Option<usize>,
+    /// The date the cross-reference was first seen by UniParc.
     pub created: Option<Date>,
+    /// The date the cross-reference was last seen by UniParc.
     pub last: Option<Date>,
     // fields
     pub properties: Vec<Property>,
 }
 
+impl DbReference {
+    /// Get the source database of the cross-reference.
+    ///
+    /// Database types unknown to this crate are returned as
+    /// [`Database::Other`].
+    ///
+    /// [`Database::Other`]: ./enum.Database.html#variant.Other
+    pub fn database(&self) -> Database {
+        Database::from(self.ty.as_str())
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for DbReference {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"dbReference");
 
@@ -50,15 +82,20 @@ impl FromXml for DbReference {
             .ok_or(Error::MissingAttribute("id", "dbReference"))?
             .decode_and_unescape_value(reader)
             .map(ShortString::from)?;
-        let active = extract_attribute(event, "active")?
+        let active = match extract_attribute(event, "active")?
             .ok_or(Error::MissingAttribute("active", "dbReference"))?
-            .decode_and_unescape_value(reader)
-            .map(ShortString::from)?;
+            .decode_and_unescape_value(reader)?
+            .as_ref()
+        {
+            "Y" => true,
+            "N" => false,
+            other => return Err(Error::invalid_value("active", "dbReference", other)),
+        };
 
         let mut properties = Vec::new();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"property" => {
-                properties.push(FromXml::from_xml(&e, reader, buffer)?);
+                properties.push(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
@@ -74,3 +111,131 @@ impl FromXml for DbReference {
         })
     }
 }
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A source database cross-referenced by UniParc.
+pub enum Database {
+    Embl,
+    EmblCon,
+    EmblTpa,
+    EmblTsa,
+    EmblWgs,
+    Ensembl,
+    EnsemblBacteria,
+    EnsemblFungi,
+    EnsemblMetazoa,
+    EnsemblPlants,
+    EnsemblProtists,
+    Epo,
+    FlyBase,
+    HInvDb,
+    Ipi,
+    Jpo,
+    Kipo,
+    Patric,
+    Pdb,
+    Pir,
+    PirArc,
+    Prf,
+    RefSeq,
+    RemTrEmbl,
+    Seed,
+    Sgd,
+    SwissProt,
+    SwissProtIsoforms,
+    Tair,
+    TrEmbl,
+    TrEmblNew,
+    Trome,
+    Uspto,
+    Vega,
+    /// A database type unknown to this crate.
+    Other(ShortString),
+}
+
+impl Database {
+    /// Get the string representation of the database, as found in the `type` attribute.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Database::Embl => "EMBL",
+            Database::EmblCon => "EMBL_CON",
+            Database::EmblTpa => "EMBL_TPA",
+            Database::EmblTsa => "EMBL_TSA",
+            Database::EmblWgs => "EMBLWGS",
+            Database::Ensembl => "Ensembl",
+            Database::EnsemblBacteria => "EnsemblBacteria",
+            Database::EnsemblFungi => "EnsemblFungi",
+            Database::EnsemblMetazoa => "EnsemblMetazoa",
+            Database::EnsemblPlants => "EnsemblPlants",
+            Database::EnsemblProtists => "EnsemblProtists",
+            Database::Epo => "EPO",
+            Database::FlyBase => "FlyBase",
+            Database::HInvDb => "H-InvDB",
+            Database::Ipi => "IPI",
+            Database::Jpo => "JPO",
+            Database::Kipo => "KIPO",
+            Database::Patric => "PATRIC",
+            Database::Pdb => "PDB",
+            Database::Pir => "PIR",
+            Database::PirArc => "PIRARC",
+            Database::Prf => "PRF",
+            Database::RefSeq => "RefSeq",
+            Database::RemTrEmbl => "REMTREMBL",
+            Database::Seed => "SEED",
+            Database::Sgd => "SGD",
+            Database::SwissProt => "UniProtKB/Swiss-Prot",
+            Database::SwissProtIsoforms => "UniProtKB/Swiss-Prot protein isoforms",
+            Database::Tair => "TAIR",
+            Database::TrEmbl => "UniProtKB/TrEMBL",
+            Database::TrEmblNew => "TREMBLNEW",
+            Database::Trome => "TROME",
+            Database::Uspto => "USPTO",
+            Database::Vega => "VEGA",
+            Database::Other(s) => s,
+        }
+    }
+}
+
+impl From<&str> for Database {
+    fn from(s: &str) -> Self {
+        match s {
+            "EMBL" => Database::Embl,
+            "EMBL_CON" => Database::EmblCon,
+            "EMBL_TPA" => Database::EmblTpa,
+            "EMBL_TSA" => Database::EmblTsa,
+            "EMBLWGS" => Database::EmblWgs,
+            "Ensembl" => Database::Ensembl,
+            "EnsemblBacteria" => Database::EnsemblBacteria,
+            "EnsemblFungi" => Database::EnsemblFungi,
+            "EnsemblMetazoa" => Database::EnsemblMetazoa,
+            "EnsemblPlants" => Database::EnsemblPlants,
+            "EnsemblProtists" => Database::EnsemblProtists,
+            "EPO" => Database::Epo,
+            "FlyBase" => Database::FlyBase,
+            "H-InvDB" => Database::HInvDb,
+            "IPI" => Database::Ipi,
+            "JPO" => Database::Jpo,
+            "KIPO" => Database::Kipo,
+            "PATRIC" => Database::Patric,
+            "PDB" => Database::Pdb,
+            "PIR" => Database::Pir,
+            "PIRARC" => Database::PirArc,
+            "PRF" => Database::Prf,
+            "RefSeq" => Database::RefSeq,
+            "REMTREMBL" => Database::RemTrEmbl,
+            "SEED" => Database::Seed,
+            "SGD" => Database::Sgd,
+            "UniProtKB/Swiss-Prot" => Database::SwissProt,
+            "UniProtKB/Swiss-Prot protein isoforms" => Database::SwissProtIsoforms,
+            "TAIR" => Database::Tair,
+            "UniProtKB/TrEMBL" => Database::TrEmbl,
+            "TREMBLNEW" => Database::TrEmblNew,
+            "TROME" => Database::Trome,
+            "USPTO" => Database::Uspto,
+            "VEGA" => Database::Vega,
+            other => Database::Other(ShortString::from(other)),
+        }
+    }
+}
@@ -1,34 +1,46 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
 
 #[derive(Debug, Clone)]
+/// A reference to an InterPro entry.
 pub struct InterproReference {
     pub name: ShortString,
     pub id: ShortString,
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for InterproReference {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"ipr");
 
         let name = extract_attribute(event, "name")?
-            .ok_or(Error::MissingAttribute("name", "signatureSequenceMatch"))?
+            .ok_or(Error::MissingAttribute("name", "ipr"))?
             .decode_and_unescape_value(reader)
             .map(ShortString::from)?;
         let id = extract_attribute(event, "id")?
-            .ok_or(Error::MissingAttribute("id", "signatureSequenceMatch"))?
+            .ok_or(Error::MissingAttribute("id", "ipr"))?
             .decode_and_unescape_value(reader)
             .map(ShortString::from)?;
 
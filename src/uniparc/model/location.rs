@@ -1,36 +1,66 @@
+#[cfg(feature = "parser")]
 use std::io::BufRead;
 
+use crate::common::prelude::*;
+use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
+use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
 
 #[derive(Debug, Clone)]
+/// The location of a signature match on a sequence.
 pub struct Location {
+    /// The position of the first residue of the match, starting from 1.
     pub start: u64,
+    /// The position of the last residue of the match, inclusive.
     pub end: u64,
+    /// The alignment of the match, if any.
+    pub alignment: Option<ShortString>,
 }
 
 impl Location {
     pub fn new(start: u64, end: u64) -> Self {
-        Self { start, end }
+        Self::with_alignment(start, end, None)
+    }
+
+    pub fn with_alignment(start: u64, end: u64, alignment: Option<ShortString>) -> Self {
+        Self {
+            start,
+            end,
+            alignment,
+        }
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Location {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"lcn");
 
         let start = decode_attribute(event, reader, "start", "lcn")?;
         let end = decode_attribute(event, reader, "end", "lcn")?;
+        let alignment = extract_attribute(event, "alignment")?
+            .map(|a| a.decode_and_unescape_value(reader).map(ShortString::from))
+            .transpose()?;
         reader.read_to_end_into(event.name(), buffer)?;
 
-        Ok(Location::new(start, end))
+        Ok(Location::with_alignment(start, end, alignment))
     }
 }
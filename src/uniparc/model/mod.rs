@@ -12,27 +12,39 @@ mod ipr;
 mod location;
 mod sigseq;
 
+pub use self::db_reference::Database;
 pub use self::db_reference::DbReference;
 pub use self::ipr::InterproReference;
 pub use self::location::Location;
 pub use self::sigseq::SignatureSequenceMatch;
 pub use crate::common::date::Date;
+pub use crate::common::date::DatePrecision;
 pub use crate::common::property::Property;
 pub use crate::common::sequence::Sequence;
 
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use core::iter::FromIterator;
+use core::ops::Deref;
+use core::ops::DerefMut;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::iter::FromIterator;
-use std::ops::Deref;
-use std::ops::DerefMut;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
 use crate::parser::UniprotDatabase;
 
 // ---------------------------------------------------------------------------
@@ -41,6 +53,7 @@ use crate::parser::UniprotDatabase;
 /// A UniParc entry.
 pub struct Entry {
     // attributes
+    /// The dataset of the entry, always `uniparc`.
     pub dataset: ShortString,
     // fields
     pub accession: ShortString,
@@ -49,37 +62,44 @@ pub struct Entry {
     pub sequence: Sequence,
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Entry {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"entry");
 
+        // the only dataset allowed by the schema is `uniparc`, but the
+        // case is normalized since it carries no information
         let dataset = extract_attribute(event, "dataset")?
             .ok_or(Error::MissingAttribute("dataset", "entry"))?
-            .decode_and_unescape_value(reader)
-            .map(ShortString::from)?;
+            .decode_and_unescape_value(reader)?;
+        if !dataset.eq_ignore_ascii_case("uniparc") {
+            return Err(Error::invalid_value("dataset", "entry", dataset));
+        }
+        let dataset = ShortString::from("uniparc");
 
         let mut accession = None;
         let mut sequence = None;
         let mut db_references = Vec::new();
         let mut signature_sequence_matches = Vec::new();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"accession" => {
                 if accession.replace(parse_text!(e, reader, buffer)).is_some() {
                     return Err(Error::DuplicateElement("accession", "entry"));
                 }
             },
             e @ b"dbReference" => {
-                db_references.push(FromXml::from_xml(&e, reader, buffer)?);
+                db_references.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"signatureSequenceMatch" => {
-                signature_sequence_matches.push(FromXml::from_xml(&e, reader, buffer)?);
+                signature_sequence_matches.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"sequence" => {
-                if sequence.replace(FromXml::from_xml(&e, reader, buffer)?).is_some() {
+                if sequence.replace(FromXml::from_xml(&e, reader, buffer, options)?).is_some() {
                     return Err(Error::DuplicateElement("sequence", "entry"));
                 }
             }
@@ -141,6 +161,16 @@ impl From<UniParc> for Vec<Entry> {
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<'a> rayon::iter::IntoParallelIterator for &'a UniParc {
+    type Iter = rayon::slice::Iter<'a, Entry>;
+    type Item = &'a Entry;
+    fn into_par_iter(self) -> Self::Iter {
+        rayon::iter::IntoParallelIterator::into_par_iter(&self.entries)
+    }
+}
+
+#[cfg(feature = "parser")]
 impl UniprotDatabase for UniParc {
     type Entry = Entry;
     const ROOTS: &'static [&'static [u8]] = &[b"uniparc"];
@@ -1,31 +1,58 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
 
 use super::InterproReference;
 use super::Location;
 
 #[derive(Debug, Clone)]
+/// A match of a sequence signature from a member database of InterPro.
 pub struct SignatureSequenceMatch {
+    /// The name of the database of the signature, e.g. `Pfam`.
     pub database: ShortString,
+    /// The identifier of the signature in the database.
     pub id: ShortString,
-    pub interpro: InterproReference,
+    /// The InterPro entry the signature is integrated into, if any.
+    pub interpro: Option<InterproReference>,
+    /// The locations of the signature on the sequence.
     pub locations: Vec<Location>,
 }
 
+impl SignatureSequenceMatch {
+    /// Get the InterPro entry the signature is integrated into, if any.
+    ///
+    /// Signatures do not have a name of their own in UniParc, so this is
+    /// also the way to get a human-readable name for the match.
+    pub fn interpro(&self) -> Option<&InterproReference> {
+        self.interpro.as_ref()
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for SignatureSequenceMatch {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"signatureSequenceMatch");
 
@@ -43,22 +70,22 @@ impl FromXml for SignatureSequenceMatch {
 
         let mut interpro = None;
         let mut locations = Vec::new();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"ipr" => {
-                if interpro.replace(FromXml::from_xml(&e, reader, buffer)?).is_some() {
-                    return Err(Error::DuplicateElement("ipr", "dbReference"));
+                if interpro.replace(FromXml::from_xml(&e, reader, buffer, options)?).is_some() {
+                    return Err(Error::DuplicateElement("ipr", "signatureSequenceMatch"));
                 }
             },
             e @ b"lcn" => {
-                locations.push(FromXml::from_xml(&e, reader, buffer)?);
+                locations.push(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
         Ok(SignatureSequenceMatch {
             database,
             id,
+            interpro,
             locations,
-            interpro: interpro.ok_or(Error::MissingElement("ipr", "dbReference"))?,
         })
     }
 }
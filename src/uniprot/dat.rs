@@ -0,0 +1,525 @@
+//! Parser for the UniProtKB plain-text flat-file format.
+//!
+//! The flat-file format (usually distributed as `.dat` files) stores each
+//! entry as a record of lines prefixed by a two-letter line code, terminated
+//! by a `//` line. See the [UniProtKB user manual] for a complete
+//! description of the format.
+//!
+//! [UniProtKB user manual]: https://web.expasy.org/docs/userman.html
+
+use std::io::BufRead;
+
+use chrono::NaiveDate;
+use quick_xml::Error as XmlError;
+
+use crate::common::ShortString;
+use crate::error::Error;
+
+use super::model::gene;
+use super::model::organism;
+use super::model::protein;
+use super::model::protein::ProteinExistence;
+use super::model::Dataset;
+use super::model::DbReference;
+use super::model::Entry;
+use super::model::FragmentType;
+use super::model::Keyword;
+
+/// A parser for UniProtKB entries in flat-file format.
+///
+/// Only the identification lines (`ID`, `AC`, `DT`), the protein and gene
+/// names (`DE`, `GN`), the organism lines (`OS`, `OC`, `OX`, `OH`), the
+/// protein existence (`PE`), the keywords (`KW`) and the sequence (`SQ`)
+/// are currently extracted; other lines are ignored.
+///
+/// Evidence tags (`{ECO:...}`) are stripped from the values, since they do
+/// not reference evidences declared in the entry like in the XML format.
+pub struct DatParser<B: BufRead> {
+    reader: B,
+    line: String,
+    finished: bool,
+    position: u64,
+    error_position: Option<u64>,
+}
+
+impl<B: BufRead> DatParser<B> {
+    /// Create a new flat-file parser from the given reader.
+    pub fn new(reader: B) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+            finished: false,
+            position: 0,
+            error_position: None,
+        }
+    }
+
+    /// Get the byte position of the error returned by the last call to `next`.
+    ///
+    /// Errors raised while reading are reported at the position of the
+    /// reader, and errors in the content of a record at the position of
+    /// the beginning of the record. Returns `None` if the last item was
+    /// not an error.
+    pub fn error_position(&self) -> Option<u64> {
+        self.error_position
+    }
+}
+
+impl<B: BufRead> Iterator for DatParser<B> {
+    type Item = Result<Entry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.error_position = None;
+        if self.finished {
+            return None;
+        }
+
+        let start = self.position;
+        let mut record = Vec::new();
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Err(e) => {
+                    self.finished = true;
+                    self.error_position = Some(self.position);
+                    return Some(Err(Error::from(e).at(self.position)));
+                }
+                Ok(0) if record.is_empty() => {
+                    self.finished = true;
+                    return None;
+                }
+                Ok(0) => {
+                    self.finished = true;
+                    self.error_position = Some(self.position);
+                    let e = String::from("record");
+                    let e = Error::from(XmlError::UnexpectedEof(e));
+                    return Some(Err(e.at(self.position)));
+                }
+                Ok(n) => {
+                    self.position += n as u64;
+                    let line = self.line.trim_end();
+                    if line.starts_with("//") {
+                        let result = parse_record(&record);
+                        if result.is_err() {
+                            self.error_position = Some(start);
+                        }
+                        return Some(result.map_err(|e| e.at(start)));
+                    } else if !line.is_empty() {
+                        record.push(line.to_string());
+                    }
+                }
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+/// Parse a complete record, without its `//` terminator.
+fn parse_record(record: &[String]) -> Result<Entry, Error> {
+    let mut entry: Option<Entry> = None;
+    let mut description = Vec::new();
+    let mut genes = Vec::new();
+    let mut species = String::new();
+    let mut lineage = String::new();
+    let mut hosts = Vec::new();
+    let mut keywords = String::new();
+
+    for line in record {
+        let code = line.get(..2).unwrap_or(line);
+        let content = line.get(5..).unwrap_or("").trim();
+
+        if code == "ID" {
+            entry = Some(parse_id(content)?);
+            continue;
+        }
+        let entry = entry
+            .as_mut()
+            .ok_or(Error::MissingElement("ID", "record"))?;
+
+        match code {
+            "AC" => entry.accessions.extend(split_list(content, ';')),
+            "DT" => parse_dt(content, entry)?,
+            "DE" => description.push(content),
+            "GN" if content == "and" => genes.push(String::new()),
+            "GN" => push_continuation(last_or_insert(&mut genes), content),
+            "OS" => push_continuation(&mut species, content),
+            "OC" => push_continuation(&mut lineage, content),
+            "OX" => {
+                let db_reference = parse_taxonomy(content)?;
+                let organism = entry.organism.get_or_insert_with(Default::default);
+                organism.db_references.push(db_reference);
+            }
+            "OH" if content.starts_with("NCBI_TaxID=") => hosts.push(String::from(content)),
+            "OH" => push_continuation(last_or_insert(&mut hosts), content),
+            "PE" => entry.protein_existence = parse_pe(content).map(Some)?,
+            "KW" => push_continuation(&mut keywords, content),
+            "SQ" => parse_sq(content, entry)?,
+            "  " => entry
+                .sequence
+                .get_or_insert_with(Default::default)
+                .value
+                .extend(content.chars().filter(|c| !c.is_whitespace())),
+            _ => (),
+        }
+    }
+
+    let mut entry = entry.ok_or(Error::MissingElement("ID", "record"))?;
+    parse_de(&description, &mut entry)?;
+    for text in genes.iter().filter(|text| !text.is_empty()) {
+        entry.genes.push(parse_gn(text)?);
+    }
+    if !species.is_empty() {
+        let organism = entry.organism.get_or_insert_with(Default::default);
+        organism.names = parse_organism_names(&species);
+    }
+    if !lineage.is_empty() {
+        let taxons = split_list(&lineage, ';').collect();
+        let organism = entry.organism.get_or_insert_with(Default::default);
+        organism.lineages.push(organism::Lineage { taxons });
+    }
+    for text in &hosts {
+        entry.organism_hosts.push(parse_oh(text)?);
+    }
+    for value in split_list(&keywords, ';') {
+        entry.keywords.push(Keyword {
+            value,
+            ..Default::default()
+        });
+    }
+
+    Ok(entry)
+}
+
+/// Get the last element of a list of continued lines, creating it if needed.
+fn last_or_insert(texts: &mut Vec<String>) -> &mut String {
+    if texts.is_empty() {
+        texts.push(String::new());
+    }
+    texts.last_mut().unwrap()
+}
+
+/// Append the content of a continuation line to a buffer.
+fn push_continuation(buffer: &mut String, content: &str) {
+    if !buffer.is_empty() {
+        buffer.push(' ');
+    }
+    buffer.push_str(content);
+}
+
+/// Remove the evidence tags (`{ECO:...}`) from a value.
+fn strip_evidences(value: &str) -> String {
+    let mut stripped = String::with_capacity(value.len());
+    let mut depth = 0usize;
+    for c in value.chars() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            _ if depth == 0 => stripped.push(c),
+            _ => (),
+        }
+    }
+    stripped.trim().to_string()
+}
+
+/// Split a list of values, ignoring evidences and the final period.
+fn split_list(text: &str, sep: char) -> impl Iterator<Item = ShortString> {
+    strip_evidences(text)
+        .trim_end_matches('.')
+        .split(sep)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ShortString::from)
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+/// Parse the `ID` line, e.g. `1433_MESCR  Reviewed;  264 AA.`.
+fn parse_id(content: &str) -> Result<Entry, Error> {
+    let mut tokens = content.split_whitespace();
+    let name = tokens.next().ok_or(Error::MissingElement("name", "ID"))?;
+    let dataset = match tokens.next() {
+        Some("Reviewed;") => Dataset::SwissProt,
+        Some("Unreviewed;") => Dataset::TrEmbl,
+        Some(other) => return Err(Error::invalid_value("dataset", "ID", other)),
+        None => return Err(Error::MissingElement("dataset", "ID")),
+    };
+
+    let mut entry = Entry::new(dataset);
+    entry.names.push(ShortString::from(name));
+    Ok(entry)
+}
+
+/// Parse a `DT` line, e.g. `05-MAY-2009, sequence version 1.`.
+fn parse_dt(content: &str, entry: &mut Entry) -> Result<(), Error> {
+    let (date, event) = content
+        .split_once(',')
+        .ok_or_else(|| Error::invalid_value("date", "DT", content))?;
+    let date = NaiveDate::parse_from_str(date, "%d-%b-%Y")
+        .map_err(|_| Error::invalid_value("date", "DT", date))?;
+
+    let event = event.trim().trim_end_matches('.');
+    if event.starts_with("integrated into") {
+        entry.created = date.into();
+    } else if let Some(version) = event.strip_prefix("sequence version ") {
+        let sequence = entry.sequence.get_or_insert_with(Default::default);
+        sequence.modified = date.into();
+        sequence.version = version.parse()?;
+    } else if let Some(version) = event.strip_prefix("entry version ") {
+        entry.modified = date.into();
+        entry.version = version.parse()?;
+    } else {
+        return Err(Error::invalid_value("event", "DT", event));
+    }
+
+    Ok(())
+}
+
+/// The kind of protein name currently described by the `DE` lines.
+#[derive(Clone, Copy)]
+enum NameKind {
+    Recommended,
+    Alternative,
+    Submitted,
+    Flags,
+}
+
+/// Parse the `DE` lines into the protein names of the entry.
+fn parse_de(lines: &[&str], entry: &mut Entry) -> Result<(), Error> {
+    let protein = &mut entry.protein;
+    let mut nomenclature = &mut protein.name;
+    let mut kind = None;
+
+    for line in lines {
+        let mut line = *line;
+        match line {
+            "Includes:" => {
+                protein.domains.push(Default::default());
+                nomenclature = protein.domains.last_mut().unwrap();
+                continue;
+            }
+            "Contains:" => {
+                protein.components.push(Default::default());
+                nomenclature = protein.components.last_mut().unwrap();
+                continue;
+            }
+            _ => (),
+        }
+
+        for (prefix, k) in [
+            ("RecName:", NameKind::Recommended),
+            ("AltName:", NameKind::Alternative),
+            ("SubName:", NameKind::Submitted),
+            ("Flags:", NameKind::Flags),
+        ] {
+            if let Some(rest) = line.strip_prefix(prefix) {
+                line = rest.trim_start();
+                kind = Some(k);
+            }
+        }
+
+        if let Some(NameKind::Flags) = kind {
+            let sequence = entry.sequence.get_or_insert_with(Default::default);
+            for flag in split_list(line, ';') {
+                match flag.as_ref() {
+                    "Fragment" => sequence.fragment = Some(FragmentType::Single),
+                    "Fragments" => sequence.fragment = Some(FragmentType::Multiple),
+                    "Precursor" => sequence.precursor = Some(true),
+                    other => return Err(Error::invalid_value("Flags", "DE", other)),
+                }
+            }
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| Error::invalid_value("name", "DE", line))?;
+        let value = ShortString::from(strip_evidences(value.trim_end_matches(';')));
+        let kind = kind.ok_or(Error::MissingElement("RecName", "DE"))?;
+
+        match key {
+            "Full" => {
+                let name = protein::Name {
+                    full: value,
+                    ..Default::default()
+                };
+                match kind {
+                    NameKind::Recommended => nomenclature.recommended = Some(name),
+                    NameKind::Alternative => nomenclature.alternative.push(name),
+                    NameKind::Submitted => nomenclature.submitted.push(name),
+                    NameKind::Flags => unreachable!(),
+                }
+            }
+            "Short" | "EC" => {
+                let name = match kind {
+                    NameKind::Recommended => nomenclature.recommended.as_mut(),
+                    NameKind::Alternative => nomenclature.alternative.last_mut(),
+                    NameKind::Submitted => nomenclature.submitted.last_mut(),
+                    NameKind::Flags => unreachable!(),
+                }
+                .ok_or(Error::MissingElement("Full", "DE"))?;
+                if key == "Short" {
+                    name.short.push(value);
+                } else {
+                    name.ec_number.push(value);
+                }
+            }
+            "Allergen" => nomenclature.allergen = Some(value),
+            "Biotech" => nomenclature.biotech = Some(value),
+            "CD_antigen" => nomenclature.cd_antigen.push(value),
+            "INN" => nomenclature.inn.push(value),
+            other => return Err(Error::invalid_value("name", "DE", other)),
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse the `GN` lines describing a single gene.
+fn parse_gn(text: &str) -> Result<gene::Gene, Error> {
+    let mut gene = gene::Gene::default();
+    for field in strip_evidences(text).split(';').map(str::trim) {
+        if field.is_empty() {
+            continue;
+        }
+        let (key, values) = field
+            .split_once('=')
+            .ok_or_else(|| Error::invalid_value("name", "GN", field))?;
+        let ty = match key {
+            "Name" => gene::NameType::Primary,
+            "Synonyms" => gene::NameType::Synonym,
+            "OrderedLocusNames" => gene::NameType::OrderedLocus,
+            "ORFNames" => gene::NameType::Orf,
+            other => return Err(Error::invalid_value("name", "GN", other)),
+        };
+        for value in split_list(values, ',') {
+            gene.names.push(gene::Name::new(value, ty));
+        }
+    }
+    Ok(gene)
+}
+
+/// Check whether a parenthesized group is part of a scientific name.
+fn is_name_qualifier(group: &str) -> bool {
+    const QUALIFIERS: &[&str] = &[
+        "strain ",
+        "isolate ",
+        "subsp.",
+        "var.",
+        "serotype ",
+        "serovar ",
+        "biovar ",
+        "pathovar ",
+        "cultivar ",
+        "clone ",
+        "subtype ",
+    ];
+    QUALIFIERS.iter().any(|q| group.starts_with(q))
+}
+
+/// Parse the organism names of an `OS` or `OH` line.
+///
+/// Names follow the `scientific (common) (synonym)` layout, where strain
+/// or isolate qualifiers in parentheses are part of the scientific name.
+fn parse_organism_names(text: &str) -> Vec<organism::Name> {
+    let mut rest = strip_evidences(text);
+    let rest_len = rest.trim_end_matches('.').len();
+    rest.truncate(rest_len);
+
+    let mut groups = Vec::new();
+    while rest.ends_with(')') {
+        let mut depth = 0;
+        let mut open = None;
+        for (i, c) in rest.char_indices().rev() {
+            match c {
+                ')' => depth += 1,
+                '(' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        open = Some(i);
+                        break;
+                    }
+                }
+                _ => (),
+            }
+        }
+        let open = match open {
+            Some(open) if open > 0 => open,
+            _ => break,
+        };
+        let group = &rest[open + 1..rest.len() - 1];
+        if is_name_qualifier(group) {
+            break;
+        }
+        groups.push(ShortString::from(group));
+        rest.truncate(rest[..open].trim_end().len());
+    }
+
+    let mut names = Vec::new();
+    if !rest.is_empty() {
+        let scientific = ShortString::from(rest);
+        names.push(organism::Name::new(
+            scientific,
+            organism::NameType::Scientific,
+        ));
+    }
+    for (i, group) in groups.into_iter().rev().enumerate() {
+        let ty = match i {
+            0 => organism::NameType::Common,
+            _ => organism::NameType::Synonym,
+        };
+        names.push(organism::Name::new(group, ty));
+    }
+    names
+}
+
+/// Parse a taxonomy cross-reference, e.g. `NCBI_TaxID=3544;`.
+fn parse_taxonomy(content: &str) -> Result<DbReference, Error> {
+    let content = strip_evidences(content);
+    let id = content
+        .trim_end_matches(';')
+        .strip_prefix("NCBI_TaxID=")
+        .ok_or_else(|| Error::invalid_value("NCBI_TaxID", "OX", content.as_str()))?;
+    Ok(DbReference {
+        ty: ShortString::from("NCBI Taxonomy"),
+        id: ShortString::from(id.trim()),
+        ..Default::default()
+    })
+}
+
+/// Parse an `OH` line, e.g. `NCBI_TaxID=9823; Sus scrofa (Pig).`.
+fn parse_oh(text: &str) -> Result<organism::Organism, Error> {
+    let (taxonomy, names) = text.split_once(';').unwrap_or((text, ""));
+    Ok(organism::Organism {
+        names: parse_organism_names(names.trim()),
+        db_references: vec![parse_taxonomy(taxonomy)?],
+        ..Default::default()
+    })
+}
+
+/// Parse the `PE` line, e.g. `2: Evidence at transcript level;`.
+fn parse_pe(content: &str) -> Result<ProteinExistence, Error> {
+    match content.split(':').next().map(str::trim) {
+        Some("1") => Ok(ProteinExistence::ProteinLevelEvidence),
+        Some("2") => Ok(ProteinExistence::TranscriptLevelEvidence),
+        Some("3") => Ok(ProteinExistence::HomologyInferred),
+        Some("4") => Ok(ProteinExistence::Predicted),
+        Some("5") => Ok(ProteinExistence::Uncertain),
+        _ => Err(Error::invalid_value("level", "PE", content)),
+    }
+}
+
+/// Parse the `SQ` header line, e.g. `SEQUENCE 264 AA; 29912 MW; E8F2EE8D617435D6 CRC64;`.
+fn parse_sq(content: &str, entry: &mut Entry) -> Result<(), Error> {
+    let tokens = content.split_whitespace().collect::<Vec<_>>();
+    match tokens.as_slice() {
+        ["SEQUENCE", length, "AA;", mass, "MW;", checksum, "CRC64;"] => {
+            let sequence = entry.sequence.get_or_insert_with(Default::default);
+            sequence.length = length.parse()?;
+            sequence.mass = mass.parse()?;
+            sequence.checksum = u64::from_str_radix(checksum, 16)?;
+            Ok(())
+        }
+        _ => Err(Error::invalid_value("header", "SQ", content)),
+    }
+}
@@ -0,0 +1,93 @@
+//! Byte offset index of the entries of a UniProt XML file.
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::io::Seek;
+use std::ops::Range;
+
+use quick_xml::events::Event;
+use quick_xml::Error as XmlError;
+use quick_xml::Reader;
+
+use crate::common::ShortString;
+use crate::error::Error;
+
+#[derive(Debug, Default, Clone)]
+/// An index mapping accessions to the byte range of their entry in a file.
+///
+/// Every accession of an entry is indexed, so that an entry can be found
+/// from any of its secondary accessions as well as its primary accession.
+pub struct AccessionIndex {
+    ranges: HashMap<ShortString, Range<u64>>,
+}
+
+impl AccessionIndex {
+    /// Build an index by scanning all the entries of the given reader.
+    ///
+    /// Offsets are computed relative to the start of the underlying stream,
+    /// not to the current position of the reader. If an accession appears
+    /// in several entries, only the first entry is indexed.
+    pub fn build<B: BufRead + Seek>(mut reader: B) -> Result<Self, Error> {
+        let offset = reader.stream_position()?;
+        let mut xml = Reader::from_reader(reader);
+        let mut buffer = Vec::new();
+        let mut ranges = HashMap::new();
+        let mut accessions = Vec::new();
+        let mut start = None;
+        let mut depth = 0usize;
+        let mut in_accession = false;
+
+        loop {
+            buffer.clear();
+            let position = offset + xml.buffer_position() as u64;
+            match xml.read_event_into(&mut buffer)? {
+                Event::Start(e) if e.local_name().as_ref() == b"entry" && start.is_none() => {
+                    start = Some(position);
+                    depth = 0;
+                }
+                Event::Start(e) if start.is_some() => {
+                    depth += 1;
+                    in_accession = depth == 1 && e.local_name().as_ref() == b"accession";
+                }
+                Event::Text(t) if in_accession => {
+                    accessions.push(ShortString::from(t.unescape()?.trim()));
+                }
+                Event::End(e) if e.local_name().as_ref() == b"entry" && depth == 0 => {
+                    if let Some(start) = start.take() {
+                        let end = offset + xml.buffer_position() as u64;
+                        for accession in accessions.drain(..) {
+                            ranges.entry(accession).or_insert(start..end);
+                        }
+                    }
+                }
+                Event::End(_) if start.is_some() => {
+                    depth -= 1;
+                    in_accession = false;
+                }
+                Event::Eof if start.is_some() => {
+                    let e = String::from("entry");
+                    return Err(Error::from(XmlError::UnexpectedEof(e)));
+                }
+                Event::Eof => break,
+                _ => (),
+            }
+        }
+
+        Ok(Self { ranges })
+    }
+
+    /// Get the byte range of the entry with the given accession, if any.
+    pub fn get(&self, accession: &str) -> Option<Range<u64>> {
+        self.ranges.get(accession).cloned()
+    }
+
+    /// Get the number of accessions in the index.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Check whether the index contains no accession.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
@@ -0,0 +1,1080 @@
+//! Deserialization of UniProtKB entries from the JSON format of the REST API.
+//!
+//! The JSON documents follow their own schema, so they are first deserialized
+//! into private types mirroring that schema, and then converted into the
+//! types of the XML object model.
+
+use std::io::BufRead;
+use std::io::Read;
+use std::str::FromStr;
+
+use serde::de::IgnoredAny;
+use serde::Deserialize;
+
+use crate::common::ShortString;
+use crate::error::Error;
+
+use super::model::comment::Comment;
+use super::model::comment::CommentType;
+use super::model::comment::SubcellularLocation;
+use super::model::feature_location::FeatureLocation;
+use super::model::feature_location::Position;
+use super::model::feature_location::Status;
+use super::model::gene;
+use super::model::gene::Gene;
+use super::model::organism;
+use super::model::organism::Lineage;
+use super::model::organism::Organism;
+use super::model::protein::Name;
+use super::model::protein::Nomenclature;
+use super::model::protein::Protein;
+use super::model::protein::ProteinExistence;
+use super::model::reference::Citation;
+use super::model::reference::CitationType;
+use super::model::reference::Creator;
+use super::model::reference::Reference;
+use super::model::reference::Source;
+use super::model::reference::SourceType;
+use super::model::Dataset;
+use super::model::Date;
+use super::model::DbReference;
+use super::model::Entry;
+use super::model::Evidence;
+use super::model::EvidenceText;
+use super::model::Feature;
+use super::model::FeatureType;
+use super::model::FragmentType;
+use super::model::Keyword;
+use super::model::Ligand;
+use super::model::LigandPart;
+use super::model::Molecule;
+use super::model::Property;
+use super::model::Sequence;
+
+/// Parse a single UniProtKB entry in JSON format.
+///
+/// This is the format returned by the UniProt REST API when requesting a
+/// single entry, e.g. at `https://rest.uniprot.org/uniprotkb/P02978.json`.
+///
+/// The JSON schema of the REST API differs from the XML schema, and not
+/// everything can be mapped to the object model:
+///
+/// - evidences are inlined in the JSON documents, so they are collected
+///   into [`Entry::evidences`] and given keys in order of appearance,
+///   which may differ from the keys of the XML documents;
+/// - only the comments with free-text content and the subcellular location
+///   comments are supported, other comment types are skipped;
+/// - cross-reference properties keep the keys of the JSON documents, which
+///   are capitalized differently from the XML property types.
+///
+/// [`Entry::evidences`]: ./struct.Entry.html#structfield.evidences
+pub fn parse_json<B: BufRead>(reader: B) -> Result<Entry, Error> {
+    let entry: JsonEntry = serde_json::from_reader(reader)?;
+    entry.into_entry()
+}
+
+/// Parse the UniProtKB entries from the `results` of a JSON search response.
+///
+/// This is the format returned by the search and stream endpoints of the
+/// UniProt REST API, e.g. at `https://rest.uniprot.org/uniprotkb/search`.
+/// Entries are deserialized one at a time while the `results` array is
+/// being read, and any other member of the response is skipped. See
+/// [`parse_json`] for the limitations of the JSON format.
+///
+/// [`parse_json`]: ./fn.parse_json.html
+pub fn parse_json_results<B: BufRead>(reader: B) -> JsonResults<B> {
+    JsonResults {
+        reader: Counting {
+            reader,
+            position: 0,
+        },
+        state: State::Start,
+        error_position: None,
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// The beginning of the response has not been read yet.
+    Start,
+    /// The reader is inside the `results` array, before the entry at the
+    /// given index.
+    Results(usize),
+    /// The `results` array has been read entirely, or an error occurred.
+    Done,
+}
+
+/// An iterator over the entries of a JSON search response.
+///
+/// Use [`parse_json_results`] to create a new instance.
+///
+/// [`parse_json_results`]: ./fn.parse_json_results.html
+pub struct JsonResults<B: BufRead> {
+    reader: Counting<B>,
+    state: State,
+    error_position: Option<u64>,
+}
+
+impl<B: BufRead> JsonResults<B> {
+    /// Get the byte position of the error returned by the last call to `next`.
+    ///
+    /// The position is counted from the beginning of the reader given to
+    /// [`parse_json_results`]. Returns `None` if the last item was not an
+    /// error.
+    ///
+    /// [`parse_json_results`]: ./fn.parse_json_results.html
+    pub fn error_position(&self) -> Option<u64> {
+        self.error_position
+    }
+
+    /// Skip the members of the response until the start of the `results` array.
+    ///
+    /// Returns `false` if the response has no `results` member.
+    fn find_results(&mut self) -> Result<bool, Error> {
+        expect_byte(&mut self.reader, b'{')?;
+        if peek_byte(&mut self.reader)? == Some(b'}') {
+            return Ok(false);
+        }
+        loop {
+            let key = {
+                let mut de = serde_json::Deserializer::from_reader(&mut self.reader);
+                String::deserialize(&mut de)?
+            };
+            expect_byte(&mut self.reader, b':')?;
+            if key == "results" {
+                expect_byte(&mut self.reader, b'[')?;
+                return Ok(true);
+            }
+            skip_value(&mut self.reader)?;
+            match peek_byte(&mut self.reader)? {
+                Some(b',') => self.reader.consume(1),
+                Some(b'}') => return Ok(false),
+                other => return Err(unexpected_byte(",` or `}", other)),
+            }
+        }
+    }
+
+    fn next_entry(&mut self, index: usize) -> Result<Option<Entry>, Error> {
+        match peek_byte(&mut self.reader)? {
+            Some(b']') => return Ok(None),
+            Some(b',') if index > 0 => self.reader.consume(1),
+            other if index > 0 => return Err(unexpected_byte(",` or `]", other)),
+            _ => (),
+        }
+        let mut de = serde_json::Deserializer::from_reader(&mut self.reader);
+        let entry = JsonEntry::deserialize(&mut de)?;
+        entry.into_entry().map(Some)
+    }
+}
+
+impl<B: BufRead> Iterator for JsonResults<B> {
+    type Item = Result<Entry, Error>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.error_position = None;
+        if self.state == State::Start {
+            match self.find_results() {
+                Ok(true) => self.state = State::Results(0),
+                Ok(false) => self.state = State::Done,
+                Err(e) => {
+                    self.state = State::Done;
+                    self.error_position = Some(self.reader.position);
+                    return Some(Err(e.at(self.reader.position)));
+                }
+            }
+        }
+        let index = match self.state {
+            State::Results(index) => index,
+            _ => return None,
+        };
+        match self.next_entry(index) {
+            Ok(Some(entry)) => {
+                self.state = State::Results(index + 1);
+                Some(Ok(entry))
+            }
+            Ok(None) => {
+                self.state = State::Done;
+                None
+            }
+            Err(e) => {
+                self.state = State::Done;
+                self.error_position = Some(self.reader.position);
+                Some(Err(e.at(self.reader.position)))
+            }
+        }
+    }
+}
+
+/// A reader counting the number of bytes consumed from the wrapped reader.
+struct Counting<B> {
+    reader: B,
+    position: u64,
+}
+
+impl<B: BufRead> Read for Counting<B> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.reader.read(buf)?;
+        self.position += n as u64;
+        Ok(n)
+    }
+}
+
+impl<B: BufRead> BufRead for Counting<B> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.reader.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.reader.consume(amt);
+        self.position += amt as u64;
+    }
+}
+
+/// Peek the next non-whitespace byte of the reader, without consuming it.
+fn peek_byte<B: BufRead>(reader: &mut B) -> Result<Option<u8>, Error> {
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            return Ok(None);
+        }
+        match buffer.iter().position(|b| !b.is_ascii_whitespace()) {
+            Some(i) => {
+                let byte = buffer[i];
+                reader.consume(i);
+                return Ok(Some(byte));
+            }
+            None => {
+                let length = buffer.len();
+                reader.consume(length);
+            }
+        }
+    }
+}
+
+/// Consume the next non-whitespace byte of the reader if it is `expected`.
+fn expect_byte<B: BufRead>(reader: &mut B, expected: u8) -> Result<(), Error> {
+    match peek_byte(reader)? {
+        Some(byte) if byte == expected => {
+            reader.consume(1);
+            Ok(())
+        }
+        other => Err(unexpected_byte(&(expected as char).to_string(), other)),
+    }
+}
+
+/// Consume the next JSON value of the reader.
+fn skip_value<B: BufRead>(reader: &mut B) -> Result<(), Error> {
+    match peek_byte(reader)? {
+        // strings, objects and arrays are skipped by `serde_json`, which
+        // stops reading after their closing delimiter
+        Some(b'"') | Some(b'{') | Some(b'[') => {
+            let mut de = serde_json::Deserializer::from_reader(reader);
+            IgnoredAny::deserialize(&mut de)?;
+        }
+        // other values must be consumed manually, since `serde_json` would
+        // read and then drop the byte following them
+        _ => loop {
+            let buffer = reader.fill_buf()?;
+            if buffer.is_empty() {
+                break;
+            }
+            match buffer
+                .iter()
+                .position(|&b| b == b',' || b == b'}' || b.is_ascii_whitespace())
+            {
+                Some(i) => {
+                    reader.consume(i);
+                    break;
+                }
+                None => {
+                    let length = buffer.len();
+                    reader.consume(length);
+                }
+            }
+        },
+    }
+    Ok(())
+}
+
+fn unexpected_byte(expected: &str, found: Option<u8>) -> Error {
+    let message = match found {
+        Some(byte) => format!("expected `{}`, found `{}`", expected, byte as char),
+        None => format!("expected `{}`, found end of file", expected),
+    };
+    Error::Json(<serde_json::Error as serde::de::Error>::custom(message))
+}
+
+// ---------------------------------------------------------------------------
+
+/// The evidences of an entry, collected while converting its annotations.
+#[derive(Default)]
+struct Evidences(Vec<JsonEvidence>);
+
+impl Evidences {
+    /// Get the keys of the given evidences, registering the new ones.
+    fn keys(&mut self, evidences: Vec<JsonEvidence>) -> Vec<usize> {
+        evidences
+            .into_iter()
+            .map(
+                |evidence| match self.0.iter().position(|e| *e == evidence) {
+                    Some(i) => i + 1,
+                    None => {
+                        self.0.push(evidence);
+                        self.0.len()
+                    }
+                },
+            )
+            .collect()
+    }
+
+    fn into_evidences(self) -> Vec<Evidence> {
+        self.0
+            .into_iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let mut evidence = Evidence::new(i + 1, e.evidence_code);
+                if let (Some(ty), Some(id)) = (e.source, e.id) {
+                    let db_reference = DbReference {
+                        ty,
+                        id,
+                        ..Default::default()
+                    };
+                    evidence.source = Some(super::model::Source::DbRef(db_reference));
+                }
+                evidence
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonEvidence {
+    evidence_code: ShortString,
+    source: Option<ShortString>,
+    id: Option<ShortString>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct JsonValue {
+    value: ShortString,
+    evidences: Vec<JsonEvidence>,
+}
+
+impl JsonValue {
+    fn into_text(self, evidences: &mut Evidences) -> EvidenceText {
+        EvidenceText::new_with_evidences(self.value, evidences.keys(self.evidences))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonEntry {
+    entry_type: ShortString,
+    primary_accession: ShortString,
+    #[serde(default)]
+    secondary_accessions: Vec<ShortString>,
+    #[serde(default, rename = "uniProtkbId")]
+    uniprotkb_id: Option<ShortString>,
+    entry_audit: Option<JsonEntryAudit>,
+    organism: Option<JsonOrganism>,
+    #[serde(default)]
+    organism_hosts: Vec<JsonOrganism>,
+    protein_existence: Option<ShortString>,
+    #[serde(default)]
+    protein_description: JsonProteinDescription,
+    #[serde(default)]
+    genes: Vec<JsonGene>,
+    #[serde(default)]
+    comments: Vec<JsonComment>,
+    #[serde(default)]
+    features: Vec<JsonFeature>,
+    #[serde(default)]
+    keywords: Vec<JsonKeyword>,
+    #[serde(default)]
+    references: Vec<JsonReference>,
+    #[serde(default, rename = "uniProtKBCrossReferences")]
+    cross_references: Vec<JsonCrossReference>,
+    sequence: Option<JsonSequence>,
+}
+
+impl JsonEntry {
+    fn into_entry(self) -> Result<Entry, Error> {
+        let dataset = if self.entry_type.contains("Swiss-Prot") {
+            Dataset::SwissProt
+        } else if self.entry_type.contains("TrEMBL") {
+            Dataset::TrEmbl
+        } else {
+            return Err(Error::invalid_value("entryType", "entry", self.entry_type));
+        };
+
+        let mut evidences = Evidences::default();
+        let mut entry = Entry::new(dataset);
+        entry.accessions.push(self.primary_accession);
+        entry.accessions.extend(self.secondary_accessions);
+        entry.names.extend(self.uniprotkb_id);
+
+        let mut sequence_audit = None;
+        if let Some(audit) = self.entry_audit {
+            entry.created = parse_date(&audit.first_public_date, "firstPublicDate")?;
+            entry.modified = parse_date(
+                &audit.last_annotation_update_date,
+                "lastAnnotationUpdateDate",
+            )?;
+            entry.version = audit.entry_version;
+            let modified = parse_date(&audit.last_sequence_update_date, "lastSequenceUpdateDate")?;
+            sequence_audit = Some((modified, audit.sequence_version));
+        }
+
+        let flags = self.protein_description.flag.clone().unwrap_or_default();
+        entry.protein = self.protein_description.into_protein();
+        for gene in self.genes {
+            entry.genes.push(gene.into_gene(&mut evidences));
+        }
+        if let Some(organism) = self.organism {
+            entry.organism = Some(organism.into_organism(&mut evidences));
+        }
+        for host in self.organism_hosts {
+            entry
+                .organism_hosts
+                .push(host.into_organism(&mut evidences));
+        }
+        for reference in self.references {
+            entry
+                .references
+                .push(reference.into_reference(&mut evidences)?);
+        }
+        for comment in self.comments {
+            entry.comments.extend(comment.into_comment(&mut evidences));
+        }
+        for dbref in self.cross_references {
+            entry
+                .db_references
+                .push(dbref.into_db_reference(&mut evidences));
+        }
+        if let Some(pe) = self.protein_existence {
+            entry.protein_existence = Some(parse_protein_existence(&pe)?);
+        }
+        for keyword in self.keywords {
+            entry.keywords.push(keyword.into_keyword(&mut evidences));
+        }
+        for feature in self.features {
+            entry.features.push(feature.into_feature(&mut evidences)?);
+        }
+        if let Some(sequence) = self.sequence {
+            let mut sequence = sequence.into_sequence()?;
+            if let Some((modified, version)) = sequence_audit {
+                sequence.modified = modified;
+                sequence.version = version;
+            }
+            for flag in flags.split(',').map(str::trim) {
+                match flag {
+                    "Fragment" => sequence.fragment = Some(FragmentType::Single),
+                    "Fragments" => sequence.fragment = Some(FragmentType::Multiple),
+                    "Precursor" => sequence.precursor = Some(true),
+                    _ => (),
+                }
+            }
+            entry.sequence = Some(sequence);
+        }
+
+        entry.evidences = evidences.into_evidences();
+        Ok(entry)
+    }
+}
+
+fn parse_date(date: &str, name: &'static str) -> Result<Date, Error> {
+    Date::from_iso8601(date).map_err(|e| Error::InvalidValue(name, "entryAudit", e))
+}
+
+/// Parse a protein existence such as `1: Evidence at protein level`.
+fn parse_protein_existence(value: &str) -> Result<ProteinExistence, Error> {
+    let name = value
+        .split_once(": ")
+        .map(|(_, name)| name)
+        .unwrap_or(value);
+    ProteinExistence::from_str(&name.to_lowercase())
+        .map_err(|_| Error::invalid_value("proteinExistence", "entry", value))
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonEntryAudit {
+    first_public_date: ShortString,
+    last_annotation_update_date: ShortString,
+    last_sequence_update_date: ShortString,
+    entry_version: usize,
+    sequence_version: usize,
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonProteinDescription {
+    recommended_name: Option<JsonProteinName>,
+    alternative_names: Vec<JsonProteinName>,
+    submission_names: Vec<JsonProteinName>,
+    allergen_name: Option<JsonValue>,
+    biotech_name: Option<JsonValue>,
+    cd_antigen_names: Vec<JsonValue>,
+    inn_names: Vec<JsonValue>,
+    includes: Vec<JsonProteinDescription>,
+    contains: Vec<JsonProteinDescription>,
+    flag: Option<ShortString>,
+}
+
+impl JsonProteinDescription {
+    fn into_protein(mut self) -> Protein {
+        let domains = std::mem::take(&mut self.includes);
+        let components = std::mem::take(&mut self.contains);
+        Protein {
+            name: self.into_nomenclature(),
+            domains: domains.into_iter().map(Self::into_nomenclature).collect(),
+            components: components
+                .into_iter()
+                .map(Self::into_nomenclature)
+                .collect(),
+        }
+    }
+
+    /// Convert the names of the protein, ignoring its domains and components.
+    fn into_nomenclature(self) -> Nomenclature {
+        let names = |names: Vec<JsonProteinName>| names.into_iter().map(Name::from).collect();
+        Nomenclature {
+            recommended: self.recommended_name.map(Name::from),
+            alternative: names(self.alternative_names),
+            submitted: names(self.submission_names),
+            allergen: self.allergen_name.map(|name| name.value),
+            biotech: self.biotech_name.map(|name| name.value),
+            cd_antigen: self.cd_antigen_names.into_iter().map(|n| n.value).collect(),
+            inn: self.inn_names.into_iter().map(|n| n.value).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonProteinName {
+    full_name: JsonValue,
+    short_names: Vec<JsonValue>,
+    ec_numbers: Vec<JsonValue>,
+}
+
+impl From<JsonProteinName> for Name {
+    fn from(name: JsonProteinName) -> Self {
+        let JsonProteinName {
+            full_name,
+            short_names,
+            ec_numbers,
+        } = name;
+        Name {
+            full: full_name.value,
+            short: short_names.into_iter().map(|n| n.value).collect(),
+            ec_number: ec_numbers.into_iter().map(|n| n.value).collect(),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonGene {
+    gene_name: Option<JsonValue>,
+    synonyms: Vec<JsonValue>,
+    ordered_locus_names: Vec<JsonValue>,
+    orf_names: Vec<JsonValue>,
+}
+
+impl JsonGene {
+    fn into_gene(self, evidences: &mut Evidences) -> Gene {
+        use self::gene::NameType::*;
+        let mut gene = Gene::default();
+        let names = self
+            .gene_name
+            .into_iter()
+            .map(|name| (name, Primary))
+            .chain(self.synonyms.into_iter().map(|name| (name, Synonym)))
+            .chain(
+                self.ordered_locus_names
+                    .into_iter()
+                    .map(|n| (n, OrderedLocus)),
+            )
+            .chain(self.orf_names.into_iter().map(|name| (name, Orf)));
+        for (name, ty) in names {
+            let keys = evidences.keys(name.evidences);
+            gene.names
+                .push(gene::Name::new_with_evidence(name.value, ty, keys));
+        }
+        gene
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonOrganism {
+    scientific_name: Option<ShortString>,
+    common_name: Option<ShortString>,
+    synonyms: Vec<ShortString>,
+    taxon_id: Option<u32>,
+    lineage: Vec<ShortString>,
+    evidences: Vec<JsonEvidence>,
+}
+
+impl JsonOrganism {
+    fn into_organism(self, evidences: &mut Evidences) -> Organism {
+        use self::organism::NameType::*;
+        let mut organism = Organism::default();
+        let names = self
+            .scientific_name
+            .into_iter()
+            .map(|name| (name, Scientific))
+            .chain(self.common_name.into_iter().map(|name| (name, Common)))
+            .chain(self.synonyms.into_iter().map(|name| (name, Synonym)));
+        for (name, ty) in names {
+            organism.names.push(organism::Name::new(name, ty));
+        }
+        if let Some(taxon_id) = self.taxon_id {
+            organism.db_references.push(DbReference {
+                ty: "NCBI Taxonomy".into(),
+                id: taxon_id.to_string().into(),
+                ..Default::default()
+            });
+        }
+        if !self.lineage.is_empty() {
+            organism.lineages.push(Lineage {
+                taxons: self.lineage,
+            });
+        }
+        organism.evidences = evidences.keys(self.evidences);
+        organism
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonReference {
+    reference_number: usize,
+    citation: JsonCitation,
+    #[serde(default)]
+    reference_positions: Vec<ShortString>,
+    #[serde(default)]
+    reference_comments: Vec<JsonReferenceComment>,
+    #[serde(default)]
+    evidences: Vec<JsonEvidence>,
+}
+
+impl JsonReference {
+    fn into_reference(self, evidences: &mut Evidences) -> Result<Reference, Error> {
+        let citation = self.citation.into_citation()?;
+        let mut reference = Reference::new(citation, self.reference_number);
+        reference.scope = self.reference_positions;
+        for comment in self.reference_comments {
+            let ty = SourceType::from_str(&comment.ty.to_lowercase())
+                .map_err(|e| Error::InvalidValue("type", "referenceComment", e))?;
+            let keys = evidences.keys(comment.evidences);
+            reference
+                .sources
+                .push(Source::with_evidences(comment.value, ty, keys));
+        }
+        reference.evidences = evidences.keys(self.evidences);
+        Ok(reference)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonReferenceComment {
+    value: ShortString,
+    #[serde(rename = "type")]
+    ty: ShortString,
+    #[serde(default)]
+    evidences: Vec<JsonEvidence>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonCitation {
+    citation_type: ShortString,
+    #[serde(default)]
+    authoring_group: Vec<ShortString>,
+    #[serde(default)]
+    authors: Vec<ShortString>,
+    #[serde(default)]
+    editors: Vec<ShortString>,
+    #[serde(default)]
+    citation_cross_references: Vec<JsonCrossReference>,
+    title: Option<ShortString>,
+    publication_date: Option<ShortString>,
+    journal: Option<ShortString>,
+    book_name: Option<ShortString>,
+    volume: Option<ShortString>,
+    first_page: Option<ShortString>,
+    last_page: Option<ShortString>,
+    publisher: Option<ShortString>,
+    address: Option<ShortString>,
+    submission_database: Option<ShortString>,
+    patent_number: Option<ShortString>,
+    locator: Option<ShortString>,
+}
+
+impl JsonCitation {
+    fn into_citation(self) -> Result<Citation, Error> {
+        let ty = CitationType::from_str(&self.citation_type)
+            .map_err(|e| Error::InvalidValue("citationType", "citation", e))?;
+        let mut citation = Citation::new(ty);
+        if let Some(date) = self.publication_date {
+            let date = Date::from_partial(&date)
+                .map_err(|e| Error::InvalidValue("publicationDate", "citation", e))?;
+            citation.date = Some(date);
+        }
+        citation.name = self.journal.or(self.book_name);
+        citation.volume = self.volume;
+        citation.first = self.first_page;
+        citation.last = self.last_page;
+        citation.publisher = self.publisher;
+        citation.city = self.address;
+        citation.db = self.submission_database;
+        citation.number = self.patent_number;
+        citation.titles.extend(self.title);
+        citation.locators.extend(self.locator);
+        citation.editors = self.editors.into_iter().map(Creator::Person).collect();
+        citation.authors = self
+            .authoring_group
+            .into_iter()
+            .map(Creator::Consortium)
+            .chain(self.authors.into_iter().map(Creator::Person))
+            .collect();
+        for dbref in self.citation_cross_references {
+            let db_reference = DbReference {
+                ty: dbref.database,
+                id: dbref.id,
+                ..Default::default()
+            };
+            citation.db_references.push(db_reference);
+        }
+        Ok(citation)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonComment {
+    comment_type: ShortString,
+    #[serde(default)]
+    texts: Vec<JsonValue>,
+    #[serde(default)]
+    subcellular_locations: Vec<JsonSubcellularLocation>,
+    note: Option<JsonNote>,
+}
+
+impl JsonComment {
+    /// Convert the comment, unless its type is not supported.
+    fn into_comment(self, evidences: &mut Evidences) -> Option<Comment> {
+        let ty = match self.comment_type.as_str() {
+            "ACTIVITY REGULATION" => CommentType::ActivityRegulation,
+            "ALLERGEN" => CommentType::Allergen,
+            "BIOTECHNOLOGY" => CommentType::Biotechnology,
+            "CAUTION" => CommentType::Caution,
+            "DEVELOPMENTAL STAGE" => CommentType::DevelopmentalStage,
+            "DISRUPTION PHENOTYPE" => CommentType::DisruptionPhenotype,
+            "DOMAIN" => CommentType::Domain,
+            "FUNCTION" => CommentType::Function,
+            "INDUCTION" => CommentType::Induction,
+            "MISCELLANEOUS" => CommentType::Miscellaneous,
+            "PATHWAY" => CommentType::Pathway,
+            "PHARMACEUTICAL" => CommentType::Pharmaceutical,
+            "POLYMORPHISM" => CommentType::Polymorphism,
+            "PTM" => CommentType::Ptm,
+            "SIMILARITY" => CommentType::Similarity,
+            "SUBUNIT" => CommentType::Subunit,
+            "TISSUE SPECIFICITY" => CommentType::TissueSpecificity,
+            "TOXIC DOSE" => CommentType::ToxicDose,
+            "SUBCELLULAR LOCATION" => {
+                let locations = self
+                    .subcellular_locations
+                    .into_iter()
+                    .map(|location| location.into_subcellular_location(evidences))
+                    .collect();
+                CommentType::SubcellularLocation(locations)
+            }
+            _ => return None,
+        };
+        let mut comment = Comment::new(ty);
+        let texts = self.note.map(|note| note.texts).unwrap_or_default();
+        for text in self.texts.into_iter().chain(texts) {
+            comment.text.push(text.into_text(evidences));
+        }
+        Some(comment)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct JsonNote {
+    texts: Vec<JsonValue>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct JsonSubcellularLocation {
+    location: Option<JsonValue>,
+    topology: Option<JsonValue>,
+    orientation: Option<JsonValue>,
+}
+
+impl JsonSubcellularLocation {
+    fn into_subcellular_location(self, evidences: &mut Evidences) -> SubcellularLocation {
+        let mut texts =
+            |value: Option<JsonValue>| value.into_iter().map(|v| v.into_text(evidences)).collect();
+        SubcellularLocation {
+            locations: texts(self.location),
+            topologies: texts(self.topology),
+            orientations: texts(self.orientation),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonCrossReference {
+    database: ShortString,
+    id: ShortString,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+    isoform_id: Option<ShortString>,
+    #[serde(default)]
+    evidences: Vec<JsonEvidence>,
+}
+
+impl JsonCrossReference {
+    fn into_db_reference(self, evidences: &mut Evidences) -> DbReference {
+        DbReference {
+            molecule: self.isoform_id.map(Molecule::Id),
+            property: self
+                .properties
+                .into_iter()
+                .map(|p| Property::new(p.key, p.value))
+                .collect(),
+            ty: self.database,
+            id: self.id,
+            evidences: evidences.keys(self.evidences),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonProperty {
+    key: ShortString,
+    value: ShortString,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonKeyword {
+    id: ShortString,
+    name: ShortString,
+    #[serde(default)]
+    evidences: Vec<JsonEvidence>,
+}
+
+impl JsonKeyword {
+    fn into_keyword(self, evidences: &mut Evidences) -> Keyword {
+        Keyword {
+            id: self.id,
+            value: self.name,
+            evidence: evidences.keys(self.evidences),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonFeature {
+    #[serde(rename = "type")]
+    ty: ShortString,
+    location: JsonLocation,
+    description: Option<ShortString>,
+    feature_id: Option<ShortString>,
+    #[serde(default)]
+    evidences: Vec<JsonEvidence>,
+    alternative_sequence: Option<JsonAlternativeSequence>,
+    ligand: Option<JsonLigand>,
+    ligand_part: Option<JsonLigand>,
+}
+
+impl JsonFeature {
+    fn into_feature(self, evidences: &mut Evidences) -> Result<Feature, Error> {
+        let ty = match feature_type(&self.ty) {
+            Some(ty) => ty,
+            None => return Err(Error::invalid_value("type", "feature", self.ty)),
+        };
+        let start = self.location.start.into_position(Status::LessThan)?;
+        let end = self.location.end.into_position(Status::GreaterThan)?;
+        let location = if start == end {
+            FeatureLocation::Position(start)
+        } else {
+            FeatureLocation::Range(start, end)
+        };
+
+        let mut feature = Feature::new(ty, location);
+        feature.id = self.feature_id;
+        feature.description = self.description.filter(|d| !d.is_empty());
+        feature.evidences = evidences.keys(self.evidences);
+        if let Some(alternative) = self.alternative_sequence {
+            feature.original = alternative.original_sequence;
+            feature.variation = alternative.alternative_sequences;
+        }
+        if let Some(ligand) = self.ligand {
+            let mut l = Ligand::new(ligand.name);
+            l.db_reference = ligand.id.as_deref().and_then(ligand_db_reference);
+            l.label = ligand.label;
+            l.note = ligand.note;
+            feature.ligand = Some(l);
+        }
+        if let Some(part) = self.ligand_part {
+            let mut l = LigandPart::new(part.name);
+            l.db_reference = part.id.as_deref().and_then(ligand_db_reference);
+            l.label = part.label;
+            l.note = part.note;
+            feature.ligand_part = Some(l);
+        }
+        Ok(feature)
+    }
+}
+
+/// Get the feature type for the given JSON feature type name.
+fn feature_type(name: &str) -> Option<FeatureType> {
+    use self::FeatureType::*;
+    let ty = match name {
+        "Active site" => ActiveSite,
+        "Alternative sequence" => SpliceVariant,
+        "Beta strand" => Strand,
+        "Binding site" => BindingSite,
+        "Calcium binding" => CalciumBindingRegion,
+        "Chain" => Chain,
+        "Coiled coil" => CoiledCoilRegion,
+        "Compositional bias" => CompositionallyBiasedRegion,
+        "Cross-link" => CrossLink,
+        "Disulfide bond" => DisulfideBond,
+        "DNA binding" => DnaBindingRegion,
+        "Domain" => Domain,
+        "Glycosylation" => GlycosylationSite,
+        "Helix" => Helix,
+        "Initiator methionine" => InitiatorMethionine,
+        "Intramembrane" => IntramembraneRegion,
+        "Lipidation" => LipidMoietyBindingRegion,
+        "Metal binding" => MetalIonBindingSite,
+        "Modified residue" => ModifiedResidue,
+        "Motif" => ShortSequenceMotif,
+        "Mutagenesis" => MutagenesisSite,
+        "Natural variant" => SequenceVariant,
+        "Non-adjacent residues" => NonConsecutiveResidues,
+        "Non-standard residue" => NonStandardAminoAcid,
+        "Non-terminal residue" => NonTerminalResidue,
+        "Nucleotide binding" => NucleotidePhosphateBindingRegion,
+        "Peptide" => Peptide,
+        "Propeptide" => Propeptide,
+        "Region" => RegionOfInterest,
+        "Repeat" => Repeat,
+        "Sequence conflict" => SequenceConflict,
+        "Sequence uncertainty" => UnsureResidue,
+        "Signal" => SignalPeptide,
+        "Site" => Site,
+        "Topological domain" => TopologicalDomain,
+        "Transit peptide" => TransitPeptide,
+        "Transmembrane" => TransmembraneRegion,
+        "Turn" => Turn,
+        "Zinc finger" => ZincFingerRegion,
+        _ => return None,
+    };
+    Some(ty)
+}
+
+/// Get the cross-reference of a ligand identifier such as `ChEBI:CHEBI:15377`.
+fn ligand_db_reference(id: &str) -> Option<DbReference> {
+    let (database, id) = id.split_once(':')?;
+    Some(DbReference {
+        ty: database.into(),
+        id: id.into(),
+        ..Default::default()
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonLocation {
+    start: JsonPosition,
+    end: JsonPosition,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonPosition {
+    value: Option<usize>,
+    modifier: ShortString,
+}
+
+impl JsonPosition {
+    /// Convert the position, using `outside` as the status of `OUTSIDE` positions.
+    fn into_position(self, outside: Status) -> Result<Position, Error> {
+        let status = match self.modifier.as_str() {
+            "EXACT" => Status::Certain,
+            "UNSURE" => Status::Uncertain,
+            "UNKNOWN" => Status::Unknown,
+            "OUTSIDE" => outside,
+            other => return Err(Error::invalid_value("modifier", "location", other)),
+        };
+        Ok(Position {
+            pos: self.value,
+            status,
+            evidence: Vec::new(),
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct JsonAlternativeSequence {
+    original_sequence: Option<ShortString>,
+    alternative_sequences: Vec<ShortString>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonLigand {
+    name: ShortString,
+    id: Option<ShortString>,
+    label: Option<ShortString>,
+    note: Option<ShortString>,
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSequence {
+    value: ShortString,
+    length: usize,
+    mol_weight: usize,
+    crc64: ShortString,
+}
+
+impl JsonSequence {
+    fn into_sequence(self) -> Result<Sequence, Error> {
+        Ok(Sequence {
+            checksum: u64::from_str_radix(&self.crc64, 16)?,
+            value: self.value,
+            length: self.length,
+            mass: self.mol_weight,
+            ..Default::default()
+        })
+    }
+}
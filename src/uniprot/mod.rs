@@ -1,81 +1,3239 @@
 //! Data types for the UniProtKB databases.
 
+#[cfg(feature = "parser")]
+use std::collections::hash_map::Entry as HashMapEntry;
+#[cfg(feature = "parser")]
+use std::collections::HashMap;
+#[cfg(feature = "parser")]
+use std::collections::HashSet;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
+#[cfg(feature = "parser")]
+use std::io::Cursor;
+#[cfg(feature = "parser")]
+use std::io::Read;
+#[cfg(feature = "parser")]
+use std::io::Seek;
+#[cfg(feature = "parser")]
+use std::io::SeekFrom;
+#[cfg(feature = "parser")]
+use std::io::Write;
+#[cfg(feature = "parser")]
+use std::ops::Range;
+#[cfg(feature = "parser")]
+use std::sync::Arc;
 
+#[cfg(feature = "parser")]
+use quick_xml::events::BytesDecl;
+#[cfg(feature = "parser")]
+use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
+use quick_xml::events::Event;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
+
+#[cfg(feature = "parser")]
+use crate::any::PeekedReader;
+#[cfg(feature = "parser")]
+use crate::error::Error;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::ParserCursor;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
+
+#[cfg(feature = "parser")]
+mod dat;
+#[cfg(feature = "parser")]
+mod index;
+#[cfg(all(feature = "parser", feature = "serde"))]
+mod json;
 mod model;
+#[cfg(feature = "parser")]
+mod refs;
+
+#[cfg(feature = "parser")]
+pub use self::dat::DatParser;
+#[cfg(feature = "parser")]
+pub use self::index::AccessionIndex;
+#[cfg(all(feature = "parser", feature = "serde"))]
+pub use self::json::parse_json;
+#[cfg(all(feature = "parser", feature = "serde"))]
+pub use self::json::parse_json_results;
+#[cfg(all(feature = "parser", feature = "serde"))]
+pub use self::json::JsonResults;
+#[doc(inline)]
+pub use self::model::*;
+#[cfg(feature = "parser")]
+pub use self::refs::EntryRef;
+#[cfg(feature = "parser")]
+pub use self::refs::RefParser;
+
+/// The sequential parser type for UniProt entries.
+#[cfg(feature = "parser")]
+pub type SequentialParser<B> = super::parser::SequentialParser<B, UniProt>;
+
+#[cfg(feature = "threading")]
+/// The threaded parser type for UniProt entries.
+pub type ThreadedParser<B> = super::parser::ThreadedParser<B, UniProt>;
+
+#[cfg(feature = "async")]
+/// The asynchronous parser type for UniProt entries.
+pub type AsyncParser<B> = super::parser::AsyncParser<B, UniProt>;
+
+/// The parser type for UniProt entries.
+#[cfg(feature = "parser")]
+pub type Parser<B> = super::parser::Parser<B, UniProt>;
+
+/// The parser type for UniProt entries read from several files.
+#[cfg(feature = "parser")]
+pub type ChainParser<I> = super::parser::ChainParser<I, UniProt>;
+
+/// Parse a Uniprot database XML file.
+///
+/// # Example:
+/// ```rust,no_run
+/// let mut client = ftp::FtpStream::connect("ftp.uniprot.org:21").unwrap();
+/// client.login("anonymous", "anonymous").unwrap();
+///
+/// let f = client.get("/pub/databases/uniprot/current_release/knowledgebase/complete/uniprot_sprot.xml.gz").unwrap();
+/// let dec = libflate::gzip::Decoder::new(f).unwrap();
+/// let mut parser = uniprot::uniprot::parse(std::io::BufReader::new(dec));
+///
+/// println!("{:#?}", parser.next());
+/// ```
+#[cfg(feature = "parser")]
+pub fn parse<B: BufRead + Send + 'static>(reader: B) -> Parser<B> {
+    Parser::new(reader)
+}
+
+/// Parse a UniProt database XML document from a byte slice.
+///
+/// Contrary to [`parse`](./fn.parse.html), this does not require the
+/// input to be owned, and always parses entries sequentially. An empty
+/// slice yields no entry.
+///
+/// # Example
+/// ```rust
+/// let xml = std::fs::read("tests/uniprot.xml").unwrap();
+/// let entries = uniprot::uniprot::parse_slice(&xml)
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(entries.len(), 250);
+/// ```
+#[cfg(feature = "parser")]
+pub fn parse_slice(bytes: &[u8]) -> SequentialParser<Cursor<&[u8]>> {
+    if bytes.is_empty() {
+        SequentialParser::empty(Cursor::new(bytes))
+    } else {
+        SequentialParser::new(Cursor::new(bytes))
+    }
+}
+
+/// Parse several UniProt database XML files as a single stream of entries.
+///
+/// This is useful to process files that were distributed separately, such
+/// as the Swiss-Prot and TrEMBL sections of UniProtKB, which cannot simply
+/// be concatenated since each file has its own root element.
+///
+/// # Example
+/// ```rust
+/// let files = ["tests/uniprot.xml", "tests/uniprot.xml"]
+///     .iter()
+///     .map(|path| std::io::BufReader::new(std::fs::File::open(path).unwrap()));
+/// let parser = uniprot::uniprot::parse_chain(files);
+/// assert_eq!(parser.count(), 500);
+/// ```
+#[cfg(feature = "parser")]
+pub fn parse_chain<I, B>(readers: I) -> ChainParser<I::IntoIter>
+where
+    I: IntoIterator<Item = B>,
+    B: BufRead + Send + 'static,
+{
+    ChainParser::new(readers)
+}
+
+/// Parse a UniProt database XML file into the given collection.
+///
+/// Entries are added to `out` as soon as they are parsed, in the order of
+/// the source file, and parsing stops at the first error, in which case the
+/// entries preceding the error are left in `out`.
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let mut entries = Vec::with_capacity(250);
+/// uniprot::uniprot::parse_into(std::io::BufReader::new(f), &mut entries).unwrap();
+/// assert_eq!(entries.len(), 250);
+/// ```
+#[cfg(feature = "parser")]
+pub fn parse_into<B, C>(reader: B, out: &mut C) -> Result<(), Error>
+where
+    B: BufRead + Send + 'static,
+    C: Extend<Entry>,
+{
+    #[allow(unused_mut)]
+    let mut parser = parse(reader);
+    #[cfg(feature = "threading")]
+    parser.ordered();
+    for entry in parser {
+        out.extend(Some(entry?));
+    }
+    Ok(())
+}
+
+/// Parse a UniProt database XML file from an asynchronous reader.
+///
+/// The returned stream yields the entries of a UniProtKB file, such as a
+/// Swiss-Prot release or a UniProt REST API response in XML format, which
+/// must have a `uniprot` root element. Entries are read asynchronously and
+/// then deserialized synchronously, so this is best suited to a network
+/// reader rather than a local file read by [`parse`](./fn.parse.html).
+///
+/// # Example
+/// ```rust
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use futures_util::StreamExt;
+///
+/// let f = tokio::fs::File::open("tests/uniprot.xml").await.unwrap();
+/// let stream = uniprot::uniprot::parse_async(tokio::io::BufReader::new(f));
+/// let entries = stream.collect::<Vec<_>>().await;
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub fn parse_async<B>(reader: B) -> AsyncParser<B>
+where
+    B: tokio::io::AsyncBufRead + Unpin,
+{
+    AsyncParser::new(reader)
+}
+
+/// Parse a UniProt database XML file which may be compressed.
+///
+/// The compression is detected from the first bytes of the reader, among
+/// the formats enabled by the `gzip`, `xz` and `zstd` features, so the same
+/// function can be used to read both plain and compressed files, whatever
+/// their extension. The reader is buffered internally, and does not need
+/// to implement `BufRead`.
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let parser = uniprot::uniprot::parse_compressed(f).unwrap();
+/// for entry in parser {
+///     println!("{:?}", entry.unwrap().primary_accession());
+/// }
+/// ```
+#[cfg(any(feature = "gzip", feature = "xz", feature = "zstd"))]
+pub fn parse_compressed<R>(reader: R) -> Result<Parser<Box<dyn BufRead + Send>>, Error>
+where
+    R: std::io::Read + Send + 'static,
+{
+    crate::compression::reader(reader).map(Parser::new)
+}
+
+/// Parse a UniProt database XML file which may be Gzip-compressed.
+///
+/// See [`parse_compressed`] for details.
+///
+/// [`parse_compressed`]: ./fn.parse_compressed.html
+#[cfg(feature = "gzip")]
+pub fn parse_gzip<R>(reader: R) -> Result<Parser<Box<dyn BufRead + Send>>, Error>
+where
+    R: std::io::Read + Send + 'static,
+{
+    parse_compressed(reader)
+}
+
+/// Parse a UniProt database XML file which may be XZ-compressed.
+///
+/// See [`parse_compressed`] for details.
+///
+/// [`parse_compressed`]: ./fn.parse_compressed.html
+#[cfg(feature = "xz")]
+pub fn parse_xz<R>(reader: R) -> Result<Parser<Box<dyn BufRead + Send>>, Error>
+where
+    R: std::io::Read + Send + 'static,
+{
+    parse_compressed(reader)
+}
+
+/// Parse a UniProt database XML file which may be Zstandard-compressed.
+///
+/// See [`parse_compressed`] for details.
+///
+/// [`parse_compressed`]: ./fn.parse_compressed.html
+#[cfg(feature = "zstd")]
+pub fn parse_zstd<R>(reader: R) -> Result<Parser<Box<dyn BufRead + Send>>, Error>
+where
+    R: std::io::Read + Send + 'static,
+{
+    parse_compressed(reader)
+}
+
+/// Parse a single UniProt entry.
+///
+/// This method is compatible with responses from the
+/// [EBI Proteins API](https://www.ebi.ac.uk/proteins/api/).
+///
+/// # Example
+///
+/// Retrieve a single protein entry using the Proteins API and the
+/// [`ureq`](https://crates.io/crates/ureq) to perform the HTTP request.
+///
+/// ```rust
+/// let api_url = "https://www.ebi.ac.uk/proteins/api/proteins/P02978";
+///
+/// let req = ureq::get(&api_url).set("Accept", "application/xml");
+/// let reader = std::io::BufReader::new(req.call().unwrap().into_reader());
+/// let entry = uniprot::uniprot::parse_entry(reader).unwrap();
+///
+/// println!("{:?}", entry);
+/// ```
+#[cfg(feature = "parser")]
+pub fn parse_entry<B: BufRead>(reader: B) -> <SequentialParser<B> as Iterator>::Item {
+    SequentialParser::parse_entry(reader)
+}
+
+/// Parse a single UniProt entry from a string.
+///
+/// The string may start with an XML declaration, and the `entry` element
+/// may be wrapped in a `uniprot` root element.
+///
+/// # Example
+/// ```rust
+/// let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+/// <entry dataset="Swiss-Prot" created="2011-06-28" modified="2019-12-11" version="1">
+///   <accession>P00000</accession>
+///   <name>TEST_HUMAN</name>
+///   <sequence length="4" mass="400" checksum="0F7E6C568A43C6DA" modified="2010-06-15" version="1">MKLV</sequence>
+/// </entry>"#;
+/// let entry = uniprot::uniprot::parse_entry_str(xml).unwrap();
+/// assert_eq!(entry.primary_accession(), Some("P00000"));
+/// ```
+#[cfg(feature = "parser")]
+pub fn parse_entry_str(xml: &str) -> Result<Entry, Error> {
+    parse_entry(xml.as_bytes())
+}
+
+/// Build an index of the byte range of each entry in a UniProt XML file.
+///
+/// All the accessions of an entry are indexed, and the ranges can then be
+/// given to [`parse_entry_at`](./fn.parse_entry_at.html) to parse a single
+/// entry without reading the entire file again.
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let index = uniprot::uniprot::index(std::io::BufReader::new(f)).unwrap();
+///
+/// let range = index.get("P0C9F0").unwrap();
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let entry = uniprot::uniprot::parse_entry_at(std::io::BufReader::new(f), range).unwrap();
+/// assert_eq!(entry.primary_accession(), Some("P0C9F0"));
+/// ```
+#[cfg(feature = "parser")]
+pub fn index<B: BufRead + Seek>(reader: B) -> Result<AccessionIndex, Error> {
+    AccessionIndex::build(reader)
+}
+
+/// Parse the UniProt entry found at the given byte range of a reader.
+#[cfg(feature = "parser")]
+pub fn parse_entry_at<B: BufRead + Seek>(mut reader: B, range: Range<u64>) -> Result<Entry, Error> {
+    reader.seek(SeekFrom::Start(range.start))?;
+    parse_entry(reader.take(range.end.saturating_sub(range.start)))
+}
+
+/// Resume parsing a UniProt database XML file from the given cursor.
+///
+/// The reader is moved to the cursor, obtained from a previous parser with
+/// [`SequentialParser::cursor`], and parsing continues with the following
+/// entries. If the cursor is in the middle of an entry, that entry is
+/// skipped and parsing continues with the next one. The positions reported
+/// by the returned parser, such as its own cursor, are counted from the
+/// beginning of the reader, so that parsing can be resumed again later.
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let mut parser = uniprot::uniprot::SequentialParser::new(std::io::BufReader::new(f));
+/// parser.by_ref().take(100).for_each(|entry| drop(entry.unwrap()));
+/// let cursor = parser.cursor();
+///
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let parser = uniprot::uniprot::resume_from(std::io::BufReader::new(f), cursor).unwrap();
+/// assert_eq!(parser.count(), 150);
+/// ```
+///
+/// [`SequentialParser::cursor`]: ../parser/struct.SequentialParser.html#method.cursor
+#[cfg(feature = "parser")]
+pub fn resume_from<B>(reader: B, cursor: ParserCursor) -> Result<Parser<PeekedReader<B>>, Error>
+where
+    B: BufRead + Seek + Send + 'static,
+{
+    let (reader, origin) = crate::parser::seek_entry(reader, cursor, "uniprot")?;
+    Ok(Parser::new(reader).with_origin(origin))
+}
+
+/// Parse a UniProt database XML file into a map indexed by primary accession.
+///
+/// An error is returned if an entry has no accession, or if several entries
+/// share the same primary accession.
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let entries = uniprot::uniprot::parse_map(std::io::BufReader::new(f)).unwrap();
+/// assert_eq!(entries["P0C9F0"].names[0], "1001R_ASFK5");
+/// ```
+#[cfg(feature = "parser")]
+pub fn parse_map<B: BufRead + Send + 'static>(reader: B) -> Result<HashMap<String, Entry>, Error> {
+    let mut entries = HashMap::new();
+    for result in parse(reader) {
+        let entry = result?;
+        let accession = entry
+            .primary_accession()
+            .ok_or(Error::MissingElement("accession", "entry"))?
+            .to_string();
+        match entries.entry(accession) {
+            HashMapEntry::Occupied(e) => return Err(Error::DuplicateAccession(e.key().clone())),
+            HashMapEntry::Vacant(e) => e.insert(entry),
+        };
+    }
+    Ok(entries)
+}
+
+/// Parse a UniProt database XML file into a map indexed by all accessions.
+///
+/// Each entry is indexed by its primary accession, as with [`parse_map`],
+/// and also by its secondary accessions, pointing to the same shared entry.
+/// Secondary accessions are never indexed over a primary accession, and
+/// secondary accessions shared by several entries (for instance after an
+/// entry was split) are not indexed at all, since they are ambiguous.
+///
+/// [`parse_map`]: ./fn.parse_map.html
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let entries = uniprot::uniprot::parse_map_with_secondary(std::io::BufReader::new(f)).unwrap();
+/// assert_eq!(entries["Q4N2B5"].primary_accession(), Some("P15711"));
+/// ```
+#[cfg(feature = "parser")]
+pub fn parse_map_with_secondary<B: BufRead + Send + 'static>(
+    reader: B,
+) -> Result<HashMap<String, Arc<Entry>>, Error> {
+    let primary = parse_map(reader)?;
+    let mut secondary: HashMap<String, Option<Arc<Entry>>> = HashMap::new();
+    let mut entries = HashMap::with_capacity(primary.len());
+    for (accession, entry) in primary {
+        let entry = Arc::new(entry);
+        for other in entry.accessions.iter().skip(1) {
+            secondary
+                .entry(other.to_string())
+                .and_modify(|e| *e = None)
+                .or_insert_with(|| Some(entry.clone()));
+        }
+        entries.insert(accession, entry);
+    }
+    for (accession, entry) in secondary {
+        if let Some(entry) = entry {
+            entries.entry(accession).or_insert(entry);
+        }
+    }
+    Ok(entries)
+}
+
+/// Build a map from secondary accessions to primary accessions.
+///
+/// Each non-primary accession of an entry is mapped to the primary
+/// accession of that entry, which allows remapping accessions of merged
+/// entries. Entries with a single accession contribute nothing. As with
+/// [`parse_map_with_secondary`], secondary accessions which are also the
+/// primary accession of an entry, or which are shared by several entries,
+/// are ambiguous and are not included in the map.
+///
+/// [`parse_map_with_secondary`]: ./fn.parse_map_with_secondary.html
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let map = uniprot::uniprot::secondary_accession_map(std::io::BufReader::new(f)).unwrap();
+/// assert_eq!(map["Q4N2B5"], "P15711");
+/// ```
+#[cfg(feature = "parser")]
+pub fn secondary_accession_map<B: BufRead + Send + 'static>(
+    reader: B,
+) -> Result<HashMap<String, String>, Error> {
+    let mut primary = HashSet::new();
+    let mut secondary: HashMap<String, Option<String>> = HashMap::new();
+    for result in parse(reader) {
+        let entry = result?;
+        let mut accessions = entry.accessions.iter();
+        let accession = accessions
+            .next()
+            .ok_or(Error::MissingElement("accession", "entry"))?;
+        for other in accessions {
+            secondary
+                .entry(other.to_string())
+                .and_modify(|e| *e = None)
+                .or_insert_with(|| Some(accession.to_string()));
+        }
+        primary.insert(accession.to_string());
+    }
+    Ok(secondary
+        .into_iter()
+        .filter(|(accession, _)| !primary.contains(accession))
+        .filter_map(|(accession, entry)| entry.map(|e| (accession, e)))
+        .collect())
+}
+
+/// Parse lightweight views over the entries of a UniProt database XML file.
+///
+/// This allocates less than [`parse`](./fn.parse.html) when only the
+/// accession, the name and the sequence of the entries are needed, but
+/// reads the entries sequentially even with the `threading` feature. See
+/// [`RefParser`](./struct.RefParser.html) for more details.
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let mut parser = uniprot::uniprot::parse_refs(std::io::BufReader::new(f));
+/// while let Some(entry) = parser.next_entry() {
+///     let entry = entry.unwrap();
+///     println!("{}: {} residues", entry.accession, entry.sequence.len());
+/// }
+/// ```
+#[cfg(feature = "parser")]
+pub fn parse_refs<B: BufRead>(reader: B) -> RefParser<B> {
+    RefParser::new(reader)
+}
+
+/// Parse a UniProt database in plain-text flat-file format.
+///
+/// The flat-file format is the `.dat` format of the UniProtKB release files.
+/// See [`DatParser`](./struct.DatParser.html) for the list of lines that
+/// are currently extracted.
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.dat").unwrap();
+/// for entry in uniprot::uniprot::parse_dat(std::io::BufReader::new(f)) {
+///     println!("{:?}", entry.unwrap().primary_accession());
+/// }
+/// ```
+#[cfg(feature = "parser")]
+pub fn parse_dat<B: BufRead>(reader: B) -> DatParser<B> {
+    DatParser::new(reader)
+}
+
+/// Fetch a single UniProt entry from the UniProt REST API.
+///
+/// The entry is retrieved in XML format from
+/// `https://rest.uniprot.org/uniprotkb/{accession}.xml`. Non-`200` responses
+/// are reported as [`Error::HttpStatus`], and empty responses (which the
+/// API may return for obsolete entries) as [`Error::EmptyResponse`].
+///
+/// # Example
+/// ```rust,no_run
+/// let entry = uniprot::uniprot::fetch("P02978").unwrap();
+/// println!("{:?}", entry.recommended_name());
+/// ```
+///
+/// [`Error::HttpStatus`]: ../error/enum.Error.html#variant.HttpStatus
+/// [`Error::EmptyResponse`]: ../error/enum.Error.html#variant.EmptyResponse
+#[cfg(feature = "http")]
+pub fn fetch(accession: &str) -> Result<Entry, Error> {
+    let url = format!("https://rest.uniprot.org/uniprotkb/{}.xml", accession);
+    crate::http::get(&url).and_then(parse_entry)
+}
+
+/// Write UniProt entries to an XML file.
+///
+/// The entries are written inside a `uniprot` root element, in the same
+/// layout as the UniProtKB release files, so that the output can be read
+/// again with [`parse`](./fn.parse.html).
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let entries = uniprot::uniprot::parse(std::io::BufReader::new(f))
+///     .map(Result::unwrap)
+///     .filter(|entry| entry.sequence.as_ref().is_some_and(|s| s.length < 100));
+///
+/// let mut out = Vec::new();
+/// uniprot::uniprot::write(&mut out, entries).unwrap();
+/// ```
+#[cfg(feature = "parser")]
+pub fn write<W, I>(writer: W, entries: I) -> Result<(), Error>
+where
+    W: Write,
+    I: IntoIterator<Item = Entry>,
+{
+    let mut xml = Writer::new_with_indent(writer, b' ', 2);
+    xml.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    let mut root = BytesStart::new("uniprot");
+    root.push_attribute(("xmlns", "http://uniprot.org/uniprot"));
+    root.push_attribute(("xmlns:xsi", "http://www.w3.org/2001/XMLSchema-instance"));
+    root.push_attribute((
+        "xsi:schemaLocation",
+        "http://uniprot.org/uniprot http://www.uniprot.org/docs/uniprot.xsd",
+    ));
+    write_element(&mut xml, root, |w| {
+        for entry in entries {
+            entry.to_xml(w)?;
+        }
+        Ok(())
+    })?;
+
+    xml.into_inner().write_all(b"\n")?;
+    Ok(())
+}
+
+/// Write the given entries as newline-delimited JSON.
+///
+/// Each entry is serialized on its own line, and the writer is flushed
+/// after every entry so that an interrupted stream still contains valid
+/// lines for all the entries written so far.
+///
+/// # Example
+/// ```rust
+/// let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+/// let entries = uniprot::uniprot::parse(std::io::BufReader::new(f))
+///     .map(Result::unwrap)
+///     .take(3);
+///
+/// let mut out = Vec::new();
+/// uniprot::uniprot::write_ndjson(&mut out, entries).unwrap();
+/// assert_eq!(out.iter().filter(|&&b| b == b'\n').count(), 3);
+/// ```
+#[cfg(all(feature = "parser", feature = "serde"))]
+pub fn write_ndjson<W, I>(mut writer: W, entries: I) -> Result<(), Error>
+where
+    W: Write,
+    I: IntoIterator<Item = Entry>,
+{
+    let mut line = Vec::new();
+    for entry in entries {
+        line.clear();
+        serde_json::to_writer(&mut line, &entry)?;
+        line.push(b'\n');
+        writer.write_all(&line)?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(all(test, feature = "parser"))]
+mod tests {
+
+    use super::*;
+    use crate::error::Error;
+    use quick_xml::Error as XmlError;
+
+    #[test]
+    fn parse_swissprot_250() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = super::parse(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(entries.len(), 250);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn parse_gzip() {
+        use std::io::Write;
+
+        let data = std::fs::read("tests/uniprot.xml").unwrap();
+        let mut encoder = libflate::gzip::Encoder::new(Vec::new()).unwrap();
+        encoder.write_all(&data).unwrap();
+        let compressed = encoder.finish().into_result().unwrap();
+
+        let plain = super::parse_gzip(std::io::Cursor::new(data))
+            .expect("reader should be created")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        let gzipped = super::parse_gzip(std::io::Cursor::new(compressed))
+            .expect("reader should be created")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(plain.len(), 250);
+        assert_eq!(gzipped.len(), plain.len());
+    }
+
+    #[cfg(feature = "xz")]
+    #[test]
+    fn parse_xz() {
+        use std::io::Write;
+
+        let data = std::fs::read("tests/uniprot.xml").unwrap();
+        let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+        encoder.write_all(&data).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let plain = super::parse_xz(std::io::Cursor::new(data))
+            .expect("reader should be created")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        let xzipped = super::parse_xz(std::io::Cursor::new(compressed))
+            .expect("reader should be created")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(plain.len(), 250);
+        assert_eq!(xzipped.len(), plain.len());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn parse_zstd() {
+        let data = std::fs::read("tests/uniprot.xml").unwrap();
+        let compressed = zstd::stream::encode_all(data.as_slice(), 3).unwrap();
+
+        let plain = super::parse_zstd(std::io::Cursor::new(data))
+            .expect("reader should be created")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        let zstded = super::parse_zstd(std::io::Cursor::new(compressed))
+            .expect("reader should be created")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(plain.len(), 250);
+        assert_eq!(zstded.len(), plain.len());
+    }
+
+    #[cfg(all(feature = "gzip", feature = "xz", feature = "zstd"))]
+    #[test]
+    fn parse_compressed_mismatch() {
+        let data = std::fs::read("tests/uniprot.xml").unwrap();
+        let compressed = zstd::stream::encode_all(data.as_slice(), 3).unwrap();
+        for parse in [super::parse_gzip, super::parse_xz] {
+            let entries = parse(std::io::Cursor::new(compressed.clone()))
+                .expect("reader should be created")
+                .collect::<Result<Vec<_>, _>>()
+                .expect("entries should parse successfully");
+            assert_eq!(entries.len(), 250);
+        }
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn parse_async() {
+        use futures_util::StreamExt;
+
+        let f = tokio::fs::File::open("tests/uniprot.xml").await.unwrap();
+        let entries = super::parse_async(tokio::io::BufReader::new(f))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(entries.len(), 250);
+
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let expected = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|entry| entry.unwrap().accessions)
+            .collect::<Vec<_>>();
+        let actual = entries
+            .into_iter()
+            .map(|e| e.accessions)
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+
+        let txt = "<uniprot>\n<entry dataset=\"Swiss-Prot\"><protein>";
+        let mut stream = super::parse_async(txt.as_bytes());
+        match stream.next().await.map(|r| r.map_err(Error::into_inner)) {
+            Some(Err(Error::Xml(XmlError::UnexpectedEof(_)))) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(stream.error_position(), Some(txt.len() as u64));
+        assert!(stream.next().await.is_none());
+
+        let txt =
+            "<?xml version=\"1.0\"?>\n<uniref><entry dataset=\"Swiss-Prot\"></entry></uniref>";
+        let mut stream = super::parse_async(txt.as_bytes());
+        match stream.next().await.map(|r| r.map_err(Error::into_inner)) {
+            Some(Err(Error::UnexpectedRoot(root))) => assert_eq!(root, "uniref"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(
+            stream.error_position(),
+            txt.find("<uniref").map(|i| i as u64)
+        );
+        assert!(stream.next().await.is_none());
+
+        let mut stream = super::parse_async(&b""[..]);
+        match stream.next().await.map(|r| r.map_err(Error::into_inner)) {
+            Some(Err(Error::Xml(XmlError::UnexpectedEof(_)))) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        for entry in SequentialParser::new(std::io::BufReader::new(f)) {
+            let entry = entry.expect("entry should parse successfully");
+            let json = serde_json::to_value(&entry).expect("entry should serialize");
+            let copy: Entry =
+                serde_json::from_value(json.clone()).expect("entry should deserialize");
+            let json2 = serde_json::to_value(&copy).expect("entry should serialize");
+            assert_eq!(json, json2);
+        }
+    }
+
+    /// Check that an entry parsed from JSON matches the same entry parsed from XML.
+    #[cfg(feature = "serde")]
+    fn assert_json_matches_xml(json: &Entry, xml: &Entry) {
+        assert!(matches!(
+            (&json.dataset, &xml.dataset),
+            (Dataset::SwissProt, Dataset::SwissProt) | (Dataset::TrEmbl, Dataset::TrEmbl)
+        ));
+        assert_eq!(json.accessions, xml.accessions);
+        assert_eq!(json.names, xml.names);
+        assert_eq!(json.created, xml.created);
+        assert_eq!(json.modified, xml.modified);
+        assert_eq!(json.version, xml.version);
+        assert_eq!(json.recommended_name(), xml.recommended_name());
+        let alternative = |e: &Entry| {
+            e.protein
+                .name
+                .alternative
+                .iter()
+                .map(|n| n.full.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(alternative(json), alternative(xml));
+        assert_eq!(json.gene_names().count(), xml.gene_names().count());
+        for (j, x) in json.genes.iter().zip(&xml.genes) {
+            assert_eq!(j.names, x.names);
+        }
+        assert_eq!(json.protein_existence, xml.protein_existence);
+
+        let organism = |e: &Entry| {
+            let o = e.organism.as_ref().expect("entry should have an organism");
+            let names = o.names.iter().map(|n| (n.value.clone(), n.ty));
+            (
+                names.collect::<Vec<_>>(),
+                o.db_references.clone(),
+                o.lineages[0].taxons.clone(),
+            )
+        };
+        assert_eq!(organism(json), organism(xml));
+
+        assert_eq!(json.references.len(), xml.references.len());
+        for (j, x) in json.references.iter().zip(&xml.references) {
+            assert_eq!(j.key, x.key);
+            assert_eq!(j.scope, x.scope);
+            assert_eq!(j.citation.ty, x.citation.ty);
+            assert_eq!(j.citation.date, x.citation.date);
+            assert_eq!(j.citation.name, x.citation.name);
+            assert_eq!(j.citation.titles, x.citation.titles);
+            assert_eq!(j.citation.first, x.citation.first);
+            assert_eq!(j.citation.last, x.citation.last);
+            assert_eq!(j.citation.db_references, x.citation.db_references);
+            let authors = |r: &reference::Reference| {
+                r.citation
+                    .authors
+                    .iter()
+                    .map(|a| a.name().to_string())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(authors(j), authors(x));
+            let sources = |r: &reference::Reference| {
+                r.sources
+                    .iter()
+                    .map(|s| (s.value.clone(), s.ty))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(sources(j), sources(x));
+        }
+
+        // evidence keys differ, so compare the evidence codes instead
+        let codes = |e: &Entry, keys: &[usize]| {
+            e.resolve_evidences(keys)
+                .into_iter()
+                .map(|ev| ev.ty.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(json.features.len(), xml.features.len());
+        for (j, x) in json.features.iter().zip(&xml.features) {
+            assert_eq!(j.ty, x.ty);
+            assert_eq!(j.location, x.location);
+            assert_eq!(j.id, x.id);
+            assert_eq!(j.description, x.description);
+            assert_eq!(codes(json, &j.evidences), codes(xml, &x.evidences));
+        }
+        assert_eq!(json.keywords.len(), xml.keywords.len());
+        for (j, x) in json.keywords.iter().zip(&xml.keywords) {
+            assert_eq!((&j.id, &j.value), (&x.id, &x.value));
+        }
+        assert_eq!(json.db_references, xml.db_references);
+
+        assert_eq!(json.comments.len(), xml.comments.len());
+        for (j, x) in json.comments.iter().zip(&xml.comments) {
+            assert_eq!(j.ty.as_str(), x.ty.as_str());
+            let texts =
+                |c: &comment::Comment| c.text.iter().map(|t| t.value.clone()).collect::<Vec<_>>();
+            assert_eq!(texts(j), texts(x));
+            if let (
+                comment::CommentType::SubcellularLocation(j),
+                comment::CommentType::SubcellularLocation(x),
+            ) = (&j.ty, &x.ty)
+            {
+                assert_eq!(j.len(), x.len());
+                assert_eq!(j[0].locations[0].value, x[0].locations[0].value);
+            }
+        }
+
+        let j = json
+            .sequence
+            .as_ref()
+            .expect("entry should have a sequence");
+        let x = xml.sequence.as_ref().expect("entry should have a sequence");
+        assert_eq!(j.value, x.value);
+        assert_eq!(j.length, x.length);
+        assert_eq!(j.mass, x.mass);
+        assert_eq!(j.checksum, x.checksum);
+        assert_eq!(j.modified, x.modified);
+        assert_eq!(j.version, x.version);
+        assert_eq!(j.precursor, x.precursor);
+        assert_eq!(j.fragment, x.fragment);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parse_json() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let xml = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q4U9M9"))
+            .expect("entry should be found");
+
+        let f = std::fs::File::open("tests/uniprot.json").unwrap();
+        let json =
+            super::parse_json(std::io::BufReader::new(f)).expect("entry should parse successfully");
+        assert_json_matches_xml(&json, &xml);
+        assert_eq!(json.strains(), vec!["Ankara"]);
+        assert!(json.validate().is_ok());
+
+        let err = super::parse_json(std::io::Cursor::new(
+            r#"{"entryType": "Inactive", "primaryAccession": "Q4U9M9"}"#,
+        ))
+        .unwrap_err();
+        assert!(matches!(err, Error::InvalidValue("entryType", "entry", _)));
+        let err = super::parse_json(std::io::Cursor::new("{}")).unwrap_err();
+        assert!(matches!(err, Error::Json(_)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parse_json_results() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let xml = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .filter(|e| matches!(e.primary_accession(), Some("P84972") | Some("Q4U9M9")))
+            .collect::<Vec<_>>();
+
+        let f = std::fs::File::open("tests/uniprot_results.json").unwrap();
+        let json = super::parse_json_results(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(json.len(), 2);
+        for (j, x) in json.iter().zip(&xml) {
+            assert_json_matches_xml(j, x);
+        }
+
+        // other members of the response are skipped
+        let entry = std::fs::read_to_string("tests/uniprot.json").unwrap();
+        let text = format!(
+            r#"{{"count": 2, "ok": true, "facets": [{{"label": "]"}}], "results": [{0}, {0}], "next": 1}}"#,
+            entry
+        );
+        let results = super::parse_json_results(std::io::Cursor::new(text))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[1].primary_accession(), Some("Q4U9M9"));
+
+        assert_eq!(super::parse_json_results(&b"{}"[..]).count(), 0);
+        assert_eq!(
+            super::parse_json_results(&b"{\"results\": []}"[..]).count(),
+            0
+        );
+        let mut results = super::parse_json_results(&b"[]"[..]);
+        let err = results.next().unwrap().unwrap_err();
+        assert_eq!(err.position(), Some(0));
+        assert!(matches!(err.inner(), Error::Json(_)));
+        assert_eq!(results.error_position(), Some(0));
+        assert!(results.next().is_none());
+        let text = format!(r#"{{"results": [{0} {0}]}}"#, entry);
+        let mut results = super::parse_json_results(std::io::Cursor::new(text));
+        assert!(results.next().unwrap().is_ok());
+        let err = results.next().unwrap().unwrap_err();
+        assert!(matches!(err.into_inner(), Error::Json(_)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn write_ndjson() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let mut entries = SequentialParser::new(std::io::BufReader::new(f))
+            .take(3)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        entries[1].names[0] = "Tab\there\u{0}\u{e9}\nline".into();
+
+        let mut out = Vec::new();
+        super::write_ndjson(&mut out, entries.clone()).expect("entries should be written");
+
+        let text = String::from_utf8(out).expect("output should be valid UTF-8");
+        let lines = text.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        for (entry, line) in entries.iter().zip(lines) {
+            let copy: Entry = serde_json::from_str(line).expect("line should deserialize");
+            assert_eq!(copy.names, entry.names);
+            assert_eq!(copy.accessions, entry.accessions);
+        }
+    }
+
+    #[test]
+    fn entry_accessors() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|r| r.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("P93259"))
+            .expect("entry should be found");
+        assert_eq!(entry.recommended_name(), Some("14-3-3-like protein"));
+        assert_eq!(entry.gene_names().collect::<Vec<_>>(), vec!["GBF"]);
+
+        let mut entry = Entry::new(Dataset::TrEmbl);
+        assert_eq!(entry.primary_accession(), None);
+        assert_eq!(entry.recommended_name(), None);
+        assert_eq!(entry.gene_names().count(), 0);
+
+        entry.protein.name.submitted.push(protein::Name {
+            full: "Uncharacterized protein".into(),
+            ..Default::default()
+        });
+        assert_eq!(entry.recommended_name(), Some("Uncharacterized protein"));
+    }
+
+    #[test]
+    fn validate() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        for entry in SequentialParser::new(std::io::BufReader::new(f)) {
+            let entry = entry.expect("entry should parse successfully");
+            assert_eq!(entry.validate(), Ok(()));
+        }
+
+        let mut entry = Entry::new(Dataset::SwissProt);
+        entry.names.push("TEST_HUMAN".into());
+        entry.organism = Some(Default::default());
+        entry.protein_existence = Some(protein::ProteinExistence::Uncertain);
+        entry.sequence = Some(Sequence {
+            value: "MKLV".into(),
+            ..Default::default()
+        });
+        let citation = reference::Citation::new(reference::CitationType::Submission);
+        entry
+            .references
+            .push(reference::Reference::new(citation, 1));
+        assert_eq!(
+            entry.validate(),
+            Err(vec![ValidationWarning::MissingElement(
+                "accession",
+                "entry"
+            )])
+        );
+
+        entry.version = 0;
+        entry.sequence.as_mut().unwrap().value.clear();
+        let warnings = entry.validate().unwrap_err();
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings.contains(&ValidationWarning::InvalidValue("version", "entry")));
+        assert!(warnings.contains(&ValidationWarning::EmptyElement("sequence", "entry")));
+
+        entry.sequence = None;
+        let warnings = entry.validate().unwrap_err();
+        assert!(warnings.contains(&ValidationWarning::MissingElement("sequence", "entry")));
+    }
+
+    #[test]
+    fn parse_partial_entry() {
+        let txt = br#"<?xml version="1.0" encoding="UTF-8"?>
+<uniprot xmlns="http://uniprot.org/uniprot">
+<entry dataset="Swiss-Prot" created="1986-07-21" modified="2022-12-14" version="213">
+  <accession>P12345</accession>
+  <sequence length="4" mass="400" checksum="0F7E6C568A43C6DA" modified="1986-07-21" version="1">MKLV</sequence>
+</entry>
+</uniprot>"#;
+        let entry = SequentialParser::new(std::io::Cursor::new(&txt[..]))
+            .next()
+            .expect("an entry should be parsed")
+            .expect("the entry should be parsed successfully");
+        assert_eq!(entry.primary_accession(), Some("P12345"));
+        assert_eq!(entry.sequence.as_ref().unwrap().value, "MKLV");
+        assert!(entry.organism.is_none());
+        assert!(entry.protein_existence.is_none());
+
+        let warnings = entry.validate().unwrap_err();
+        assert!(warnings.contains(&ValidationWarning::MissingElement("organism", "entry")));
+        assert!(!warnings.contains(&ValidationWarning::MissingElement("sequence", "entry")));
+    }
+
+    #[test]
+    fn resume_from() {
+        let open = || std::io::BufReader::new(std::fs::File::open("tests/uniprot.xml").unwrap());
+
+        let mut parser = SequentialParser::new(open());
+        let first = parser.cursor();
+        let head = parser
+            .by_ref()
+            .take(125)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        let cursor = parser.cursor();
+        let next = parser.next().unwrap().unwrap();
+
+        let tail = super::resume_from(open(), cursor)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(head.len() + tail.len(), 250);
+        let accessions = tail
+            .iter()
+            .map(|e| e.accessions[0].clone())
+            .collect::<HashSet<_>>();
+        assert!(accessions.contains(&next.accessions[0]));
+
+        let count = super::resume_from(open(), first).unwrap().count();
+        assert_eq!(count, 250);
+
+        // the resumed parser reports positions in the source file
+        let mut resumed = super::resume_from(open(), cursor).unwrap();
+        assert!(resumed.cursor().offset >= cursor.offset);
+        #[cfg(feature = "threading")]
+        resumed.ordered();
+        resumed.by_ref().take(10).for_each(|e| drop(e.unwrap()));
+        let count = super::resume_from(open(), resumed.cursor())
+            .unwrap()
+            .count();
+        assert_eq!(count, 115);
+        assert!(resumed.bytes_read() >= resumed.cursor().offset);
+
+        // a cursor in the middle of an entry skips to the next one
+        let cursor = ParserCursor {
+            offset: cursor.offset + 100,
+        };
+        let count = super::resume_from(open(), cursor).unwrap().count();
+        assert_eq!(count, 124);
+
+        // a cursor after the last entry yields nothing
+        for entry in &mut parser {
+            entry.expect("entry should parse successfully");
+        }
+        let count = super::resume_from(open(), parser.cursor()).unwrap().count();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn parse_into() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let mut entries = Vec::with_capacity(250);
+        super::parse_into(std::io::BufReader::new(f), &mut entries)
+            .expect("entries should parse successfully");
+        assert_eq!(entries.len(), 250);
+        assert_eq!(entries.capacity(), 250);
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let expected = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully").accessions)
+            .collect::<Vec<_>>();
+        let accessions = entries.iter().map(|e| e.accessions.clone());
+        assert!(accessions.eq(expected));
+
+        let mut accessions = HashSet::new();
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        super::parse_into(
+            std::io::BufReader::new(f),
+            &mut AccessionSet(&mut accessions),
+        )
+        .expect("entries should parse successfully");
+        assert_eq!(accessions.len(), 250);
+
+        let mut xml = std::fs::read_to_string("tests/uniprot.xml").unwrap();
+        let last = xml.rfind("<entry dataset=\"Swiss-Prot\"").unwrap();
+        xml.replace_range(last..last + 28, "<entry dataset=\"Unknown\"");
+        let mut entries = Vec::new();
+        let result = super::parse_into(std::io::Cursor::new(xml), &mut entries);
+        let err = result.unwrap_err();
+        assert!(err.position().is_some());
+        assert!(matches!(err.inner(), Error::InvalidValue("dataset", _, _)));
+        assert_eq!(entries.len(), 249);
+    }
+
+    /// A collection only keeping the primary accession of entries.
+    struct AccessionSet<'a>(&'a mut HashSet<String>);
+
+    impl Extend<Entry> for AccessionSet<'_> {
+        fn extend<I: IntoIterator<Item = Entry>>(&mut self, entries: I) {
+            for entry in entries {
+                self.0.insert(entry.accessions[0].to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn parse_chain() {
+        let open = || std::io::BufReader::new(std::fs::File::open("tests/uniprot.xml").unwrap());
+        let readers: Vec<Box<dyn BufRead + Send>> = vec![
+            Box::new(open()),
+            Box::new(std::io::Cursor::new(Vec::new())),
+            Box::new(open()),
+        ];
+        let entries = super::parse_chain(readers)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(entries.len(), 500);
+
+        let readers: Vec<Box<dyn BufRead + Send>> = vec![
+            Box::new(open()),
+            Box::new(std::io::Cursor::new(b"<html></html>".to_vec())),
+        ];
+        let results = super::parse_chain(readers).collect::<Vec<_>>();
+        assert!(results[..250].iter().all(Result::is_ok));
+        let err = results[250].as_ref().unwrap_err();
+        assert!(matches!(err.inner(), Error::UnexpectedRoot(_)));
+    }
+
+    #[test]
+    fn namespace_prefix() {
+        let xml = std::fs::read_to_string("tests/uniprot.xml").unwrap();
+        let mut prefixed = String::with_capacity(xml.len() * 2);
+        let mut chars = xml.chars().peekable();
+        while let Some(c) = chars.next() {
+            prefixed.push(c);
+            if c == '<' {
+                if chars.peek() == Some(&'/') {
+                    prefixed.push(chars.next().unwrap());
+                }
+                if chars.peek().is_some_and(char::is_ascii_alphabetic) {
+                    prefixed.push_str("up:");
+                }
+            }
+        }
+        let prefixed = prefixed.replace("xmlns=\"", "xmlns:up=\"");
+        assert!(prefixed.contains("<up:entry "));
+
+        let expected = SequentialParser::new(xml.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        let entries = SequentialParser::new(prefixed.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(entries.len(), 250);
+        for (entry, expected) in entries.iter().zip(&expected) {
+            assert_eq!(entry.accessions, expected.accessions);
+            assert_eq!(entry.features.len(), expected.features.len());
+            assert_eq!(entry.comments.len(), expected.comments.len());
+        }
+
+        let count = crate::count_entries(prefixed.as_bytes()).unwrap();
+        assert_eq!(count, 250);
+
+        let mut parser = super::parse_refs(prefixed.as_bytes());
+        let mut n = 0;
+        while let Some(entry) = parser.next_entry() {
+            let entry = entry.expect("entry should parse successfully");
+            assert_eq!(
+                Some(entry.accession.as_ref()),
+                expected[n].primary_accession()
+            );
+            n += 1;
+        }
+        assert_eq!(n, 250);
+
+        #[cfg(feature = "threading")]
+        {
+            let entries = ThreadedParser::new(std::io::Cursor::new(prefixed.clone()))
+                .collect::<Result<Vec<_>, _>>()
+                .expect("entries should parse successfully");
+            assert_eq!(entries.len(), 250);
+        }
+
+        let mut parser = SequentialParser::new(prefixed.as_bytes());
+        parser.by_ref().take(125).for_each(|e| drop(e.unwrap()));
+        let cursor = parser.cursor();
+        let tail = super::resume_from(std::io::Cursor::new(prefixed.clone()), cursor)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(tail.len(), 125);
+        assert_eq!(tail[0].accessions, expected[125].accessions);
+
+        let end = ParserCursor {
+            offset: prefixed.len() as u64,
+        };
+        let count = super::resume_from(std::io::Cursor::new(prefixed), end)
+            .unwrap()
+            .count();
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn kinetics_km_values() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q43309"))
+            .expect("entry should be found");
+        let kinetics = entry
+            .comments
+            .iter()
+            .find_map(|c| match &c.ty {
+                comment::CommentType::BiophysicochemicalProperties(bcp) => bcp.kinetics.as_ref(),
+                _ => None,
+            })
+            .expect("entry should have kinetics");
+        let values = kinetics.km_values();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].value, 15.0);
+        assert_eq!(values[0].unit, "uM");
+        assert_eq!(values[0].substrate.as_deref(), Some("AdoMet"));
+    }
+
+    #[test]
+    fn mass_spectrometry() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("P0DKW9"))
+            .expect("entry should be found");
+        let ms = entry
+            .comments
+            .iter()
+            .find_map(|c| match &c.ty {
+                comment::CommentType::MassSpectrometry(ms) => Some(ms),
+                _ => None,
+            })
+            .expect("entry should have a mass spectrometry comment");
+        assert_eq!(ms.mass, Some(8752.02));
+        assert_eq!(ms.method.as_deref(), Some("Electrospray"));
+        assert!(entry.comments.iter().all(|c| c.locations.is_empty()));
+
+        let txt = r#"<entry dataset="Swiss-Prot" created="1986-07-21" modified="2022-12-14" version="213">
+  <accession>P12345</accession>
+  <comment type="mass spectrometry" mass="3495.8" error="0.2" method="MALDI" evidence="1">
+    <molecule>Peptide 1</molecule>
+    <location>
+      <begin position="25"/>
+      <end position="55"/>
+    </location>
+  </comment>
+</entry>"#;
+        let entry = super::parse_entry_str(txt).expect("entry should parse successfully");
+        let comment = &entry.comments[0];
+        assert_eq!(comment.evidences, vec![1]);
+        assert!(comment.molecule.is_some());
+        match &comment.ty {
+            comment::CommentType::MassSpectrometry(ms) => {
+                assert_eq!(ms.mass, Some(3495.8));
+                assert_eq!(ms.error.as_deref(), Some("0.2"));
+                assert_eq!(comment.locations.len(), 1);
+            }
+            other => panic!("unexpected comment type: {:?}", other),
+        }
+
+        let mut out = Vec::new();
+        super::write(&mut out, vec![entry.clone()]).expect("entry should be written");
+        let copy = super::parse_entry(std::io::Cursor::new(out)).unwrap();
+        assert_eq!(
+            format!("{:?}", copy.comments),
+            format!("{:?}", entry.comments)
+        );
+
+        let missing = txt.replace(r#" mass="3495.8""#, "");
+        let entry = super::parse_entry_str(&missing).expect("entry should parse successfully");
+        match &entry.comments[0].ty {
+            comment::CommentType::MassSpectrometry(ms) => assert_eq!(ms.mass, None),
+            other => panic!("unexpected comment type: {:?}", other),
+        }
+
+        let invalid = txt.replace("3495.8", "heavy");
+        match super::parse_entry_str(&invalid).map_err(Error::into_inner) {
+            Err(Error::InvalidValue("mass", "comment", value)) => assert_eq!(value.0, "heavy"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn molecule_id() {
+        let txt = r#"<entry dataset="Swiss-Prot" created="1986-07-21" modified="2022-12-14" version="213">
+  <accession>P12345</accession>
+  <comment type="subunit">
+    <molecule id="P12345-2"/>
+    <text>Homodimer.</text>
+  </comment>
+  <comment type="subunit">
+    <molecule>Isoform 3</molecule>
+    <text>Monomer.</text>
+  </comment>
+</entry>"#;
+        let entry = super::parse_entry_str(txt).expect("entry should parse successfully");
+        assert_eq!(
+            entry.comments[0].molecule,
+            Some(Molecule::Id("P12345-2".into()))
+        );
+        assert_eq!(
+            entry.comments[1].molecule,
+            Some(Molecule::Name("Isoform 3".into()))
+        );
+    }
+
+    #[test]
+    fn conflict_reference() {
+        let txt = r#"<entry dataset="Swiss-Prot" created="1986-07-21" modified="2022-12-14" version="213">
+  <accession>P12345</accession>
+  <comment type="sequence caution" evidence="3">
+    <conflict type="erroneous initiation" ref="2">
+      <sequence resource="EMBL-CDS" id="CAA12345" version="1"/>
+    </conflict>
+  </comment>
+</entry>"#;
+        let entry = super::parse_entry_str(txt).expect("entry should parse successfully");
+        match &entry.comments[0].ty {
+            comment::CommentType::SequenceCaution(conflict) => {
+                assert_eq!(conflict.ty, comment::ConflictType::ErroneousInitiation);
+                assert_eq!(conflict.reference.as_deref(), Some("2"));
+            }
+            other => panic!("unexpected comment type: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn citation_editors() {
+        let txt = r#"<entry dataset="Swiss-Prot" created="1986-07-21" modified="2022-12-14" version="213">
+  <accession>P12345</accession>
+  <reference key="1">
+    <citation type="book" date="1994" name="Plant molecular biology">
+      <editorList>
+        <person name="Smith J."/>
+        <consortium name="The Plant Consortium"/>
+      </editorList>
+      <authorList>
+        <person name="Doe J."/>
+      </authorList>
+    </citation>
+    <scope>NUCLEOTIDE SEQUENCE</scope>
+  </reference>
+</entry>"#;
+        let entry = super::parse_entry_str(txt).expect("entry should parse successfully");
+        let citation = &entry.references[0].citation;
+        assert_eq!(citation.authors.len(), 1);
+        assert_eq!(citation.authors[0].name(), "Doe J.");
+        assert_eq!(citation.editors.len(), 2);
+        assert_eq!(citation.editors[0].name(), "Smith J.");
+        assert_eq!(citation.editors[1].name(), "The Plant Consortium");
+    }
+
+    #[test]
+    fn parse_map() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = super::parse_map(std::io::BufReader::new(f)).unwrap();
+        assert_eq!(entries.len(), 250);
+        assert_eq!(entries["P15711"].accessions, vec!["P15711", "Q4N2B5"]);
+        assert!(!entries.contains_key("Q4N2B5"));
+
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = super::parse_map_with_secondary(std::io::BufReader::new(f)).unwrap();
+        assert!(Arc::ptr_eq(&entries["P15711"], &entries["Q4N2B5"]));
+        assert_eq!(entries["P68744"].primary_accession(), Some("P68744"));
+        assert_eq!(entries["P68745"].primary_accession(), Some("P68745"));
+        assert!(!entries.contains_key("P18556"));
+
+        let readers: Vec<Box<dyn BufRead + Send>> = vec![
+            Box::new(std::io::BufReader::new(
+                std::fs::File::open("tests/uniprot.xml").unwrap(),
+            )),
+            Box::new(std::io::BufReader::new(
+                std::fs::File::open("tests/uniprot.xml").unwrap(),
+            )),
+        ];
+        let chained = std::io::Cursor::new({
+            let mut out = Vec::new();
+            super::write(&mut out, super::parse_chain(readers).map(Result::unwrap)).unwrap();
+            out
+        });
+        match super::parse_map(chained) {
+            Err(Error::DuplicateAccession(_)) => (),
+            other => panic!("unexpected result: {:?}", other.map(|m| m.len())),
+        }
+    }
+
+    #[test]
+    fn secondary_accession_map() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let map = super::secondary_accession_map(std::io::BufReader::new(f)).unwrap();
+        assert_eq!(map["Q4N2B5"], "P15711");
+        assert!(!map.contains_key("P15711"));
+        assert!(!map.contains_key("P18556"));
+        assert!(!map.values().any(|primary| primary == "P0C9F0"));
+    }
+
+    #[test]
+    fn citation_identifiers() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q65209"))
+            .expect("entry should be found");
+        let citation = &entry.references[0].citation;
+        assert_eq!(citation.pubmed_id(), Some("2325203"));
+        assert_eq!(citation.doi(), Some("10.1128/jvi.64.5.2073-2081.1990"));
+    }
+
+    #[test]
+    fn citation_format() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q65209"))
+            .expect("entry should be found");
+        let formatted = entry.references[0].citation.format();
+        assert!(formatted.starts_with("Gonzalez A., Calvo V., "));
+        assert!(formatted.contains("Multigene families in African swine fever virus: family 360."));
+        assert!(
+            formatted.ends_with(" J. Virol. 64:2073-2081(1990)."),
+            "{}",
+            formatted
+        );
+    }
+
+    #[test]
+    fn reference_scope_categories() {
+        use self::model::reference::ReferenceScope;
+
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .next()
+            .expect("an entry should be parsed")
+            .expect("the entry should be parsed successfully");
+        assert_eq!(entry.primary_accession(), Some("P0C9F0"));
+        assert_eq!(
+            entry.references[0].scope_categories(),
+            vec![ReferenceScope::NucleotideSequence]
+        );
+    }
+
+    #[test]
+    fn deduplicate() {
+        use std::collections::HashSet;
+
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .take(2)
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        let copy = entries[0].clone();
+
+        let db_references = entries[0]
+            .db_references
+            .iter()
+            .chain(copy.db_references.iter())
+            .collect::<HashSet<_>>();
+        assert_eq!(db_references.len(), entries[0].db_references.len());
+        let names = entries
+            .iter()
+            .chain(std::iter::once(&copy))
+            .flat_map(|e| e.genes.iter().flat_map(|g| g.names.iter()))
+            .collect::<HashSet<_>>();
+        assert!(names.contains(&entries[1].genes[0].names[0]));
+        assert_eq!(
+            names.len(),
+            entries[0].genes[0].names.len() + entries[1].genes[0].names.len()
+        );
+    }
+
+    #[test]
+    fn features_at() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q91FL1"))
+            .expect("entry should be found");
+
+        let types = |pos| {
+            entry
+                .features_at(pos)
+                .into_iter()
+                .map(|f| f.ty.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(types(50), vec![FeatureType::Chain, FeatureType::Domain]);
+        assert_eq!(types(15), vec![FeatureType::Chain, FeatureType::Domain]);
+        assert_eq!(types(125), vec![FeatureType::Chain]);
+        assert_eq!(
+            types(200),
+            vec![FeatureType::Chain, FeatureType::CoiledCoilRegion]
+        );
+        assert!(types(0).is_empty());
+        assert!(types(400).is_empty());
+    }
+
+    #[test]
+    fn parse_slice() {
+        let entries = super::parse_slice(include_bytes!("../../tests/uniprot.xml"))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(entries.len(), 250);
+
+        assert_eq!(super::parse_slice(&[]).count(), 0);
+        let mut parser = super::parse_slice(b"   ");
+        let err = parser.next().unwrap().unwrap_err();
+        assert!(matches!(err.into_inner(), Error::Xml(_)));
+    }
+
+    #[test]
+    fn cofactor_chebi_id() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q42524"))
+            .expect("entry should be found");
+        let cofactor = entry
+            .comments
+            .iter()
+            .find_map(|c| match &c.ty {
+                comment::CommentType::Cofactor(cofactors) => cofactors.first(),
+                _ => None,
+            })
+            .expect("entry should have a cofactor");
+        assert_eq!(cofactor.name, "Mg(2+)");
+        assert_eq!(cofactor.chebi_id(), Some("CHEBI:18420"));
+
+        let mut cofactor = cofactor.clone();
+        cofactor.db_reference.ty = "Rhea".into();
+        assert_eq!(cofactor.chebi_id(), None);
+    }
+
+    #[test]
+    fn fragments() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .filter(|e| matches!(e.primary_accession(), Some("P84972" | "P0C9F0")))
+            .collect::<Vec<_>>();
+
+        let fragment = entries
+            .iter()
+            .find(|e| e.accessions[0] == "P84972")
+            .unwrap();
+        let sequence = fragment.sequence.as_ref().unwrap();
+        assert_eq!(sequence.fragment, Some(FragmentType::Single));
+        assert!(sequence.is_fragment());
+        assert!(!sequence.is_complete());
+        assert!(fragment.is_fragment());
+
+        let complete = entries
+            .iter()
+            .find(|e| e.accessions[0] == "P0C9F0")
+            .unwrap();
+        let sequence = complete.sequence.as_ref().unwrap();
+        assert!(sequence.is_complete());
+        assert!(!sequence.is_fragment());
+        assert!(!complete.is_fragment());
+
+        assert!(!Entry::new(Dataset::SwissProt).is_fragment());
+    }
+
+    #[test]
+    fn annotation_score() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .filter(|e| matches!(e.primary_accession(), Some("P37821" | "P0C9F0")))
+            .collect::<Vec<_>>();
+        let rich = entries
+            .iter()
+            .find(|e| e.accessions[0] == "P37821")
+            .unwrap();
+        let sparse = entries
+            .iter()
+            .find(|e| e.accessions[0] == "P0C9F0")
+            .unwrap();
+        assert_eq!(rich.annotation_score(), 5);
+        assert_eq!(sparse.annotation_score(), 2);
+        assert!(rich.annotation_score() > sparse.annotation_score());
+
+        let bare = Entry::new(Dataset::TrEmbl);
+        assert_eq!(bare.annotation_score(), 1);
+    }
+
+    #[test]
+    fn to_flat_string() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let mut entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("P0C9F0"))
+            .expect("entry should be found");
+
+        let dat = std::fs::read_to_string("tests/uniprot.dat").unwrap();
+        let expected = dat
+            .lines()
+            .take_while(|line| *line != "//")
+            .filter(|line| {
+                let codes = [
+                    "ID", "AC", "DE", "GN", "OS", "OC", "OX", "OH", "PE", "SQ", "  ",
+                ];
+                codes.iter().any(|code| line.starts_with(code))
+            })
+            .map(|line| format!("{}\n", line))
+            .collect::<String>();
+        assert_eq!(entry.to_flat_string(), format!("{}//\n", expected));
+
+        entry.accessions = (0..12)
+            .map(|i| format!("P0C9F{}", i).as_str().into())
+            .collect();
+        let flat = entry.to_flat_string();
+        let ac = flat
+            .lines()
+            .filter(|line| line.starts_with("AC"))
+            .collect::<Vec<_>>();
+        assert_eq!(ac.len(), 2);
+        assert!(ac.iter().all(|line| line.len() <= 80));
+
+        let parsed = super::parse_dat(std::io::Cursor::new(flat))
+            .next()
+            .expect("record should be found")
+            .expect("record should parse successfully");
+        assert_eq!(parsed.accessions, entry.accessions);
+        assert_eq!(
+            parsed.gene_names().collect::<Vec<_>>(),
+            entry.gene_names().collect::<Vec<_>>()
+        );
+        assert_eq!(parsed.recommended_name(), entry.recommended_name());
+        assert_eq!(
+            parsed.sequence.map(|s| s.value),
+            entry.sequence.map(|s| s.value)
+        );
+    }
+
+    #[test]
+    fn gene_names() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+
+        let entry = entries
+            .iter()
+            .find(|e| e.primary_accession() == Some("P18485"))
+            .expect("entry should be found");
+        let gene = &entry.genes[0];
+        assert_eq!(gene.primary(), Some("ACS2"));
+        assert_eq!(gene.synonyms(), vec!["ACC2", "PCVV4A"]);
+        assert!(gene.ordered_loci().is_empty());
+        assert!(gene.orf_names().is_empty());
+
+        let entry = entries
+            .iter()
+            .find(|e| e.primary_accession() == Some("Q37001"))
+            .expect("entry should be found");
+        let gene = &entry.genes[0];
+        assert_eq!(gene.primary(), Some("ACS5"));
+        assert_eq!(gene.synonyms(), vec!["ACC5", "ETO2"]);
+        assert_eq!(gene.ordered_loci(), vec!["At5g65800"]);
+        assert_eq!(gene.orf_names(), vec!["F6H11.90", "MPA24.15"]);
+
+        let mut gene = gene::Gene::default();
+        assert_eq!(gene.primary(), None);
+        gene.names
+            .push(gene::Name::new("A".into(), gene::NameType::Primary));
+        gene.names
+            .push(gene::Name::new("B".into(), gene::NameType::Primary));
+        assert_eq!(gene.primary(), Some("A"));
+    }
+
+    #[test]
+    fn accessions() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        for entry in SequentialParser::new(std::io::BufReader::new(f)) {
+            let entry = entry.expect("entry should parse successfully");
+            for accession in &entry.accessions {
+                assert!(
+                    accession.parse::<Accession>().is_ok(),
+                    "invalid accession: {}",
+                    accession
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn citation_dates() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q65209"))
+            .expect("entry should be found");
+        let dates = entry
+            .references
+            .iter()
+            .map(|r| r.citation.date.as_ref().map(|d| d.year()))
+            .collect::<Vec<_>>();
+        assert_eq!(dates, vec![Some(1990), Some(1995), Some(2020)]);
+
+        let txt = r#"<entry dataset="Swiss-Prot" created="1986-07-21" modified="2022-12-14" version="213">
+  <accession>P12345</accession>
+  <reference key="1">
+    <citation type="journal article" date="2003-03" name="J. Virol."/>
+  </reference>
+  <reference key="2">
+    <citation type="submission" date="2001" db="EMBL/GenBank/DDBJ databases"/>
+  </reference>
+  <reference key="3">
+    <citation type="submission" db="EMBL/GenBank/DDBJ databases"/>
+  </reference>
+  <reference key="4">
+    <citation type="submission" date="2019-01-01" db="EMBL/GenBank/DDBJ databases"/>
+  </reference>
+</entry>"#;
+        let entry = super::parse_entry_str(txt).expect("entry should parse successfully");
+        let date = entry.references[0].citation.date.as_ref().unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2003, 3, 1));
+        let date = entry.references[1].citation.date.as_ref().unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2001, 1, 1));
+        assert!(entry.references[2].citation.date.is_none());
+
+        let mut out = Vec::new();
+        super::write(&mut out, vec![entry]).expect("entry should be written");
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(r#"<citation type="journal article" date="2003-03""#));
+        assert!(out.contains(r#"<citation type="submission" date="2001""#));
+        assert!(out.contains(r#"<citation type="submission" date="2019-01-01""#));
+
+        let txt = txt.replace("2003-03", "March 2003");
+        assert!(super::parse_entry_str(&txt).is_err());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_iter() {
+        use rayon::iter::IntoParallelIterator;
+        use rayon::iter::ParallelIterator;
+
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let uniprot = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<UniProt, _>>()
+            .expect("entries should parse successfully");
+        let length = |entry: &Entry| entry.sequence.as_ref().map_or(0, |s| s.length);
+
+        let expected = uniprot.iter().map(length).sum::<usize>();
+        let actual = (&uniprot).into_par_iter().map(length).sum::<usize>();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn feature_variants() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        let variants = |accession: &str| {
+            entries
+                .iter()
+                .find(|e| e.primary_accession() == Some(accession))
+                .expect("entry should be found")
+                .features
+                .iter()
+                .filter_map(Feature::variant)
+                .collect::<Vec<_>>()
+        };
+
+        let variants_p37821 = variants("P37821");
+        let variant = variants_p37821
+            .iter()
+            .find(|v| v.position == 47)
+            .expect("variant should be found");
+        assert_eq!(variant.original, "E");
+        assert_eq!(variant.variation, vec!["D", "Q"]);
+
+        let variants_q37001 = variants("Q37001");
+        let variant = variants_q37001
+            .iter()
+            .find(|v| v.position == 459)
+            .expect("variant should be found");
+        assert_eq!(variant.original, "RVSWTDRVPDER");
+        assert_eq!(variant.variation, vec!["PGFMDRSCT"]);
+
+        let location = feature_location::FeatureLocation::Position(feature_location::Position {
+            pos: Some(73),
+            status: feature_location::Status::Certain,
+            evidence: Vec::new(),
+        });
+        let mut feature = Feature::new(FeatureType::SequenceVariant, location);
+        assert_eq!(feature.variant(), None);
+        feature.original = Some("A".into());
+        feature.variation.push("V".into());
+        assert_eq!(feature.variant().map(|v| v.position), Some(73));
+        feature.ty = FeatureType::Domain;
+        assert_eq!(feature.variant(), None);
+    }
+
+    #[test]
+    fn sequence_checksums() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        for entry in SequentialParser::new(std::io::BufReader::new(f)) {
+            let entry = entry.expect("entry should parse successfully");
+            let sequence = entry.sequence.expect("entry should have a sequence");
+            assert_eq!(sequence.crc64(), sequence.checksum);
+            assert!(sequence.verify_checksum());
+
+            // UniProt masses are rounded, and ambiguous residues estimated
+            let ambiguous = sequence.value.bytes().filter(|b| b"BZJX".contains(b));
+            let delta = sequence.computed_mass() - sequence.mass as f64;
+            assert!(delta.abs() <= 0.5 + ambiguous.count() as f64);
+        }
+
+        let mut sequence = Sequence::default();
+        assert_eq!(sequence.crc64(), 0);
+        assert_eq!(sequence.computed_mass(), 0.0);
+        sequence.value = "MU".into();
+        assert!(!sequence.verify_checksum());
+        assert!((sequence.computed_mass() - 299.2466).abs() < 1e-3);
+    }
+
+    #[test]
+    fn resolve_evidences() {
+        let mut entry = Entry::new(Dataset::SwissProt);
+        entry.evidences.push(Evidence::new(1, "ECO:0000269".into()));
+        entry.evidences.push(Evidence::new(2, "ECO:0000305".into()));
+
+        let mut comment = comment::Comment::new(comment::CommentType::Function);
+        comment.evidences = vec![2, 5];
+        entry.comments.push(comment);
+
+        let map = entry.evidence_map();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map[&1].ty, "ECO:0000269");
+
+        let evidences = entry.resolve_evidences(&entry.comments[0].evidences);
+        assert_eq!(evidences.len(), 1);
+        assert_eq!(evidences[0].key, 2);
+        assert_eq!(evidences[0].ty, "ECO:0000305");
+    }
+
+    #[test]
+    fn organelle_types() {
+        use self::model::gene_location::LocationType;
+
+        let data = std::fs::read_to_string("tests/uniprot.xml").unwrap();
+        let start = data.find("<entry").unwrap();
+        let end = data.find("</entry>").unwrap() + "</entry>".len();
+        let xml = &data[start..end];
+
+        let entry = super::parse_entry(std::io::Cursor::new(xml)).unwrap();
+        assert!(entry.gene_location.is_empty());
+        assert!(!entry.is_organellar());
+        assert!(entry.organelle_types().is_empty());
+
+        let i = xml.find("<reference").unwrap();
+        let locations = "<geneLocation type=\"mitochondrion\"/>\n  ";
+        let txt = format!("{}{}{}", &xml[..i], locations, &xml[i..]);
+        let entry = super::parse_entry(std::io::Cursor::new(txt)).unwrap();
+        assert!(entry.is_organellar());
+        assert_eq!(entry.organelle_types(), vec![LocationType::Mitochondrion]);
+
+        let locations = concat!(
+            "<geneLocation type=\"plasmid\"><name>pWR100</name></geneLocation>\n  ",
+            "<geneLocation type=\"plastid\"/>\n  ",
+            "<geneLocation type=\"plasmid\"><name status=\"unknown\">pB</name></geneLocation>\n  ",
+            "<geneLocation type=\"plastid\"/>\n  ",
+        );
+        let txt = format!("{}{}{}", &xml[..i], locations, &xml[i..]);
+        let entry = super::parse_entry(std::io::Cursor::new(txt)).unwrap();
+        assert_eq!(entry.gene_location.len(), 4);
+        assert_eq!(entry.gene_location[0].names[0].value, "pWR100");
+        assert!(entry.is_organellar());
+        assert_eq!(entry.organelle_types(), vec![LocationType::Plastid]);
+
+        let mut entry = entry;
+        entry
+            .gene_location
+            .retain(|loc| loc.ty == LocationType::Plasmid);
+        assert!(!entry.is_organellar());
+        assert!(entry.organelle_types().is_empty());
+    }
+
+    #[test]
+    fn try_from_str() {
+        use std::convert::TryFrom;
+
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+
+        assert_eq!(FeatureType::try_from("helix"), Ok(FeatureType::Helix));
+        for feature in entries.iter().flat_map(|e| e.features.iter()) {
+            assert_eq!(
+                FeatureType::try_from(feature.ty.as_str()),
+                Ok(feature.ty.clone())
+            );
+        }
+        for reference in entries.iter().flat_map(|e| e.references.iter()) {
+            let ty = reference.citation.ty;
+            assert_eq!(reference::CitationType::try_from(ty.as_str()), Ok(ty));
+        }
+        for name in entries
+            .iter()
+            .flat_map(|e| e.genes.iter().flat_map(|g| g.names.iter()))
+        {
+            assert_eq!(gene::NameType::try_from(name.ty.as_str()), Ok(name.ty));
+        }
+        for name in entries
+            .iter()
+            .flat_map(|e| e.organism.iter().flat_map(|o| o.names.iter()))
+        {
+            assert_eq!(organism::NameType::try_from(name.ty.as_str()), Ok(name.ty));
+        }
+        for direction in [
+            comment::Direction::LeftToRight,
+            comment::Direction::RightToLeft,
+        ] {
+            assert_eq!(
+                comment::Direction::try_from(direction.as_str()),
+                Ok(direction)
+            );
+        }
+        for resource in [comment::Resource::Embl, comment::Resource::EmblCds] {
+            assert_eq!(comment::Resource::try_from(resource.as_str()), Ok(resource));
+        }
+        for comment in entries.iter().flat_map(|e| e.comments.iter()) {
+            let kind = comment.ty.kind().expect("comment type should be known");
+            assert_eq!(
+                comment::CommentKind::try_from(comment.ty.to_string().as_str()),
+                Ok(kind)
+            );
+            assert_eq!(kind.as_str(), comment.ty.as_str());
+        }
+
+        assert_eq!(
+            FeatureType::try_from("future thing"),
+            Err(crate::error::InvalidValue::from("future thing"))
+        );
+    }
+
+    #[test]
+    fn sequence_whitespace() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let sequential = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let parsed = super::parse(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        for entry in sequential.iter().chain(parsed.iter()) {
+            let sequence = entry
+                .sequence
+                .as_ref()
+                .expect("entry should have a sequence");
+            assert_eq!(sequence.value.len(), sequence.length);
+            assert!(sequence.verify_checksum());
+        }
+
+        let data = std::fs::read_to_string("tests/uniprot.xml").unwrap();
+        let start = data.find("<entry").unwrap();
+        let end = data.find("</entry>").unwrap() + "</entry>".len();
+        let mut xml = data[start..end].to_string();
+        let value = sequential[0].sequence.as_ref().unwrap().value.to_string();
+        let wrapped = value
+            .as_bytes()
+            .chunks(60)
+            .map(|chunk| std::str::from_utf8(chunk).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n    ");
+        xml = xml.replace(&value, &format!("\n    {}\n  \t", wrapped));
+        let entry = super::parse_entry(std::io::Cursor::new(&xml)).unwrap();
+        let sequence = entry.sequence.as_ref().unwrap();
+        assert_eq!(sequence.value, value);
+        assert_eq!(sequence.length, value.len());
+
+        let length = format!("<sequence length=\"{}\"", value.len());
+        let mismatched = xml.replacen(
+            &length,
+            &format!("<sequence length=\"{}\"", value.len() + 1),
+            1,
+        );
+        let entry = super::parse_entry(std::io::Cursor::new(mismatched)).unwrap();
+        assert_eq!(entry.sequence.unwrap().length, value.len());
+    }
+
+    #[test]
+    fn is_membrane_protein() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let mut entries = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .filter(|e| matches!(e.primary_accession(), Some("P0C9K4") | Some("Q00379")))
+            .collect::<Vec<_>>();
+
+        let entry = &mut entries[1];
+        assert_eq!(entry.primary_accession(), Some("P0C9K4"));
+        assert!(entry.has_keyword("KW-0812"));
+        assert!(!entry.has_keyword("Transmembrane"));
+        assert!(entry.keyword_values().contains(&"Transmembrane helix"));
+        assert!(entry.is_membrane_protein());
+        entry.keywords.clear();
+        assert!(entry.keyword_values().is_empty());
+        assert!(entry.is_membrane_protein());
+
+        let entry = &entries[0];
+        assert_eq!(entry.primary_accession(), Some("Q00379"));
+        assert!(!entry.has_keyword("KW-0472"));
+        assert!(!entry.is_membrane_protein());
+    }
+
+    #[test]
+    fn reference_by_key() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let mut entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q65209"))
+            .expect("entry should be found");
+
+        let reference = entry.reference_by_key(2).expect("reference should exist");
+        assert_eq!(
+            reference.citation.titles,
+            vec!["Analysis of the complete nucleotide sequence of African swine fever virus."]
+        );
+        assert!(entry.reference_by_key(4).is_none());
+
+        let map = entry.reference_map();
+        assert_eq!(map.len(), 3);
+        assert_eq!(
+            map[&3].citation.titles,
+            vec!["The African Swine Fever Virus Transcriptome."]
+        );
+
+        let mut duplicate = entry.references[2].clone();
+        duplicate.key = 1;
+        entry.references.push(duplicate);
+        let first = &entry.references[0];
+        assert!(std::ptr::eq(entry.reference_by_key(1).unwrap(), first));
+        assert!(std::ptr::eq(entry.reference_map()[&1], first));
+    }
+
+    #[test]
+    fn experimental_evidences() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q65209"))
+            .expect("entry should be found");
+
+        let codes = entry
+            .evidences
+            .iter()
+            .map(|ev| ev.eco_code())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![
+                Some("ECO:0000250"),
+                Some("ECO:0000269"),
+                Some("ECO:0000305")
+            ]
+        );
+        let experimental = entry
+            .evidences
+            .iter()
+            .filter(|ev| ev.is_experimental())
+            .map(|ev| ev.key)
+            .collect::<Vec<_>>();
+        assert_eq!(experimental, vec![2]);
+    }
+
+    #[test]
+    fn cross_references() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+
+        let entry = entries
+            .iter()
+            .find(|e| e.primary_accession() == Some("E2RU97"))
+            .expect("entry should be found");
+        assert_eq!(entry.pdb_ids(), vec!["4F7R", "4ZQ0", "5BY9"]);
+        assert_eq!(entry.cross_references("pdb").count(), 0);
+        let pdb = entry.cross_references("PDB").next().unwrap();
+        assert_eq!(pdb.property_value("method"), Some("X-ray"));
+        assert_eq!(pdb.property_value("resolution"), Some("3.20 A"));
+        assert_eq!(pdb.property_value("unknown"), None);
+        assert_eq!(entry.cross_references("PDBsum").count(), 3);
+
+        let entry = entries
+            .iter()
+            .find(|e| e.primary_accession() == Some("Q7XQ85"))
+            .expect("entry should be found");
+        assert_eq!(entry.ec_numbers(), vec!["4.4.1.14"]);
+        let ec = entry.cross_references("EC").next().unwrap();
+        assert_eq!(ec.evidences, vec![1]);
+    }
+
+    #[test]
+    fn interaction_type() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|r| r.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q43309"))
+            .expect("entry should be found");
+        let interactions = entry
+            .comments
+            .iter()
+            .filter_map(|c| match &c.ty {
+                comment::CommentType::Interaction(interaction) => Some(interaction),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert!(interactions.len() >= 2);
+
+        let homodimer = &interactions[0];
+        assert_eq!(homodimer.ty(), comment::InteractionType::SelfInteraction);
+        assert_eq!(homodimer.interactants.0.interactant_id, "EBI-2436015");
+        assert_eq!(homodimer.interactants.1.interactant_id, "EBI-2436015");
+
+        let binary = &interactions[1];
+        assert_eq!(binary.ty(), comment::InteractionType::Binary);
+        assert_eq!(binary.interactants.0.interactant_id, "EBI-2436015");
+        assert_eq!(binary.interactants.1.interactant_id, "EBI-2356658");
+        assert_eq!(binary.interactants.1.id.as_deref(), Some("Q9SAR0"));
+        assert_eq!(binary.interactants.1.label.as_deref(), Some("ACS6"));
+    }
+
+    #[test]
+    fn organism_names() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        let organism = |accession: &str| {
+            entries
+                .iter()
+                .find(|e| e.primary_accession() == Some(accession))
+                .and_then(|e| e.organism.as_ref())
+                .expect("organism should be found")
+        };
+
+        let arath = organism("Q43309");
+        assert_eq!(arath.scientific_name(), Some("Arabidopsis thaliana"));
+        assert_eq!(arath.common_name(), Some("Mouse-ear cress"));
+        assert!(arath.synonyms().is_empty());
+
+        let myctu = organism("V5QRX7");
+        assert_eq!(
+            myctu.scientific_name(),
+            Some("Mycobacterium tuberculosis (strain ATCC 25618 / H37Rv)")
+        );
+        assert_eq!(myctu.common_name(), None);
+
+        let asfv = organism("Q65209");
+        assert_eq!(asfv.common_name(), Some("Ba71V"));
+        assert_eq!(asfv.synonyms(), vec!["ASFV"]);
+    }
+
+    #[test]
+    fn organism_lineage() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        let organism = |accession: &str| {
+            entries
+                .iter()
+                .find(|e| e.primary_accession() == Some(accession))
+                .and_then(|e| e.organism.as_ref())
+                .expect("organism should be found")
+        };
+
+        let myctu = organism("V5QRX7");
+        assert!(myctu.is_bacterial());
+        assert!(!myctu.is_eukaryotic());
+        assert_eq!(myctu.lineages[0].domain(), Some("Bacteria"));
+        assert!(myctu.lineages[0].contains("Actinobacteria"));
+        assert!(!myctu.lineages[0].contains("Bacillus"));
+
+        let arath = organism("Q43309");
+        assert!(arath.is_eukaryotic());
+        assert!(arath.lineages[0].contains("Viridiplantae"));
+
+        let asfv = organism("Q65209");
+        assert!(!asfv.is_bacterial());
+        assert!(!asfv.is_eukaryotic());
+        assert_eq!(asfv.lineages[0].domain(), Some("Viruses"));
+
+        let empty = organism::Organism {
+            lineages: vec![organism::Lineage::default()],
+            ..Default::default()
+        };
+        assert_eq!(empty.lineages[0].domain(), None);
+        assert!(!empty.is_bacterial());
+        assert!(!organism::Organism::default().is_eukaryotic());
+    }
+
+    #[test]
+    fn taxon_ids() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|r| r.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("P0C9F0"))
+            .expect("entry should be found");
+        let organism = entry.organism.as_ref().unwrap();
+        assert_eq!(organism.taxon_id(), Some(561445));
+        assert_eq!(
+            entry.host_taxon_ids(),
+            vec![6937, 85517, 41426, 273792, 9823]
+        );
+
+        let mut organism = organism::Organism::default();
+        assert_eq!(organism.taxon_id(), None);
+        for id in ["9606", "10090"] {
+            organism.db_references.push(DbReference {
+                ty: "NCBI Taxonomy".into(),
+                id: id.into(),
+                ..Default::default()
+            });
+        }
+        assert_eq!(organism.taxon_id(), Some(9606));
+    }
+
+    #[test]
+    fn count_entries() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let count = crate::count_entries(std::io::BufReader::new(f)).unwrap();
+        assert_eq!(count, 250);
+
+        // check tags split across buffer boundaries are counted once
+        for capacity in [1, 3, 7, 8, 9] {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let reader = std::io::BufReader::with_capacity(capacity, f);
+            assert_eq!(crate::count_entries(reader).unwrap(), 250);
+        }
+    }
+
+    #[test]
+    fn parse_refs() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let mut parser = super::parse_refs(std::io::BufReader::new(f));
+        let mut expected = entries.iter();
+        while let Some(entry) = parser.next_entry() {
+            let entry = entry.expect("entry should parse successfully");
+            let owned = expected.next().expect("an owned entry should be parsed");
+            assert_eq!(Some(entry.accession.as_ref()), owned.primary_accession());
+            assert_eq!(entry.name, owned.names[0].as_str());
+            let sequence = owned.sequence.as_ref().unwrap();
+            assert_eq!(entry.sequence, sequence.value.as_str());
+            assert!(matches!(entry.accession, std::borrow::Cow::Borrowed(_)));
+            assert!(matches!(entry.sequence, std::borrow::Cow::Borrowed(_)));
+        }
+        assert!(expected.next().is_none());
+        assert!(parser.next_entry().is_none());
+        assert_eq!(parser.error_position(), None);
+
+        let txt = "<uniprot><entry><accession>P00001</accession><name>TEST</name><sequence length=\"8\">\n  MKLV\n  MKLV \n</sequence></entry></uniprot>";
+        let mut parser = super::parse_refs(std::io::Cursor::new(txt));
+        let entry = parser
+            .next_entry()
+            .expect("an entry should be parsed")
+            .expect("entry should parse successfully");
+        assert_eq!(entry.sequence, "MKLVMKLV");
+        assert!(matches!(entry.sequence, std::borrow::Cow::Owned(_)));
+
+        let txt = "<uniprot>\n<entry><accession>P00001</accession></entry>\n</uniprot>";
+        let mut parser = super::parse_refs(std::io::Cursor::new(txt));
+        match parser.next_entry().map(|r| r.map_err(Error::into_inner)) {
+            Some(Err(Error::MissingElement("name", "entry"))) => (),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        let position = parser
+            .error_position()
+            .expect("error should have a position");
+        assert!(txt.find("<entry").unwrap() as u64 <= position);
+        assert!(position <= txt.find("\n</uniprot>").unwrap() as u64);
+    }
+
+    #[test]
+    fn parse_entry_str() {
+        let data = std::fs::read_to_string("tests/uniprot.xml").unwrap();
+        let start = data.find("<entry").unwrap();
+        let end = data.find("</entry>").unwrap() + "</entry>".len();
+        let xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\n  {}\n",
+            &data[start..end]
+        );
+
+        let entry = super::parse_entry_str(&xml).expect("entry should parse successfully");
+        assert_eq!(entry.primary_accession(), Some("P0C9F0"));
+        let sequence = entry.sequence.as_ref().unwrap();
+        assert_eq!(sequence.length, sequence.value.len());
+
+        let err = super::parse_entry_str("<?xml version=\"1.0\"?>\n").unwrap_err();
+        match err.into_inner() {
+            Error::Xml(XmlError::UnexpectedEof(_)) => (),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn index_round_trip() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let index = super::index(std::io::BufReader::new(f)).expect("index should build");
+        let accessions = entries
+            .iter()
+            .flat_map(|e| e.accessions.iter())
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(index.len(), accessions.len());
+        assert_eq!(index.get("P00000"), None);
+
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let mut reader = std::io::BufReader::new(f);
+        for accession in accessions {
+            let expected = entries
+                .iter()
+                .find(|e| e.accessions.contains(accession))
+                .unwrap();
+            let range = index.get(accession).expect("accession should be indexed");
+            let entry =
+                super::parse_entry_at(&mut reader, range).expect("entry should parse successfully");
+            assert_eq!(entry.primary_accession(), expected.primary_accession());
+        }
+    }
+
+    #[test]
+    fn sequence_attributes() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|r| r.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("A1IVR9"))
+            .expect("entry should be found");
+        let sequence = entry.sequence.as_ref().unwrap();
+        assert_eq!(sequence.version, 1);
+        assert_eq!(sequence.modified, Date::from_iso8601("2007-02-06").unwrap());
+        assert_eq!(sequence.precursor, Some(true));
+        assert_eq!(sequence.fragment, Some(FragmentType::Single));
+
+        let txt = br#"<uniprot><entry dataset="TrEMBL" created="2011-06-28" modified="2019-12-11" version="1"><accession>P00000</accession><name>TEST</name><sequence length="4" mass="400" checksum="0F7E6C568A43C6DA" modified="2010-06-15" version="2" fragment="multiple">MKLV</sequence></entry></uniprot>"#;
+        let entry = SequentialParser::new(std::io::Cursor::new(&txt[..]))
+            .next()
+            .expect("an entry should be parsed")
+            .expect("the entry should be parsed successfully");
+        let sequence = entry.sequence.as_ref().unwrap();
+        assert_eq!(sequence.version, 2);
+        assert_eq!(sequence.modified, Date::from_iso8601("2010-06-15").unwrap());
+        assert_eq!(sequence.precursor, None);
+        assert_eq!(sequence.fragment, Some(FragmentType::Multiple));
+    }
+
+    #[test]
+    fn fail_invalid_fragment() {
+        let txt = br#"<uniprot><entry dataset="TrEMBL" created="2011-06-28" modified="2019-12-11" version="1"><accession>P00000</accession><name>TEST</name><sequence length="4" mass="400" checksum="0F7E6C568A43C6DA" modified="2010-06-15" version="2" fragment="several">MKLV</sequence></entry></uniprot>"#;
+        let err = SequentialParser::new(std::io::Cursor::new(&txt[..]))
+            .next()
+            .expect("should raise an error")
+            .unwrap_err();
+        match err.into_inner() {
+            Error::InvalidValue(_, _, _) => (),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn subcellular_location_evidences() {
+        let subcellular_locations = |entry: &Entry| {
+            entry
+                .comments
+                .iter()
+                .find_map(|c| match &c.ty {
+                    comment::CommentType::SubcellularLocation(locs) => Some(locs.clone()),
+                    _ => None,
+                })
+                .expect("entry should have a subcellular location comment")
+        };
+
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|r| r.expect("entry should parse successfully"))
+            .filter(|e| matches!(e.primary_accession(), Some("Q9SNN8") | Some("Q4U9M9")))
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+
+        // Q4U9M9: topologies have evidences
+        let locs = subcellular_locations(&entries[0]);
+        assert_eq!(locs[0].locations[0].value, "Cell membrane");
+        assert_eq!(locs[0].locations[0].evidences, vec![4]);
+        assert_eq!(locs[0].topologies.len(), 2);
+        assert_eq!(locs[0].topologies[1].value, "GPI-anchor");
+        assert_eq!(locs[0].topologies[1].evidences, vec![4]);
+
+        // Q9SNN8: membrane location with an experimental evidence
+        let locs = subcellular_locations(&entries[1]);
+        assert_eq!(locs[0].locations[1].value, "Amyloplast membrane");
+        assert_eq!(locs[0].locations[1].evidences, vec![4]);
+        assert_eq!(entries[1].evidences[3].key, 4);
+        assert_eq!(entries[1].evidences[3].ty, "ECO:0000269");
+
+        let txt = br#"<uniprot><entry dataset="TrEMBL" created="2011-06-28" modified="2019-12-11" version="1"><accession>P00000</accession><name>TEST</name><comment type="subcellular location"><subcellularLocation><location>Cytoplasm</location><orientation evidence="1 2">Cytoplasmic side</orientation></subcellularLocation></comment><sequence length="4" mass="400" checksum="0F7E6C568A43C6DA" modified="2010-06-15" version="1">MKLV</sequence></entry></uniprot>"#;
+        let entry = SequentialParser::new(std::io::Cursor::new(&txt[..]))
+            .next()
+            .expect("an entry should be parsed")
+            .expect("the entry should be parsed successfully");
+        let locs = subcellular_locations(&entry);
+        assert_eq!(locs[0].locations[0].value, "Cytoplasm");
+        assert!(locs[0].locations[0].evidences.is_empty());
+        assert_eq!(locs[0].orientations[0].evidences, vec![1, 2]);
+    }
+
+    #[test]
+    fn disease_mim_id() {
+        let txt = br#"<uniprot><entry dataset="Swiss-Prot" created="2011-06-28" modified="2019-12-11" version="1"><accession>P00000</accession><name>TEST</name><comment type="disease"><disease id="DI-00342"><name>Cystic fibrosis</name><acronym>CF</acronym><description>A common generalized disorder of the exocrine glands.</description><dbReference type="MIM" id="219700"/></disease></comment><sequence length="4" mass="400" checksum="0F7E6C568A43C6DA" modified="2010-06-15" version="1">MKLV</sequence></entry></uniprot>"#;
+        let entry = SequentialParser::new(std::io::Cursor::new(&txt[..]))
+            .next()
+            .expect("an entry should be parsed")
+            .expect("the entry should be parsed successfully");
+        let disease = match &entry.comments[0].ty {
+            comment::CommentType::Disease(Some(disease)) => disease,
+            other => panic!("unexpected comment type: {:?}", other),
+        };
+        assert_eq!(disease.id, "DI-00342");
+        assert_eq!(disease.acronym, "CF");
+        assert_eq!(disease.mim_id(), Some("219700"));
+
+        let mut disease = disease.clone();
+        disease.db_reference.ty = "Orphanet".into();
+        assert_eq!(disease.mim_id(), None);
+    }
+
+    #[test]
+    fn comment_locations() {
+        let txt = br#"<uniprot><entry dataset="Swiss-Prot" created="2011-06-28" modified="2019-12-11" version="1"><accession>P00000</accession><name>TEST</name><comment type="RNA editing"><location><position position="2"/></location><location><position position="4"/></location><text>Partially edited.</text></comment><comment type="function"><text>Does something.</text></comment><sequence length="4" mass="400" checksum="0F7E6C568A43C6DA" modified="2010-06-15" version="1">MKLV</sequence></entry></uniprot>"#;
+        let entry = SequentialParser::new(std::io::Cursor::new(&txt[..]))
+            .next()
+            .expect("an entry should be parsed")
+            .expect("the entry should be parsed successfully");
+
+        let editing = &entry.comments[0];
+        let positions = editing
+            .locations
+            .iter()
+            .map(|l| l.bounds())
+            .collect::<Vec<_>>();
+        assert_eq!(positions, vec![Some((2, 2)), Some((4, 4))]);
+        match &editing.ty {
+            comment::CommentType::RnaEditing => (),
+            other => panic!("unexpected comment type: {:?}", other),
+        }
+        assert_eq!(editing.text[0].value, "Partially edited.");
+        assert!(entry.comments[1].locations.is_empty());
+
+        let mut out = Vec::new();
+        super::write(&mut out, vec![entry]).unwrap();
+        let entry = SequentialParser::new(std::io::Cursor::new(out))
+            .next()
+            .expect("an entry should be parsed")
+            .expect("the entry should be parsed successfully");
+        assert_eq!(entry.comments[0].locations.len(), 2);
+    }
+
+    #[test]
+    fn lenient_unknown_types() {
+        let txt = br#"<uniprot><entry dataset="Swiss-Prot" created="2011-06-28" modified="2019-12-11" version="1"><accession>P00000</accession><name>TEST</name><comment type="future comment"><location><position position="2"/></location><text>Something new.</text></comment><feature type="future thing"><location><position position="3"/></location></feature><sequence length="4" mass="400" checksum="0F7E6C568A43C6DA" modified="2010-06-15" version="1">MKLV</sequence></entry></uniprot>"#;
+
+        let result = SequentialParser::new(std::io::Cursor::new(&txt[..]))
+            .next()
+            .expect("an entry should be parsed");
+        let err = result.unwrap_err();
+        assert!(matches!(err.inner(), Error::InvalidValue("type", _, _)));
+
+        let entry = SequentialParser::new(std::io::Cursor::new(&txt[..]))
+            .lenient()
+            .next()
+            .expect("an entry should be parsed")
+            .expect("the entry should be parsed successfully");
+        assert_eq!(
+            entry.features[0].ty,
+            FeatureType::Unknown("future thing".into())
+        );
+        assert_eq!(entry.features[0].ty.as_str(), "future thing");
+        let comment = &entry.comments[0];
+        match &comment.ty {
+            comment::CommentType::Unknown(ty) => assert_eq!(ty, "future comment"),
+            other => panic!("unexpected comment type: {:?}", other),
+        }
+        assert_eq!(comment.text[0].value, "Something new.");
+        assert_eq!(comment.locations.len(), 1);
+
+        #[cfg(feature = "threading")]
+        {
+            let entry = ThreadedParser::new(std::io::Cursor::new(&txt[..]))
+                .lenient()
+                .next()
+                .expect("an entry should be parsed")
+                .expect("the entry should be parsed successfully");
+            assert_eq!(entry.features[0].ty.to_string(), "future thing");
+        }
+
+        let mut out = Vec::new();
+        super::write(&mut out, vec![entry]).unwrap();
+        let entry = SequentialParser::new(std::io::Cursor::new(out))
+            .lenient()
+            .next()
+            .expect("an entry should be parsed")
+            .expect("the entry should be parsed successfully");
+        assert_eq!(entry.comments[0].ty.as_str(), "future comment");
+        assert_eq!(entry.features[0].ty.as_str(), "future thing");
+    }
+
+    #[test]
+    fn component_ec_numbers() {
+        let txt = br#"<uniprot><entry dataset="Swiss-Prot" created="2011-06-28" modified="2019-12-11" version="1"><accession>P00000</accession><name>TEST</name><protein><recommendedName><fullName>Genome polyprotein</fullName><ecNumber>2.7.7.48</ecNumber></recommendedName><component><recommendedName><fullName>Protease</fullName><ecNumber>3.4.22.28</ecNumber></recommendedName></component><component><recommendedName><fullName>RNA-directed RNA polymerase</fullName><ecNumber>2.7.7.48</ecNumber></recommendedName></component></protein><dbReference type="EC" id="3.6.1.15"/><sequence length="4" mass="400" checksum="0F7E6C568A43C6DA" modified="2010-06-15" version="1">MKLV</sequence></entry></uniprot>"#;
+        let entry = SequentialParser::new(std::io::Cursor::new(&txt[..]))
+            .next()
+            .expect("an entry should be parsed")
+            .expect("the entry should be parsed successfully");
+        assert_eq!(entry.protein.ec_numbers(), vec!["2.7.7.48", "3.4.22.28"]);
+        assert_eq!(
+            entry.ec_numbers(),
+            vec!["2.7.7.48", "3.4.22.28", "3.6.1.15"]
+        );
+    }
+
+    #[test]
+    fn feature_source() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("P18485"))
+            .expect("entry should be found");
+
+        let conflict = entry
+            .features
+            .iter()
+            .find(|f| f.ty == FeatureType::SequenceConflict && f.reference.as_deref() == Some("2"))
+            .expect("feature should be found");
+        assert_eq!(conflict.reference_key(), Some(2));
+        let reference = entry
+            .feature_source(conflict)
+            .expect("reference should be found");
+        assert_eq!(reference.key, 2);
+        assert_eq!(
+            reference.citation.titles[0],
+            "Cloning and sequence of two different cDNAs encoding 1-aminocyclopropane-1-carboxylate synthase in tomato."
+        );
+
+        let chain = entry
+            .features
+            .iter()
+            .find(|f| f.ty == FeatureType::Chain)
+            .expect("feature should be found");
+        assert_eq!(chain.reference_key(), None);
+        assert!(entry.feature_source(chain).is_none());
+
+        let mut dangling = conflict.clone();
+        dangling.reference = Some("42".into());
+        assert_eq!(dangling.reference_key(), Some(42));
+        assert!(entry.feature_source(&dangling).is_none());
+    }
+
+    #[test]
+    fn feature_sequence() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let mut entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q4U9M9"))
+            .expect("entry should be found");
+        let sequence = entry
+            .sequence
+            .clone()
+            .expect("entry should have a sequence");
+        let length = sequence.value.len();
+
+        let signal = entry
+            .features
+            .iter()
+            .find(|f| f.ty == FeatureType::SignalPeptide)
+            .expect("feature should be found");
+        assert_eq!(signal.location.bounds(), Some((1, 19)));
+        assert_eq!(entry.feature_sequence(signal), Some(&sequence.value[..19]));
+
+        let chain = entry
+            .features
+            .iter()
+            .find(|f| f.ty == FeatureType::Chain)
+            .expect("feature should be found");
+        let (begin, end) = chain.location.bounds().unwrap();
+        assert_eq!(begin, 20);
+        assert_eq!(
+            entry.feature_sequence(chain),
+            Some(&sequence.value[begin - 1..end])
+        );
+
+        assert_eq!(sequence.subseq(1..=1), Some(&sequence.value[..1]));
+        assert_eq!(
+            sequence.subseq(length..=length),
+            Some(&sequence.value[length - 1..])
+        );
+        assert_eq!(sequence.subseq(1..=length), Some(sequence.value.as_str()));
+        assert_eq!(sequence.subseq(0..=3), None);
+        assert_eq!(sequence.subseq(length..=length + 1), None);
+        assert_eq!(sequence.subseq(length + 1..=length), None);
+
+        let mut unknown = signal.clone();
+        if let feature_location::FeatureLocation::Range(begin, _) = &mut unknown.location {
+            begin.pos = None;
+            begin.status = feature_location::Status::Unknown;
+        }
+        assert_eq!(entry.feature_sequence(&unknown), None);
+
+        entry.sequence = None;
+        assert_eq!(entry.feature_sequence(signal), None);
+    }
+
+    #[test]
+    fn feature_ligand() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("E2RU97"))
+            .expect("entry should be found");
+        let mut binding_site = entry
+            .features
+            .iter()
+            .find(|f| f.ty == FeatureType::BindingSite)
+            .cloned()
+            .expect("feature should be found");
+        assert_eq!(binding_site.ligand_name(), Some("O-phospho-L-serine"));
+        assert_eq!(binding_site.ligand_chebi(), Some("CHEBI:57524"));
+
+        if let Some(ligand) = binding_site.ligand.as_mut() {
+            ligand.db_reference = None;
+        }
+        assert_eq!(binding_site.ligand_name(), Some("O-phospho-L-serine"));
+        assert_eq!(binding_site.ligand_chebi(), None);
+
+        binding_site.ligand = None;
+        assert_eq!(binding_site.ligand_name(), None);
+        assert_eq!(binding_site.ligand_chebi(), None);
+    }
+
+    #[test]
+    fn reaction_rhea_id() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .filter(|e| matches!(e.primary_accession(), Some("Q00379") | Some("G2X4G0")))
+            .collect::<Vec<_>>();
+        let reactions = entries
+            .iter()
+            .map(|e| {
+                e.comments
+                    .iter()
+                    .find_map(|c| match &c.ty {
+                        comment::CommentType::CatalyticActivity(act) => Some(&act.reaction),
+                        _ => None,
+                    })
+                    .expect("comment should be found")
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(entries[0].primary_accession(), Some("Q00379"));
+        assert_eq!(reactions[0].rhea_id(), Some("RHEA:21744"));
+        assert_eq!(
+            reactions[0].chebi_participants(),
+            vec!["CHEBI:15378", "CHEBI:17509", "CHEBI:58360", "CHEBI:59789"]
+        );
 
-#[doc(inline)]
-pub use self::model::*;
+        assert_eq!(entries[1].primary_accession(), Some("G2X4G0"));
+        assert_eq!(reactions[1].rhea_id(), None);
+        assert!(reactions[1].chebi_participants().is_empty());
+    }
 
-/// The sequential parser type for UniProt entries.
-pub type SequentialParser<B> = super::parser::SequentialParser<B, UniProt>;
+    #[test]
+    fn diff() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q4U9M9"))
+            .expect("entry should be found");
+        assert!(entry.diff(&entry).is_empty());
 
-#[cfg(feature = "threading")]
-/// The threaded parser type for UniProt entries.
-pub type ThreadedParser<B> = super::parser::ThreadedParser<B, UniProt>;
+        let mut modified = entry.clone();
+        modified.keywords.reverse();
+        modified.features.reverse();
+        modified.db_references.rotate_left(1);
+        assert!(entry.diff(&modified).is_empty());
 
-/// The parser type for UniProt entries.
-pub type Parser<B> = super::parser::Parser<B, UniProt>;
+        let keyword = model::Keyword {
+            id: "KW-0002".into(),
+            value: "3D-structure".into(),
+            ..Default::default()
+        };
+        modified.keywords.push(keyword.clone());
+        let diff = entry.diff(&modified);
+        assert_eq!(diff.added_keywords, vec![&keyword]);
+        assert!(diff.removed_keywords.is_empty());
+        assert!(diff.added_features.is_empty() && diff.removed_features.is_empty());
+        assert!(diff.added_comments.is_empty() && diff.removed_comments.is_empty());
+        assert!(!diff.sequence_changed);
+        assert_eq!(modified.diff(&entry).removed_keywords, vec![&keyword]);
 
-/// Parse a Uniprot database XML file.
-///
-/// # Example:
-/// ```rust,no_run
-/// let mut client = ftp::FtpStream::connect("ftp.uniprot.org:21").unwrap();
-/// client.login("anonymous", "anonymous").unwrap();
-///
-/// let f = client.get("/pub/databases/uniprot/current_release/knowledgebase/complete/uniprot_sprot.xml.gz").unwrap();
-/// let dec = libflate::gzip::Decoder::new(f).unwrap();
-/// let mut parser = uniprot::uniprot::parse(std::io::BufReader::new(dec));
-///
-/// println!("{:#?}", parser.next());
-/// ```
-pub fn parse<B: BufRead + Send + 'static>(reader: B) -> Parser<B> {
-    Parser::new(reader)
-}
+        let removed = modified.features.pop().unwrap();
+        if let Some(sequence) = modified.sequence.as_mut() {
+            sequence.value.push('M');
+        }
+        let diff = entry.diff(&modified);
+        assert_eq!(diff.removed_features, vec![&removed]);
+        assert!(diff.sequence_changed);
+    }
 
-/// Parse a single UniProt entry.
-///
-/// This method is compatible with responses from the
-/// [EBI Proteins API](https://www.ebi.ac.uk/proteins/api/).
-///
-/// # Example
-///
-/// Retrieve a single protein entry using the Proteins API and the
-/// [`ureq`](https://crates.io/crates/ureq) to perform the HTTP request.
-///
-/// ```rust
-/// let api_url = "https://www.ebi.ac.uk/proteins/api/proteins/P02978";
-///
-/// let req = ureq::get(&api_url).set("Accept", "application/xml");
-/// let reader = std::io::BufReader::new(req.call().unwrap().into_reader());
-/// let entry = uniprot::uniprot::parse_entry(reader).unwrap();
-///
-/// println!("{:?}", entry);
-/// ```
-pub fn parse_entry<B: BufRead>(reader: B) -> <SequentialParser<B> as Iterator>::Item {
-    SequentialParser::parse_entry(reader)
-}
+    #[test]
+    fn sources_strains() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let mut entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q4U9M9"))
+            .expect("entry should be found");
+        assert!(entry.strains().contains(&"Ankara"));
+        let n = entry.sources().len();
 
-#[cfg(test)]
-mod tests {
+        let mut reference = entry.references[0].clone();
+        reference.sources = vec![
+            reference::Source::new("Leaf".into(), reference::SourceType::Tissue),
+            reference::Source::new("cv. Columbia".into(), reference::SourceType::Strain),
+            reference::Source::new("pTi".into(), reference::SourceType::Plasmid),
+        ];
+        entry.references.push(reference);
+        assert_eq!(entry.sources().len(), n + 3);
+        assert_eq!(entry.sources()[n].value, "Leaf");
+        assert_eq!(entry.strains().last(), Some(&"cv. Columbia"));
+        assert!(!entry.strains().contains(&"Leaf"));
+    }
 
-    use super::*;
-    use crate::error::Error;
-    use quick_xml::Error as XmlError;
+    #[test]
+    fn go_terms() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q4U9M9"))
+            .expect("entry should be found");
+
+        let membrane = entry
+            .db_references
+            .iter()
+            .find(|dbref| dbref.id == "GO:0005886")
+            .and_then(|dbref| dbref.go_term())
+            .expect("GO term should be found");
+        assert_eq!(membrane.aspect, GoAspect::CellularComponent);
+        assert_eq!(membrane.name, "plasma membrane");
+        assert_eq!(membrane.evidence_code, "ECO:0007669");
+
+        assert!(entry
+            .db_references
+            .iter()
+            .filter(|dbref| dbref.ty != "GO")
+            .all(|dbref| dbref.go_term().is_none()));
+    }
 
     #[test]
-    fn parse_swissprot_250() {
+    fn isoform_features() {
         let f = std::fs::File::open("tests/uniprot.xml").unwrap();
-        let entries = super::parse(std::io::BufReader::new(f))
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|e| e.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("Q42524"))
+            .expect("entry should be found");
+        let isoforms = entry
+            .comments
+            .iter()
+            .find_map(|c| match &c.ty {
+                comment::CommentType::AlternativeProduct(product) => Some(&product.isoforms),
+                _ => None,
+            })
+            .expect("alternative products should be found");
+        assert_eq!(isoforms.len(), 2);
+
+        assert!(matches!(
+            isoforms[0].sequence.ty,
+            comment::IsoformSequenceType::Displayed
+        ));
+        assert!(entry.isoform_features(&isoforms[0]).is_empty());
+
+        let features = entry.isoform_features(&isoforms[1]);
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0].ty, FeatureType::SpliceVariant);
+        assert_eq!(features[0].id.as_deref(), Some("VSP_008911"));
+    }
+
+    #[test]
+    fn comment_text_evidences() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|r| r.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("E2RU97"))
+            .expect("entry should be found");
+
+        let function = entry
+            .comments
+            .iter()
+            .find(|c| matches!(c.ty, comment::CommentType::Function))
+            .expect("entry should have a function comment");
+        assert_eq!(function.ty.to_string(), "function");
+        assert_eq!(function.text.len(), 1);
+        assert_eq!(function.text[0].evidences, vec![1, 3, 4, 13]);
+
+        let ptms = entry
+            .comments
+            .iter()
+            .filter(|c| matches!(c.ty, comment::CommentType::Ptm))
+            .map(|c| c.text[0].evidences.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(ptms, vec![vec![3, 4, 5], vec![3, 4, 6, 9, 10]]);
+    }
+
+    #[test]
+    fn parse_legacy_feature_types() {
+        let xml = std::fs::read_to_string("tests/uniprot.xml").unwrap();
+        let start = xml.find("<entry ").unwrap();
+        let end = xml.find("</entry>").unwrap() + "</entry>".len();
+        let features = [
+            "calcium-binding region",
+            "metal ion-binding site",
+            "nucleotide phosphate-binding region",
+        ]
+        .iter()
+        .map(|ty| {
+            format!(
+                "<feature type=\"{}\"><location><position position=\"1\"/></location></feature>",
+                ty
+            )
+        })
+        .collect::<String>();
+        let entry = xml[start..end].replacen("<evidence ", &format!("{}<evidence ", features), 1);
+
+        let entry = super::parse_entry(std::io::Cursor::new(entry.as_bytes()))
+            .expect("entry should parse successfully");
+        let types = entry
+            .features
+            .iter()
+            .map(|f| f.ty.clone())
+            .filter(|ty| {
+                matches!(
+                    ty,
+                    FeatureType::CalciumBindingRegion
+                        | FeatureType::MetalIonBindingSite
+                        | FeatureType::NucleotidePhosphateBindingRegion
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                FeatureType::CalciumBindingRegion,
+                FeatureType::MetalIonBindingSite,
+                FeatureType::NucleotidePhosphateBindingRegion,
+            ]
+        );
+    }
+
+    #[test]
+    fn entry_to_fasta() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entry = SequentialParser::new(std::io::BufReader::new(f))
+            .map(|r| r.expect("entry should parse successfully"))
+            .find(|e| e.primary_accession() == Some("P93259"))
+            .expect("entry should be found");
+        assert_eq!(
+            entry.to_fasta(),
+            concat!(
+                ">sp|P93259|1433_MESCR 14-3-3-like protein OS=Mesembryanthemum crystallinum OX=3544 GN=GBF PE=2 SV=1\n",
+                "MSSESSREENVYMAKLAEQAERYEEMVEFMEKVAKMTDTEELSVEERNLLSVAYKNVIGA\n",
+                "RRASWRIISSIEQKEESRGNEDHVSTIKEYRGKIETELSKICDGILNLLESHLIPSASTA\n",
+                "ESKVFYLKMKGDYHRYLAEFKTGAERKEAAENTLLAYKSAQDIALAELAPTHPIRLGLAL\n",
+                "NFSVFYYEILNSPDRACNLAKQAFDEAISELDTLGEESYKDSTLIMQLLRDNLTLWTSDN\n",
+                "AEEGGDEIKEAAAKRESGEEKPQQ\n",
+            )
+        );
+
+        let mut entry = Entry::new(Dataset::TrEmbl);
+        entry.accessions.push("A0A000".into());
+        entry.names.push("A0A000_HUMAN".into());
+        assert_eq!(entry.to_fasta(), ">tr|A0A000|A0A000_HUMAN\n");
+
+        entry.protein_existence = Some(protein::ProteinExistence::Uncertain);
+        entry.sequence = Some(Sequence {
+            value: "MKV".into(),
+            version: 1,
+            ..Default::default()
+        });
+        assert_eq!(entry.to_fasta(), ">tr|A0A000|A0A000_HUMAN PE=5 SV=1\nMKV\n");
+    }
+
+    #[test]
+    fn write_roundtrip() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .take(10)
             .collect::<Result<Vec<_>, _>>()
             .expect("entries should parse successfully");
-        assert_eq!(entries.len(), 250);
+
+        let mut out = Vec::new();
+        super::write(&mut out, entries.clone()).expect("entries should be written");
+
+        let copies = SequentialParser::new(std::io::Cursor::new(out))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("written entries should parse successfully");
+        assert_eq!(copies.len(), entries.len());
+        for (entry, copy) in entries.iter().zip(copies.iter()) {
+            assert_eq!(format!("{:?}", entry), format!("{:?}", copy));
+        }
+    }
+
+    #[test]
+    fn parse_dat_equivalent() {
+        let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+        let xml_entries = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+
+        let f = std::fs::File::open("tests/uniprot.dat").unwrap();
+        let dat_entries = super::parse_dat(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        assert_eq!(dat_entries.len(), 3);
+
+        for dat in &dat_entries {
+            let xml = xml_entries
+                .iter()
+                .find(|e| e.accessions == dat.accessions)
+                .expect("entry should be found in XML file");
+            assert_eq!(dat.names, xml.names);
+            assert_eq!(dat.created, xml.created);
+            assert_eq!(dat.modified, xml.modified);
+            assert_eq!(dat.version, xml.version);
+            assert_eq!(dat.protein_existence, xml.protein_existence);
+
+            let names = |p: &protein::Protein| format!("{:?}", p.name);
+            assert_eq!(names(&dat.protein), names(&xml.protein));
+
+            let genes = |e: &Entry| {
+                e.genes
+                    .iter()
+                    .flat_map(|g| g.names.iter().map(|n| (n.value.clone(), n.ty)))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(genes(dat), genes(xml));
+
+            let organism = |o: &organism::Organism| format!("{:?}", o);
+            assert_eq!(
+                organism(dat.organism.as_ref().unwrap()),
+                organism(xml.organism.as_ref().unwrap())
+            );
+            assert_eq!(dat.organism_hosts.len(), xml.organism_hosts.len());
+            for (dat_host, xml_host) in dat.organism_hosts.iter().zip(&xml.organism_hosts) {
+                assert_eq!(organism(dat_host), organism(xml_host));
+            }
+
+            let keywords = |e: &Entry| {
+                e.keywords
+                    .iter()
+                    .map(|k| k.value.clone())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(keywords(dat), keywords(xml));
+
+            let (dat_seq, xml_seq) = (
+                dat.sequence.as_ref().unwrap(),
+                xml.sequence.as_ref().unwrap(),
+            );
+            assert_eq!(dat_seq.value, xml_seq.value);
+            assert_eq!(dat_seq.length, xml_seq.length);
+            assert_eq!(dat_seq.mass, xml_seq.mass);
+            assert_eq!(dat_seq.checksum, xml_seq.checksum);
+            assert_eq!(dat_seq.modified, xml_seq.modified);
+            assert_eq!(dat_seq.version, xml_seq.version);
+        }
+    }
+
+    #[test]
+    fn parse_dat_unexpected_eof() {
+        let txt = &b"ID   1433_MESCR              Reviewed;         264 AA.\nAC   P93259;\n"[..];
+        let mut parser = super::parse_dat(std::io::Cursor::new(txt));
+        let err = parser.next().expect("should raise an error").unwrap_err();
+        match err.into_inner() {
+            Error::Xml(XmlError::UnexpectedEof(_)) => (),
+            other => panic!("unexpected error: {:?}", other),
+        }
+        assert_eq!(parser.error_position(), Some(txt.len() as u64));
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    #[ignore = "requires network access"]
+    fn fetch_entry() {
+        let entry = super::fetch("P02978").expect("entry should be fetched");
+        assert_eq!(entry.primary_accession(), Some("P02978"));
+    }
+
+    /// Get the test file with every other entry marked as unreviewed, along
+    /// with the accessions of the reviewed entries.
+    fn mixed_datasets() -> (String, Vec<String>) {
+        let xml = std::fs::read_to_string("tests/uniprot.xml").unwrap();
+        let mut parts = xml.split("dataset=\"Swiss-Prot\"");
+        let mut mixed = String::from(parts.next().unwrap());
+        for (i, part) in parts.enumerate() {
+            let dataset = if i % 2 == 0 { "Swiss-Prot" } else { "TrEMBL" };
+            mixed.push_str(&format!("dataset=\"{}\"", dataset));
+            mixed.push_str(part);
+        }
+        let reviewed = SequentialParser::new(std::io::Cursor::new(&xml))
+            .step_by(2)
+            .map(|e| e.unwrap().accessions[0].to_string())
+            .collect();
+        (mixed, reviewed)
     }
 
     mod sequential {
         use super::*;
 
+        #[test]
+        fn reviewed_only() {
+            let (xml, reviewed) = mixed_datasets();
+            let entries = SequentialParser::new(std::io::Cursor::new(&xml))
+                .reviewed_only()
+                .collect::<Result<Vec<_>, _>>()
+                .expect("entries should parse successfully");
+            assert_eq!(entries.len(), 125);
+            assert!(entries
+                .iter()
+                .all(|e| matches!(e.dataset, Dataset::SwissProt)));
+            let accessions = entries
+                .iter()
+                .map(|e| e.accessions[0].to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(accessions, reviewed);
+
+            let txt = xml.replacen("dataset=\"TrEMBL\"", "", 1);
+            let results = SequentialParser::new(std::io::Cursor::new(&txt))
+                .reviewed_only()
+                .collect::<Vec<_>>();
+            assert_eq!(results.len(), 2);
+            assert!(results[0].is_ok());
+            assert!(matches!(
+                results[1].as_ref().map_err(Error::context),
+                Err(Some(("dataset", "entry")))
+            ));
+        }
+
+        #[test]
+        fn for_each_ref() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let expected = SequentialParser::new(std::io::BufReader::new(f))
+                .map(|e| e.unwrap())
+                .collect::<Vec<_>>();
+
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let mut count = 0;
+            SequentialParser::new(std::io::BufReader::new(f))
+                .for_each_ref(|entry| {
+                    assert_eq!(format!("{:?}", entry), format!("{:?}", expected[count]));
+                    count += 1;
+                    Ok(())
+                })
+                .expect("entries should parse successfully");
+            assert_eq!(count, 250);
+
+            let (xml, reviewed) = mixed_datasets();
+            let mut accessions = Vec::new();
+            let mut parser = SequentialParser::new(std::io::Cursor::new(&xml));
+            parser.reviewed_only();
+            parser
+                .for_each_ref(|entry| {
+                    accessions.push(entry.accessions[0].to_string());
+                    Ok(())
+                })
+                .expect("entries should parse successfully");
+            assert_eq!(accessions, reviewed);
+
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let mut count = 0;
+            let err = SequentialParser::new(std::io::BufReader::new(f))
+                .for_each_ref(|_| {
+                    count += 1;
+                    match count {
+                        3 => Err(Error::DuplicateAccession(String::from("P00001"))),
+                        _ => Ok(()),
+                    }
+                })
+                .unwrap_err();
+            assert!(matches!(err, Error::DuplicateAccession(_)));
+            assert_eq!(count, 3);
+        }
+
+        #[test]
+        fn for_each_ref_error() {
+            let xml = std::fs::read_to_string("tests/uniprot.xml").unwrap();
+            let mut entries = xml
+                .match_indices("<entry ")
+                .map(|(i, _)| {
+                    let j = i + xml[i..].find("</entry>").unwrap() + "</entry>".len();
+                    xml[i..j].to_string()
+                })
+                .take(3)
+                .collect::<Vec<_>>();
+            entries[1] = entries[1].replacen("</fullName>", "</shortName>", 1);
+            let txt = format!("<uniprot>{}</uniprot>", entries.join("\n"));
+
+            let mut count = 0;
+            let mut parser = SequentialParser::new(std::io::Cursor::new(txt.as_bytes()));
+            assert!(parser
+                .for_each_ref(|_| {
+                    count += 1;
+                    Ok(())
+                })
+                .is_err());
+            assert_eq!(count, 1);
+            assert!(parser.error_position().is_some());
+            assert!(parser.next().is_none());
+
+            let mut parser = SequentialParser::new(std::io::Cursor::new(txt.as_bytes()));
+            parser.skip_errors();
+            assert!(parser.for_each_ref(|_| Ok(())).is_err());
+            let entry = parser
+                .next()
+                .expect("an entry should be parsed")
+                .expect("the entry should be parsed successfully");
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let expected = SequentialParser::new(std::io::BufReader::new(f))
+                .nth(2)
+                .expect("an entry should be parsed")
+                .expect("the entry should be parsed successfully");
+            assert_eq!(entry.accessions, expected.accessions);
+            assert!(parser.next().is_none());
+        }
+
         #[test]
         fn parse_single_entry() {
             let f = std::fs::File::open("tests/uniprot.xml").unwrap();
@@ -85,6 +3243,41 @@ mod tests {
                 .expect("the entry should be parsed successfully");
         }
 
+        #[test]
+        fn metadata() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let parser = SequentialParser::new(std::io::BufReader::new(f));
+            assert_eq!(parser.metadata(), None);
+            assert_eq!(parser.count(), 250);
+
+            let txt = br#"<uniprot release="2023_01" version="2.1">
+                <copyright>
+                    Copyrighted by the UniProt Consortium.
+                </copyright>
+                <entry dataset="Swiss-Prot" created="2011-06-28" modified="2019-12-11" version="1">
+                    <accession>P00000</accession>
+                    <name>TEST</name>
+                    <sequence length="4" mass="400" checksum="0F7E6C568A43C6DA" modified="2010-06-15" version="1">MKLV</sequence>
+                </entry>
+            </uniprot>"#;
+            let mut parser = SequentialParser::new(std::io::Cursor::new(&txt[..]));
+            let metadata = parser.metadata().expect("metadata should be found");
+            assert_eq!(metadata.release.as_deref(), Some("2023_01"));
+            assert_eq!(metadata.version.as_deref(), Some("2.1"));
+            assert_eq!(metadata.release_date, None);
+            assert_eq!(
+                metadata.copyright.as_deref(),
+                Some("Copyrighted by the UniProt Consortium.")
+            );
+
+            let entry = parser
+                .next()
+                .expect("an entry should be parsed")
+                .expect("the entry should be parsed successfully");
+            assert_eq!(entry.primary_accession(), Some("P00000"));
+            assert!(parser.next().is_none());
+        }
+
         #[test]
         fn fail_unexpected_eof() {
             let txt = &b"<uniprot><entry dataset=\"Swiss-Prot\" created=\"2011-06-28\" modified=\"2019-12-11\" version=\"39\">"[..];
@@ -92,12 +3285,109 @@ mod tests {
                 .next()
                 .expect("should raise an error")
                 .unwrap_err();
-            match err {
+            match err.into_inner() {
                 Error::Xml(XmlError::UnexpectedEof(_)) => (),
                 other => panic!("unexpected error: {:?}", other),
             }
         }
 
+        #[test]
+        fn bytes_read() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let size = f.metadata().unwrap().len();
+            let mut parser = SequentialParser::new(std::io::BufReader::new(f));
+
+            let mut last = parser.bytes_read();
+            while let Some(entry) = parser.next() {
+                entry.expect("entry should parse successfully");
+                assert!(parser.bytes_read() >= last);
+                last = parser.bytes_read();
+            }
+            assert!(last <= size);
+            assert!(size - last < 16);
+        }
+
+        #[test]
+        fn stats() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let size = f.metadata().unwrap().len();
+            let mut parser = SequentialParser::new(std::io::BufReader::new(f)).with_stats();
+            assert_eq!(parser.stats().entries, 0);
+            assert_eq!(parser.stats().elapsed, std::time::Duration::default());
+
+            for entry in &mut parser {
+                entry.expect("entry should parse successfully");
+            }
+            let stats = parser.stats();
+            assert_eq!(stats.entries, 250);
+            assert!(stats.bytes <= size);
+            assert!(size - stats.bytes < 16);
+            assert_eq!(parser.stats().elapsed, stats.elapsed);
+
+            let txt = &b"<uniprot></uniprot>"[..];
+            let mut parser = SequentialParser::new(txt).with_stats();
+            assert!(parser.next().is_none());
+            assert_eq!(parser.stats().entries, 0);
+            assert_eq!(parser.stats().entries_per_second(), 0.0);
+        }
+
+        #[test]
+        fn ignore() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let mut parser = SequentialParser::new(std::io::BufReader::new(f));
+            parser
+                .ignore("comment")
+                .ignore("reference")
+                .ignore("lineage");
+            let mut count = 0;
+            for entry in parser {
+                let entry = entry.expect("entry should parse successfully");
+                assert!(!entry.accessions.is_empty());
+                assert!(entry.references.is_empty());
+                assert!(entry.comments.is_empty());
+                let organism = entry.organism.expect("organism should be parsed");
+                assert!(organism.lineages.is_empty());
+                assert!(!organism.names.is_empty());
+                count += 1;
+            }
+            assert_eq!(count, 250);
+
+            // ignored elements do not leak to other parsers of the thread
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let entry = SequentialParser::new(std::io::BufReader::new(f))
+                .next()
+                .expect("an entry should be parsed")
+                .expect("the entry should be parsed successfully");
+            assert!(!entry.references.is_empty());
+        }
+
+        #[test]
+        fn skip_errors() {
+            let xml = std::fs::read_to_string("tests/uniprot.xml").unwrap();
+            let mut entries = xml
+                .match_indices("<entry ")
+                .map(|(i, _)| {
+                    let j = i + xml[i..].find("</entry>").unwrap() + "</entry>".len();
+                    xml[i..j].to_string()
+                })
+                .take(3)
+                .collect::<Vec<_>>();
+            entries[1] = entries[1].replacen("</fullName>", "</shortName>", 1);
+            let txt = format!("<uniprot>{}</uniprot>", entries.join("\n"));
+
+            let mut parser = SequentialParser::new(std::io::Cursor::new(txt.as_bytes()));
+            let results = parser.skip_errors().collect::<Vec<_>>();
+            assert_eq!(results.len(), 3);
+            assert!(results[0].is_ok());
+            assert!(results[1].is_err());
+            assert!(results[2].is_ok());
+
+            let mut parser = SequentialParser::new(std::io::Cursor::new(txt.as_bytes()));
+            let results = parser.by_ref().collect::<Vec<_>>();
+            assert_eq!(results.len(), 2);
+            assert!(results[1].is_err());
+        }
+
         #[test]
         fn fail_unexpected_root() {
             let txt = &b"<something><entry>"[..];
@@ -105,16 +3395,94 @@ mod tests {
                 .next()
                 .expect("should raise an error")
                 .unwrap_err();
-            match err {
+            match err.into_inner() {
                 Error::UnexpectedRoot(r) => assert_eq!(r, "something"),
                 other => panic!("unexpected error: {:?}", other),
             }
         }
+
+        #[test]
+        fn error_position() {
+            let txt = "<uniprot>\n<entry dataset=\"Swiss-Prot\"></protein></entry>\n</uniprot>";
+            let mut parser = SequentialParser::new(std::io::Cursor::new(txt));
+            assert!(parser.next().expect("should raise an error").is_err());
+            let start = txt.find("<entry").unwrap() as u64;
+            let end = (txt.find("</protein>").unwrap() + "</protein>".len()) as u64;
+            let position = parser
+                .error_position()
+                .expect("error should have a position");
+            assert!(start <= position && position <= end);
+
+            let mut parser = SequentialParser::new(std::io::Cursor::new(""));
+            assert!(parser.next().expect("should raise an error").is_err());
+            assert_eq!(parser.error_position(), Some(0));
+        }
     }
 
     #[cfg(feature = "threading")]
     mod threaded {
         use super::*;
+        use crate::parser::ParserConfig;
+        use std::io::Read;
+        use std::num::NonZeroUsize;
+
+        #[test]
+        fn reviewed_only() {
+            let (xml, reviewed) = mixed_datasets();
+            let entries = ThreadedParser::new(std::io::Cursor::new(xml.clone().into_bytes()))
+                .ordered()
+                .reviewed_only()
+                .collect::<Result<Vec<_>, _>>()
+                .expect("entries should parse successfully");
+            assert_eq!(entries.len(), 125);
+            assert!(entries
+                .iter()
+                .all(|e| matches!(e.dataset, Dataset::SwissProt)));
+            let accessions = entries
+                .iter()
+                .map(|e| e.accessions[0].to_string())
+                .collect::<Vec<_>>();
+            assert_eq!(accessions, reviewed);
+
+            let txt = xml.replacen("dataset=\"TrEMBL\"", "", 1);
+            let results = ThreadedParser::new(std::io::Cursor::new(txt.into_bytes()))
+                .ordered()
+                .reviewed_only()
+                .collect::<Vec<_>>();
+            assert_eq!(results.len(), 126);
+            assert!(matches!(
+                results[1].as_ref().map_err(Error::context),
+                Err(Some(("dataset", "entry")))
+            ));
+        }
+
+        #[test]
+        fn max_entry_bytes() {
+            let max = NonZeroUsize::new(1024).unwrap();
+
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let entries = ThreadedParser::new(std::io::BufReader::new(f))
+                .max_entry_bytes(NonZeroUsize::new(1 << 20).unwrap())
+                .collect::<Result<Vec<_>, _>>()
+                .expect("entries should parse successfully");
+            assert_eq!(entries.len(), 250);
+
+            for prefix in [&b"<uniprot><entry>"[..], &b"<uniprot><entry "[..]] {
+                let reader = std::io::BufReader::new(
+                    std::io::Cursor::new(prefix).chain(std::io::repeat(b' ')),
+                );
+                let mut parser = ThreadedParser::new(reader);
+                parser.max_entry_bytes(max);
+                let err = parser
+                    .next()
+                    .expect("an error should be yielded")
+                    .unwrap_err();
+                assert_eq!(parser.error_position(), Some(9));
+                assert_eq!(err.position(), Some(9));
+                assert!(matches!(err.inner(), Error::EntryTooLarge(1024)));
+                assert!(parser.next().is_none());
+            }
+        }
 
         #[test]
         fn parse_single_entry() {
@@ -125,6 +3493,150 @@ mod tests {
                 .expect("the entry should be parsed successfully");
         }
 
+        #[test]
+        fn bytes_read() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let size = f.metadata().unwrap().len();
+            let mut parser = ThreadedParser::new(std::io::BufReader::new(f));
+
+            let mut last = parser.bytes_read();
+            while let Some(entry) = parser.next() {
+                entry.expect("entry should parse successfully");
+                assert!(parser.bytes_read() >= last);
+                last = parser.bytes_read();
+            }
+            assert_eq!(parser.bytes_read(), size);
+        }
+
+        #[test]
+        fn stats() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let size = f.metadata().unwrap().len();
+            let mut parser = ThreadedParser::new(std::io::BufReader::new(f)).with_stats();
+            assert_eq!(parser.stats().entries, 0);
+
+            for entry in &mut parser {
+                entry.expect("entry should parse successfully");
+            }
+            let stats = parser.stats();
+            assert_eq!(stats.entries, 250);
+            assert_eq!(stats.bytes, size);
+            assert!(stats.entries_per_second() > 0.0);
+        }
+
+        #[test]
+        fn ignore() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let mut parser = ThreadedParser::new(std::io::BufReader::new(f));
+            parser.ignore("reference");
+            let mut count = 0;
+            for entry in parser {
+                let entry = entry.expect("entry should parse successfully");
+                assert!(entry.references.is_empty());
+                count += 1;
+            }
+            assert_eq!(count, 250);
+        }
+
+        #[test]
+        #[should_panic(expected = "once the parser has started")]
+        fn ignore_after_start() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let mut parser = ThreadedParser::new(std::io::BufReader::new(f));
+            parser.next();
+            parser.ignore("reference");
+        }
+
+        #[test]
+        #[should_panic(expected = "once the parser has started")]
+        fn max_entry_bytes_after_start() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let mut parser = ThreadedParser::new(std::io::BufReader::new(f));
+            parser.next();
+            parser.max_entry_bytes(NonZeroUsize::new(1024).unwrap());
+        }
+
+        #[test]
+        #[should_panic(expected = "once the parser has started")]
+        fn reviewed_only_after_start() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let mut parser = ThreadedParser::new(std::io::BufReader::new(f));
+            parser.next();
+            parser.reviewed_only();
+        }
+
+        #[test]
+        #[should_panic(expected = "once the parser has started")]
+        fn ordered_after_start() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let mut parser = ThreadedParser::new(std::io::BufReader::new(f));
+            parser.next();
+            parser.ordered();
+        }
+
+        #[test]
+        fn parse_ordered() {
+            let accessions = |entry: Result<Entry, Error>| {
+                let entry = entry.expect("entry should parse successfully");
+                entry.accessions[0].clone()
+            };
+
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let expected = SequentialParser::new(std::io::BufReader::new(f))
+                .map(accessions)
+                .collect::<Vec<_>>();
+
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let threads = std::num::NonZeroUsize::new(4).unwrap();
+            let actual = ThreadedParser::with_threads(std::io::BufReader::new(f), threads)
+                .ordered()
+                .map(accessions)
+                .collect::<Vec<_>>();
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn parse_with_small_capacity() {
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let threads = std::num::NonZeroUsize::new(128).unwrap();
+            let capacity = std::num::NonZeroUsize::new(1).unwrap();
+            let parser = ThreadedParser::with_threads_and_capacity(
+                std::io::BufReader::new(f),
+                threads,
+                capacity,
+            );
+            let entries = parser
+                .collect::<Result<Vec<_>, _>>()
+                .expect("entries should parse successfully");
+            assert_eq!(entries.len(), 250);
+        }
+
+        #[test]
+        fn parse_with_config() {
+            let sequences = |entry: Result<Entry, Error>| {
+                let entry = entry.expect("entry should parse successfully");
+                entry.sequence.expect("entry should have a sequence").value
+            };
+
+            let config = ParserConfig::default();
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let expected = SequentialParser::with_config(std::io::BufReader::new(f), config)
+                .map(sequences)
+                .collect::<Vec<_>>();
+
+            let f = std::fs::File::open("tests/uniprot.xml").unwrap();
+            let actual = ThreadedParser::with_config(std::io::BufReader::new(f), config)
+                .ordered()
+                .map(sequences)
+                .collect::<Vec<_>>();
+
+            assert_eq!(actual.len(), expected.len());
+            for (a, e) in actual.iter().zip(expected.iter()) {
+                assert_eq!(a.as_bytes(), e.as_bytes());
+            }
+        }
+
         #[test]
         fn fail_unexpected_eof() {
             let txt = &b"<uniprot><entry dataset=\"Swiss-Prot\" created=\"2011-06-28\" modified=\"2019-12-11\" version=\"39\">"[..];
@@ -132,7 +3644,7 @@ mod tests {
                 .next()
                 .expect("should raise an error")
                 .unwrap_err();
-            match err {
+            match err.into_inner() {
                 Error::Xml(XmlError::UnexpectedEof(_)) => (),
                 other => panic!("unexpected error: {:?}", other),
             }
@@ -145,10 +3657,27 @@ mod tests {
                 .next()
                 .expect("should raise an error")
                 .unwrap_err();
-            match err {
+            match err.into_inner() {
                 Error::UnexpectedRoot(r) => assert_eq!(r, "something"),
                 other => panic!("unexpected error: {:?}", other),
             }
         }
+
+        #[test]
+        fn error_position() {
+            let txt = "<uniprot>\n<entry dataset=\"Swiss-Prot\"></protein></entry>\n</uniprot>";
+            let mut parser = ThreadedParser::new(std::io::Cursor::new(txt));
+            assert!(parser.next().expect("should raise an error").is_err());
+            let start = txt.find("<entry").unwrap() as u64;
+            let end = (txt.find("</protein>").unwrap() + "</protein>".len()) as u64;
+            let position = parser
+                .error_position()
+                .expect("error should have a position");
+            assert!(start <= position && position <= end);
+
+            let mut parser = ThreadedParser::new(std::io::Cursor::new(""));
+            assert!(parser.next().expect("should raise an error").is_err());
+            assert_eq!(parser.error_position(), Some(0));
+        }
     }
 }
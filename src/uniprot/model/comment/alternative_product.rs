@@ -1,18 +1,41 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
 use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_empty_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AlternativeProduct {
     pub events: Vec<Event>,
     pub isoforms: Vec<Isoform>,
@@ -21,13 +44,30 @@ pub struct AlternativeProduct {
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Event {
+    #[cfg_attr(feature = "serde", serde(rename = "alternative splicing"))]
     AlternativeSplicing,
+    #[cfg_attr(feature = "serde", serde(rename = "alternative initiation"))]
     AlternativeInitiation,
+    #[cfg_attr(feature = "serde", serde(rename = "alternative promoter"))]
     AlternativePromoter,
+    #[cfg_attr(feature = "serde", serde(rename = "ribosomal frameshifting"))]
     RibosomalFrameshifting,
 }
 
+impl Event {
+    /// Get the string representation of the event.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Event::AlternativeSplicing => "alternative splicing",
+            Event::AlternativeInitiation => "alternative initiation",
+            Event::AlternativePromoter => "alternative promoter",
+            Event::RibosomalFrameshifting => "ribosomal frameshifting",
+        }
+    }
+}
+
 impl FromStr for Event {
     type Err = InvalidValue;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -42,11 +82,13 @@ impl FromStr for Event {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Event {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"event");
         reader.read_to_end_into(event.name(), buffer)?;
@@ -54,9 +96,19 @@ impl FromXml for Event {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Event {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("event");
+        elem.push_attribute(("type", self.as_str()));
+        write_empty_element(writer, elem)
+    }
+}
+
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Isoform {
     pub ids: Vec<ShortString>,
     pub names: Vec<ShortString>,
@@ -75,11 +127,13 @@ impl Isoform {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Isoform {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"isoform");
 
@@ -88,7 +142,7 @@ impl FromXml for Isoform {
         let mut texts = Vec::new();
         let mut optseq: Option<IsoformSequence> = None;
 
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"id" => {
                 ids.push(parse_text!(e, reader, buffer));
             },
@@ -99,7 +153,7 @@ impl FromXml for Isoform {
                 texts.push(parse_text!(e, reader, buffer));
             },
             e @ b"sequence" => {
-                let seq = FromXml::from_xml(&e, reader, buffer)?;
+                let seq = FromXml::from_xml(&e, reader, buffer, options)?;
                 if optseq.replace(seq).is_some() {
                     return Err(Error::DuplicateElement("sequence", "isoform"));
                 }
@@ -117,9 +171,29 @@ impl FromXml for Isoform {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Isoform {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        write_element(writer, BytesStart::new("isoform"), |w| {
+            for id in &self.ids {
+                write_text_element(w, BytesStart::new("id"), id)?;
+            }
+            for name in &self.names {
+                write_text_element(w, BytesStart::new("name"), name)?;
+            }
+            self.sequence.to_xml(w)?;
+            for text in &self.texts {
+                write_text_element(w, BytesStart::new("text"), text)?;
+            }
+            Ok(())
+        })
+    }
+}
+
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IsoformSequence {
     pub ty: IsoformSequenceType,
     pub reference: Option<String>,
@@ -141,11 +215,13 @@ impl IsoformSequence {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for IsoformSequence {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"sequence");
 
@@ -160,16 +236,45 @@ impl FromXml for IsoformSequence {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for IsoformSequence {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("sequence");
+        elem.push_attribute(("type", self.ty.as_str()));
+        if let Some(reference) = &self.reference {
+            elem.push_attribute(("ref", reference.as_str()));
+        }
+        write_empty_element(writer, elem)
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IsoformSequenceType {
+    #[cfg_attr(feature = "serde", serde(rename = "not described"))]
     NotDescribed,
+    #[cfg_attr(feature = "serde", serde(rename = "described"))]
     Described,
+    #[cfg_attr(feature = "serde", serde(rename = "displayed"))]
     Displayed,
+    #[cfg_attr(feature = "serde", serde(rename = "external"))]
     External,
 }
 
+impl IsoformSequenceType {
+    /// Get the string representation of the isoform sequence type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IsoformSequenceType::NotDescribed => "not described",
+            IsoformSequenceType::Described => "described",
+            IsoformSequenceType::Displayed => "displayed",
+            IsoformSequenceType::External => "external",
+        }
+    }
+}
+
 impl FromStr for IsoformSequenceType {
     type Err = InvalidValue;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
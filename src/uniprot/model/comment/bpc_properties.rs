@@ -1,14 +1,34 @@
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BiophysicochemicalProperties {
     pub absorption: Option<Absorption>,
     pub kinetics: Option<Kinetics>,
@@ -19,23 +39,26 @@ pub struct BiophysicochemicalProperties {
 
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Absorption {
     pub max: Option<ShortString>,  // FIXME: evidence ShortString
     pub min: Option<ShortString>,  // FIXME: evidence ShortString
     pub text: Option<ShortString>, // FIXME: evidence ShortString
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Absorption {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"absorption");
 
         let mut absorption = Absorption::default();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"max" => {
                 let max = parse_text!(e, reader, buffer);
                 if absorption.max.replace(max).is_some() {
@@ -60,25 +83,60 @@ impl FromXml for Absorption {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Absorption {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        write_element(writer, BytesStart::new("absorption"), |w| {
+            if let Some(max) = &self.max {
+                write_text_element(w, BytesStart::new("max"), max)?;
+            }
+            if let Some(min) = &self.min {
+                write_text_element(w, BytesStart::new("min"), min)?;
+            }
+            if let Some(text) = &self.text {
+                write_text_element(w, BytesStart::new("text"), text)?;
+            }
+            Ok(())
+        })
+    }
+}
+
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Kinetics {
     pub km: Vec<ShortString>,      // FIXME: evidence ShortString
     pub vmax: Vec<ShortString>,    // FIXME: evidence ShortString
     pub text: Option<ShortString>, // FIXME: evidence ShortString
 }
 
+impl Kinetics {
+    /// Get the KM values of the kinetics parsed as numeric measurements.
+    ///
+    /// The free-text `KM` elements are expected to be formatted like
+    /// `0.5 uM for ATP`, with an optional substrate. Values that cannot be
+    /// parsed this way are skipped.
+    pub fn km_values(&self) -> Vec<KmMeasurement> {
+        self.km
+            .iter()
+            .filter_map(|km| KmMeasurement::from_str(km).ok())
+            .collect()
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Kinetics {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"kinetics");
 
         let mut kinetics = Kinetics::default();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"KM" => {
                 kinetics.km.push(parse_text!(e, reader, buffer));
             },
@@ -96,3 +154,100 @@ impl FromXml for Kinetics {
         Ok(kinetics)
     }
 }
+
+#[cfg(feature = "parser")]
+impl ToXml for Kinetics {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        write_element(writer, BytesStart::new("kinetics"), |w| {
+            for km in &self.km {
+                write_text_element(w, BytesStart::new("KM"), km)?;
+            }
+            for vmax in &self.vmax {
+                write_text_element(w, BytesStart::new("Vmax"), vmax)?;
+            }
+            if let Some(text) = &self.text {
+                write_text_element(w, BytesStart::new("text"), text)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A Michaelis constant measurement parsed from a `KM` element.
+pub struct KmMeasurement {
+    /// The numeric value of the constant.
+    pub value: f64,
+    /// The unit of the value, e.g. `uM` or `mM`.
+    pub unit: ShortString,
+    /// The substrate the constant was measured for, if any.
+    pub substrate: Option<ShortString>,
+}
+
+impl FromStr for KmMeasurement {
+    type Err = InvalidValue;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let text = text.strip_prefix("KM=").unwrap_or(text);
+        let (measure, substrate) = match text.split_once(" for ") {
+            Some((measure, substrate)) => (measure, Some(substrate)),
+            None => (text, None),
+        };
+
+        let mut words = measure.split_whitespace();
+        let value = words
+            .next()
+            .and_then(|v| v.parse::<f64>().ok())
+            .ok_or_else(|| InvalidValue::from(s))?;
+        let unit = match (words.next(), words.next()) {
+            (Some(unit), None) => ShortString::from(unit),
+            _ => return Err(InvalidValue::from(s)),
+        };
+
+        // drop the experimental conditions following the substrate
+        let substrate = substrate
+            .map(|s| s.split(" (").next().unwrap_or(s))
+            .map(|s| s.trim().trim_end_matches(&['.', ';'][..]))
+            .filter(|s| !s.is_empty())
+            .map(ShortString::from);
+
+        Ok(KmMeasurement {
+            value,
+            unit,
+            substrate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn km_values() {
+        let kinetics = Kinetics {
+            km: vec![
+                "0.5 uM for ATP".into(),
+                "1.2 mM for NADH (at pH 7.5 and 25 degrees Celsius)".into(),
+                "37 uM".into(),
+                "Has a higher affinity for ATP than for GTP".into(),
+                "not determined".into(),
+            ],
+            ..Default::default()
+        };
+        let values = kinetics.km_values();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[0].value, 0.5);
+        assert_eq!(values[0].unit, "uM");
+        assert_eq!(values[0].substrate.as_deref(), Some("ATP"));
+        assert_eq!(values[1].value, 1.2);
+        assert_eq!(values[1].unit, "mM");
+        assert_eq!(values[1].substrate.as_deref(), Some("NADH"));
+        assert_eq!(values[2].value, 37.0);
+        assert_eq!(values[2].substrate, None);
+    }
+}
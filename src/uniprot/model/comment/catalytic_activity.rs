@@ -1,19 +1,43 @@
+use core::convert::TryFrom;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
 use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::push_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 use super::super::db_reference::DbReference;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CatalyticActivity {
     pub reaction: Reaction,
     pub physiological_reactions: Vec<PhysiologicalReaction>,
@@ -30,7 +54,8 @@ impl CatalyticActivity {
 
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Reaction {
     pub text: ShortString,
     pub db_references: Vec<DbReference>,
@@ -45,20 +70,42 @@ impl Reaction {
             evidences: Default::default(),
         }
     }
+
+    /// Get the Rhea identifier of the reaction, if any.
+    ///
+    /// Returns `None` for legacy reactions only described by a free text
+    /// and an EC number, which are not cross-referenced to Rhea.
+    pub fn rhea_id(&self) -> Option<&str> {
+        self.db_references
+            .iter()
+            .find(|dbref| dbref.ty == "Rhea")
+            .map(|dbref| dbref.id.as_str())
+    }
+
+    /// Get the ChEBI identifiers of the reaction participants.
+    pub fn chebi_participants(&self) -> Vec<&str> {
+        self.db_references
+            .iter()
+            .filter(|dbref| dbref.ty == "ChEBI")
+            .map(|dbref| dbref.id.as_str())
+            .collect()
+    }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Reaction {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"reaction");
 
         let mut db_references = Vec::new();
         let mut opttext = None;
 
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"text" => {
                 let text = parse_text!(e, reader, buffer);
                 if opttext.replace(text).is_some() {
@@ -66,7 +113,7 @@ impl FromXml for Reaction {
                 }
             },
             e @ b"dbReference" => {
-                db_references.push(FromXml::from_xml(&e, reader, buffer)?);
+                db_references.push(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
@@ -74,15 +121,31 @@ impl FromXml for Reaction {
             .map(Reaction::new)
             .ok_or(Error::MissingAttribute("text", "reaction"))?;
         reaction.db_references = db_references;
-        reaction.evidences = get_evidences(reader, &event)?;
+        reaction.evidences = get_evidences(reader, event)?;
 
         Ok(reaction)
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Reaction {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("reaction");
+        push_evidences(&mut elem, &self.evidences);
+        write_element(writer, elem, |w| {
+            write_text_element(w, BytesStart::new("text"), &self.text)?;
+            for db_reference in &self.db_references {
+                db_reference.to_xml(w)?;
+            }
+            Ok(())
+        })
+    }
+}
+
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Describes a physiological reaction.
 pub struct PhysiologicalReaction {
     pub db_reference: DbReference,
@@ -90,23 +153,25 @@ pub struct PhysiologicalReaction {
     pub direction: Direction,
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for PhysiologicalReaction {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"physiologicalReaction");
 
         use self::Direction::*;
 
-        let evidences = get_evidences(reader, &event)?;
+        let evidences = get_evidences(reader, event)?;
         let direction = decode_attribute(event, reader, "direction", "physiologicalReaction")?;
 
         let mut optdbref = None;
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"dbReference" => {
-                let dbref = FromXml::from_xml(&e, reader, buffer)?;
+                let dbref = FromXml::from_xml(&e, reader, buffer, options)?;
                 if optdbref.replace(dbref).is_some() {
                     return Err(Error::DuplicateElement("dbReference", "reaction"));
                 }
@@ -125,14 +190,37 @@ impl FromXml for PhysiologicalReaction {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for PhysiologicalReaction {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("physiologicalReaction");
+        elem.push_attribute(("direction", self.direction.as_str()));
+        push_evidences(&mut elem, &self.evidences);
+        write_element(writer, elem, |w| self.db_reference.to_xml(w))
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
+    #[cfg_attr(feature = "serde", serde(rename = "left-to-right"))]
     LeftToRight,
+    #[cfg_attr(feature = "serde", serde(rename = "right-to-left"))]
     RightToLeft,
 }
 
+impl Direction {
+    /// Get the string representation of the direction.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::LeftToRight => "left-to-right",
+            Direction::RightToLeft => "right-to-left",
+        }
+    }
+}
+
 impl FromStr for Direction {
     type Err = InvalidValue;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -143,3 +231,10 @@ impl FromStr for Direction {
         }
     }
 }
+
+impl TryFrom<&str> for Direction {
+    type Error = InvalidValue;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
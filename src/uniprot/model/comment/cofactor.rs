@@ -1,35 +1,70 @@
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::push_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 use super::super::db_reference::DbReference;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cofactor {
     pub name: ShortString,
     pub db_reference: DbReference,
     pub evidences: Vec<usize>,
 }
 
+impl Cofactor {
+    /// Get the ChEBI identifier of the cofactor, if referenced.
+    pub fn chebi_id(&self) -> Option<&str> {
+        if self.db_reference.ty == "ChEBI" {
+            Some(&self.db_reference.id)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Cofactor {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"cofactor");
 
         let mut optname = None;
         let mut optdbref = None;
 
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"name" => {
                 let name = parse_text!(e, reader, buffer);
                 if optname.replace(name).is_some() {
@@ -37,7 +72,7 @@ impl FromXml for Cofactor {
                 }
             },
             e @ b"dbReference" => {
-                let dbref = FromXml::from_xml(&e, reader, buffer)?;
+                let dbref = FromXml::from_xml(&e, reader, buffer, options)?;
                 if optdbref.replace(dbref).is_some() {
                     return Err(Error::DuplicateElement("dbReference", "cofactor"));
                 }
@@ -55,3 +90,15 @@ impl FromXml for Cofactor {
         })
     }
 }
+
+#[cfg(feature = "parser")]
+impl ToXml for Cofactor {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("cofactor");
+        push_evidences(&mut elem, &self.evidences);
+        write_element(writer, elem, |w| {
+            write_text_element(w, BytesStart::new("name"), &self.name)?;
+            self.db_reference.to_xml(w)
+        })
+    }
+}
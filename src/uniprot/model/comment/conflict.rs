@@ -1,18 +1,40 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use core::convert::TryFrom;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
 use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_empty_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Conflict {
     pub ty: ConflictType,
     pub reference: Option<ShortString>,
@@ -29,11 +51,13 @@ impl Conflict {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Conflict {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"conflict");
 
@@ -42,15 +66,15 @@ impl FromXml for Conflict {
             decode_attribute(event, reader, "type", "conflict").map(Conflict::new)?;
 
         // extract optional reference
-        conflict.reference = extract_attribute(event, "type")?
+        conflict.reference = extract_attribute(event, "ref")?
             .map(|x| x.decode_and_unescape_value(reader))
             .transpose()?
             .map(From::from);
 
         // extract `sequence` element
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"sequence" => {
-                let sequence = FromXml::from_xml(&e, reader, buffer)?;
+                let sequence = FromXml::from_xml(&e, reader, buffer, options)?;
                 if conflict.sequence.replace(sequence).is_some() {
                     return Err(Error::DuplicateElement("sequence", "conflict"));
                 }
@@ -61,18 +85,56 @@ impl FromXml for Conflict {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Conflict {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("conflict");
+        elem.push_attribute(("type", self.ty.as_str()));
+        if let Some(reference) = &self.reference {
+            elem.push_attribute(("ref", reference.as_ref()));
+        }
+        write_element(writer, elem, |w| {
+            if let Some(sequence) = &self.sequence {
+                sequence.to_xml(w)?;
+            }
+            Ok(())
+        })
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ConflictType {
+    #[cfg_attr(feature = "serde", serde(rename = "frameshift"))]
     Frameshift,
+    #[cfg_attr(feature = "serde", serde(rename = "erroneous initiation"))]
     ErroneousInitiation,
+    #[cfg_attr(feature = "serde", serde(rename = "erroneous termination"))]
     ErroneousTermination,
+    #[cfg_attr(feature = "serde", serde(rename = "erroneous gene model prediction"))]
     ErroneousGeneModelPrediction,
+    #[cfg_attr(feature = "serde", serde(rename = "erroneous translation"))]
     ErroneousTranslation,
+    #[cfg_attr(feature = "serde", serde(rename = "miscellaneous discrepancy"))]
     MiscellaneousDiscrepancy,
 }
 
+impl ConflictType {
+    /// Get the string representation of the conflict type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConflictType::Frameshift => "frameshift",
+            ConflictType::ErroneousInitiation => "erroneous initiation",
+            ConflictType::ErroneousTermination => "erroneous termination",
+            ConflictType::ErroneousGeneModelPrediction => "erroneous gene model prediction",
+            ConflictType::ErroneousTranslation => "erroneous translation",
+            ConflictType::MiscellaneousDiscrepancy => "miscellaneous discrepancy",
+        }
+    }
+}
+
 impl FromStr for ConflictType {
     type Err = InvalidValue;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -91,7 +153,8 @@ impl FromStr for ConflictType {
 
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ConflictSequence {
     pub id: ShortString,
     pub resource: Resource,
@@ -115,11 +178,13 @@ impl ConflictSequence {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for ConflictSequence {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"sequence");
 
@@ -139,14 +204,40 @@ impl FromXml for ConflictSequence {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for ConflictSequence {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("sequence");
+        elem.push_attribute(("resource", self.resource.as_str()));
+        elem.push_attribute(("id", self.id.as_ref()));
+        if let Some(version) = self.version {
+            elem.push_attribute(("version", version.to_string().as_str()));
+        }
+        write_empty_element(writer, elem)
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Resource {
+    #[cfg_attr(feature = "serde", serde(rename = "EMBL"))]
     Embl,
+    #[cfg_attr(feature = "serde", serde(rename = "EMBL-CDS"))]
     EmblCds,
 }
 
+impl Resource {
+    /// Get the string representation of the resource.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Resource::Embl => "EMBL",
+            Resource::EmblCds => "EMBL-CDS",
+        }
+    }
+}
+
 impl FromStr for Resource {
     type Err = InvalidValue;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -157,3 +248,10 @@ impl FromStr for Resource {
         }
     }
 }
+
+impl TryFrom<&str> for Resource {
+    type Error = InvalidValue;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
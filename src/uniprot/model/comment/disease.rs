@@ -1,18 +1,38 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 use super::super::db_reference::DbReference;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Disease {
     pub id: ShortString,
     pub name: ShortString,
@@ -21,11 +41,26 @@ pub struct Disease {
     pub db_reference: DbReference,
 }
 
+impl Disease {
+    /// Get the MIM identifier of the disease, if referenced.
+    ///
+    /// The identifier can be used to link the disease to the OMIM database.
+    pub fn mim_id(&self) -> Option<&str> {
+        if self.db_reference.ty == "MIM" {
+            Some(&self.db_reference.id)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Disease {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"disease");
 
@@ -46,7 +81,7 @@ impl FromXml for Disease {
             .decode_and_unescape_value(reader)
             .map(From::from)?;
 
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"name" => {
                 let name = parse_text!(e, reader, buffer);
                 if optname.replace(name).is_some() {
@@ -66,7 +101,7 @@ impl FromXml for Disease {
                 }
             },
             e @ b"dbReference" => {
-                let db_reference = FromXml::from_xml(&e, reader, buffer)?;
+                let db_reference = FromXml::from_xml(&e, reader, buffer, options)?;
                 if optdbref.replace(db_reference).is_some() {
                     return Err(Error::DuplicateElement("dbReference", "disease"));
                 }
@@ -82,3 +117,17 @@ impl FromXml for Disease {
         })
     }
 }
+
+#[cfg(feature = "parser")]
+impl ToXml for Disease {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("disease");
+        elem.push_attribute(("id", self.id.as_ref()));
+        write_element(writer, elem, |w| {
+            write_text_element(w, BytesStart::new("name"), &self.name)?;
+            write_text_element(w, BytesStart::new("acronym"), &self.acronym)?;
+            write_text_element(w, BytesStart::new("description"), &self.description)?;
+            self.db_reference.to_xml(w)
+        })
+    }
+}
@@ -1,26 +1,90 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 use super::super::DbReference;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Interaction {
     pub interactants: (Interactant, Interactant),
     pub organisms_differ: bool,
     pub experiments: usize,
 }
 
-#[derive(Debug, Clone)]
+impl Interaction {
+    /// Get the type of the interaction.
+    ///
+    /// The UniProt XML does not record the interaction type explicitly: an
+    /// interaction is a self-interaction when both interactants share the
+    /// same IntAct identifier, and a binary interaction otherwise.
+    pub fn ty(&self) -> InteractionType {
+        let (i1, i2) = &self.interactants;
+        if i1.interactant_id == i2.interactant_id {
+            InteractionType::SelfInteraction
+        } else {
+            InteractionType::Binary
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The type of a protein-protein interaction.
+pub enum InteractionType {
+    /// An interaction between two different molecules.
+    #[cfg_attr(feature = "serde", serde(rename = "binary"))]
+    Binary,
+    /// An interaction of a molecule with itself.
+    #[cfg_attr(feature = "serde", serde(rename = "self"))]
+    SelfInteraction,
+}
+
+impl InteractionType {
+    /// Get the string representation of the interaction type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InteractionType::Binary => "binary",
+            InteractionType::SelfInteraction => "self",
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Interactant {
+    /// The IntAct identifier of the interactant, from the `intactId` attribute.
     pub interactant_id: ShortString,
     pub id: Option<ShortString>,
     pub label: Option<ShortString>,
@@ -38,11 +102,13 @@ impl Interactant {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Interactant {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"interactant");
 
@@ -60,7 +126,7 @@ impl FromXml for Interactant {
             .map(From::from)
             .map(Interactant::new)?;
 
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"id" => {
                 let id = parse_text!(e, reader, buffer);
                 if interactant.id.replace(id).is_some() {
@@ -74,10 +140,30 @@ impl FromXml for Interactant {
                 }
             },
             e @ b"dbReference" => {
-                interactant.db_reference.push(FromXml::from_xml(&e, reader, buffer)?);
+                interactant.db_reference.push(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
         Ok(interactant)
     }
 }
+
+#[cfg(feature = "parser")]
+impl ToXml for Interactant {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("interactant");
+        elem.push_attribute(("intactId", self.interactant_id.as_ref()));
+        write_element(writer, elem, |w| {
+            if let Some(id) = &self.id {
+                write_text_element(w, BytesStart::new("id"), id)?;
+            }
+            if let Some(label) = &self.label {
+                write_text_element(w, BytesStart::new("label"), label)?;
+            }
+            for db_reference in &self.db_reference {
+                db_reference.to_xml(w)?;
+            }
+            Ok(())
+        })
+    }
+}
@@ -1,6 +1,8 @@
+use crate::common::prelude::*;
 use crate::common::ShortString;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MassSpectrometry {
     pub mass: Option<f64>,
     pub error: Option<String>,
@@ -11,21 +11,51 @@ mod mass_spectrometry;
 mod online_information;
 mod subcellular_location;
 
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use core::convert::TryFrom;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 #[cfg(feature = "url-links")]
 use url::Url;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::push_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_empty_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
+use super::evidence_text::EvidenceText;
 use super::feature_location::FeatureLocation;
 use super::molecule::Molecule;
 
@@ -37,6 +67,7 @@ pub use self::alternative_product::IsoformSequenceType;
 pub use self::bpc_properties::Absorption;
 pub use self::bpc_properties::BiophysicochemicalProperties;
 pub use self::bpc_properties::Kinetics;
+pub use self::bpc_properties::KmMeasurement;
 pub use self::catalytic_activity::CatalyticActivity;
 pub use self::catalytic_activity::Direction;
 pub use self::catalytic_activity::PhysiologicalReaction;
@@ -49,17 +80,23 @@ pub use self::conflict::Resource;
 pub use self::disease::Disease;
 pub use self::interaction::Interactant;
 pub use self::interaction::Interaction;
+pub use self::interaction::InteractionType;
 pub use self::mass_spectrometry::MassSpectrometry;
 pub use self::online_information::OnlineInformation;
 pub use self::subcellular_location::SubcellularLocation;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Describes different types of general annotations.
 pub struct Comment {
     // fields
     pub molecule: Option<Molecule>,
-    // location: Vec<Location>,
-    pub text: Vec<ShortString>, // FIXME: type should be evidence text?
+    /// The sequence locations referenced by the comment.
+    ///
+    /// This includes the edited positions of RNA editing comments, and the
+    /// regions covered by the molecule of mass spectrometry comments.
+    pub locations: Vec<FeatureLocation>,
+    pub text: Vec<EvidenceText>,
     pub ty: CommentType,
     pub evidences: Vec<usize>, // TODO: extract evidence attribute
 }
@@ -69,17 +106,20 @@ impl Comment {
         Self {
             ty,
             molecule: Default::default(),
+            locations: Default::default(),
             text: Default::default(),
             evidences: Default::default(),
         }
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Comment {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"comment");
 
@@ -93,93 +133,93 @@ impl FromXml for Comment {
         {
             b"function" => {
                 comment.ty = CommentType::Function;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"similarity" => {
                 comment.ty = CommentType::Similarity;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"subunit" => {
                 comment.ty = CommentType::Subunit;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"PTM" => {
                 comment.ty = CommentType::Ptm;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"developmental stage" => {
                 comment.ty = CommentType::DevelopmentalStage;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"disruption phenotype" => {
                 comment.ty = CommentType::DisruptionPhenotype;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"tissue specificity" => {
                 comment.ty = CommentType::TissueSpecificity;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"miscellaneous" => {
                 comment.ty = CommentType::Miscellaneous;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"induction" => {
                 comment.ty = CommentType::Induction;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"caution" => {
                 comment.ty = CommentType::Caution;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"pathway" => {
                 comment.ty = CommentType::Pathway;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"toxic dose" => {
                 comment.ty = CommentType::ToxicDose;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"activity regulation" => {
                 comment.ty = CommentType::ActivityRegulation;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"domain" => {
                 comment.ty = CommentType::Domain;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"biotechnology" => {
                 comment.ty = CommentType::Biotechnology;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"polymorphism" => {
                 comment.ty = CommentType::Polymorphism;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"pharmaceutical" => {
                 comment.ty = CommentType::Pharmaceutical;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"allergen" => {
                 comment.ty = CommentType::Allergen;
-                parse_comment! {event, reader, buffer, comment}
+                parse_comment! {event, reader, buffer, options, comment}
             }
             b"subcellular location" => {
                 let mut locations = Vec::new();
-                parse_comment! {event, reader, buffer, comment,
+                parse_comment! {event, reader, buffer, options, comment,
                     e @ b"subcellularLocation" => {
-                        locations.push(FromXml::from_xml(&e, reader, buffer)?);
+                        locations.push(FromXml::from_xml(&e, reader, buffer, options)?);
                     }
                 }
                 comment.ty = CommentType::SubcellularLocation(locations);
             }
             b"alternative products" => {
                 let mut product = AlternativeProduct::default();
-                parse_comment! {event, reader, buffer, comment,
+                parse_comment! {event, reader, buffer, options, comment,
                     e @ b"event" => {
-                        product.events.push(FromXml::from_xml(&e, reader, buffer)?);
+                        product.events.push(FromXml::from_xml(&e, reader, buffer, options)?);
                     },
                     e @ b"isoform" => {
-                        product.isoforms.push(FromXml::from_xml(&e, reader, buffer)?);
+                        product.isoforms.push(FromXml::from_xml(&e, reader, buffer, options)?);
                     }
                 }
                 comment.ty = CommentType::AlternativeProduct(product);
@@ -190,9 +230,9 @@ impl FromXml for Comment {
                 let mut interactants = Vec::new();
 
                 // extract interaction elements
-                parse_comment! {event, reader, buffer, comment,
+                parse_comment! {event, reader, buffer, options, comment,
                     e @ b"interactant" => {
-                        interactants.push(Interactant::from_xml(&e, reader, buffer)?);
+                        interactants.push(Interactant::from_xml(&e, reader, buffer, options)?);
                     },
                     e @ b"organismsDiffer" => {
                         let text = parse_text!(e, reader, buffer);
@@ -228,9 +268,9 @@ impl FromXml for Comment {
                 let mut optconflict = None;
 
                 // extract inner `conflict`
-                parse_comment! {event, reader, buffer, comment,
+                parse_comment! {event, reader, buffer, options, comment,
                     e @ b"conflict" => {
-                        let conflict = FromXml::from_xml(&e, reader, buffer)?;
+                        let conflict = FromXml::from_xml(&e, reader, buffer, options)?;
                         if optconflict.replace(conflict).is_some() {
                             return Err(Error::DuplicateElement("conflict", "comment"));
                         }
@@ -244,31 +284,35 @@ impl FromXml for Comment {
             }
 
             b"mass spectrometry" => {
-                let mut ms = MassSpectrometry::default();
-                ms.mass = extract_attribute(event, "mass")?
+                let mass = extract_attribute(event, "mass")?
                     .map(|x| x.decode_and_unescape_value(reader))
                     .transpose()?
-                    .map(|s| f64::from_str(&s))
-                    .transpose()
-                    .expect("could not parse `mass` as f64");
-                ms.error = extract_attribute(event, "error")?
+                    .map(|s| {
+                        f64::from_str(&s).map_err(|_| Error::invalid_value("mass", "comment", s))
+                    })
+                    .transpose()?;
+                let error = extract_attribute(event, "error")?
                     .map(|x| x.decode_and_unescape_value(reader))
                     .transpose()?
                     .map(From::from);
-                ms.method = extract_attribute(event, "method")?
+                let method = extract_attribute(event, "method")?
                     .map(|x| x.decode_and_unescape_value(reader))
                     .transpose()?
                     .map(From::from);
 
-                parse_comment! {event, reader, buffer, comment}
-                comment.ty = CommentType::MassSpectrometry(ms);
+                parse_comment! {event, reader, buffer, options, comment}
+                comment.ty = CommentType::MassSpectrometry(MassSpectrometry {
+                    mass,
+                    error,
+                    method,
+                });
             }
 
             b"disease" => {
                 let mut optdisease = None;
-                parse_comment! {event, reader, buffer, comment,
+                parse_comment! {event, reader, buffer, options, comment,
                     e @ b"disease" => {
-                        let disease = FromXml::from_xml(&e, reader, buffer)?;
+                        let disease = FromXml::from_xml(&e, reader, buffer, options)?;
                         if optdisease.replace(disease).is_some() {
                             return Err(Error::DuplicateElement("disease", "comment"));
                         }
@@ -279,21 +323,21 @@ impl FromXml for Comment {
 
             b"biophysicochemical properties" => {
                 let mut bcp = BiophysicochemicalProperties::default();
-                parse_comment! {event, reader, buffer, comment,
+                parse_comment! {event, reader, buffer, options, comment,
                     e @ b"absorption" => {
-                        let absorption = FromXml::from_xml(&e, reader, buffer)?;
+                        let absorption = FromXml::from_xml(&e, reader, buffer, options)?;
                         if bcp.absorption.replace(absorption).is_some() {
                             return Err(Error::DuplicateElement("absorption", "comment"));
                         }
                     },
                     e @ b"kinetics" => {
-                        let kinetics = FromXml::from_xml(&e, reader, buffer)?;
+                        let kinetics = FromXml::from_xml(&e, reader, buffer, options)?;
                         if bcp.kinetics.replace(kinetics).is_some() {
                             return Err(Error::DuplicateElement("kinetics", "comment"));
                         }
                     },
                     e @ b"phDependence" => {
-                        parse_inner!{e, reader, buffer,
+                        parse_inner!{e, reader, buffer, options,
                             e @ b"text" => {
                                 let text = parse_text!(e, reader, buffer);
                                 bcp.ph_dependence = Some(text);
@@ -301,7 +345,7 @@ impl FromXml for Comment {
                         }
                     },
                     e @ b"redoxPotential" => {
-                        parse_inner!{e, reader, buffer,
+                        parse_inner!{e, reader, buffer, options,
                             e @ b"text" => {
                                 let text = parse_text!(e, reader, buffer);
                                 bcp.redox_potential = Some(text);
@@ -309,7 +353,7 @@ impl FromXml for Comment {
                         }
                     },
                     e @ b"temperatureDependence" => {
-                        parse_inner!{e, reader, buffer,
+                        parse_inner!{e, reader, buffer, options,
                             e @ b"text" => {
                                 let text = parse_text!(e, reader, buffer);
                                 bcp.temperature_dependence = Some(text);
@@ -324,15 +368,15 @@ impl FromXml for Comment {
                 let mut physio = Vec::new();
                 let mut optreact = None;
 
-                parse_comment! {event, reader, buffer, comment,
+                parse_comment! {event, reader, buffer, options, comment,
                     e @ b"reaction" => {
-                        let reaction = FromXml::from_xml(&e, reader, buffer)?;
+                        let reaction = FromXml::from_xml(&e, reader, buffer, options)?;
                         if optreact.replace(reaction).is_some() {
                             return Err(Error::DuplicateElement("reaction", "comment"));
                         }
                     },
                     e @ b"physiologicalReaction" => {
-                        physio.push(FromXml::from_xml(&e, reader, buffer)?);
+                        physio.push(FromXml::from_xml(&e, reader, buffer, options)?);
                     }
                 }
 
@@ -348,13 +392,15 @@ impl FromXml for Comment {
             }
 
             b"online information" => {
-                let mut info = OnlineInformation::default();
-                info.name = extract_attribute(event, "name")?
-                    .map(|a| a.decode_and_unescape_value(reader))
-                    .transpose()?
-                    .map(From::from);
-
-                parse_comment! {event, reader, buffer, comment,
+                let mut info = OnlineInformation {
+                    name: extract_attribute(event, "name")?
+                        .map(|a| a.decode_and_unescape_value(reader))
+                        .transpose()?
+                        .map(From::from),
+                    ..Default::default()
+                };
+
+                parse_comment! {event, reader, buffer, options, comment,
                     e @ b"link" => {
                         let uri = e.attributes()
                             .find(|x| x.is_err() || x.as_ref().map(|a| a.key.as_ref() == b"uri").unwrap_or_default())
@@ -375,22 +421,23 @@ impl FromXml for Comment {
 
             b"cofactor" => {
                 let mut cofactors = Vec::new();
-                parse_comment! {event, reader, buffer, comment,
+                parse_comment! {event, reader, buffer, options, comment,
                     e @ b"cofactor" => {
-                        cofactors.push(FromXml::from_xml(&e, reader, buffer)?);
+                        cofactors.push(FromXml::from_xml(&e, reader, buffer, options)?);
                     }
                 }
                 comment.ty = CommentType::Cofactor(cofactors)
             }
 
             b"RNA editing" => {
-                let mut locations = Vec::new();
-                parse_comment! {event, reader, buffer, comment,
-                    e @ b"location" => {
-                        locations.push(FromXml::from_xml(&e, reader, buffer)?);
-                    }
-                }
-                comment.ty = CommentType::RnaEditing(locations);
+                parse_comment! {event, reader, buffer, options, comment}
+                comment.ty = CommentType::RnaEditing;
+            }
+
+            other if options.is_lenient() => {
+                let ty = std::string::String::from_utf8_lossy(other);
+                comment.ty = CommentType::Unknown(ty.as_ref().into());
+                parse_comment! {event, reader, buffer, options, comment}
             }
 
             other => {
@@ -406,35 +453,458 @@ impl FromXml for Comment {
     }
 }
 
-#[derive(Debug, Clone)]
+#[cfg(feature = "parser")]
+impl ToXml for Comment {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("comment");
+        elem.push_attribute(("type", self.ty.as_str()));
+        match &self.ty {
+            CommentType::MassSpectrometry(ms) => {
+                if let Some(mass) = ms.mass {
+                    elem.push_attribute(("mass", mass.to_string().as_str()));
+                }
+                if let Some(error) = &ms.error {
+                    elem.push_attribute(("error", error.as_str()));
+                }
+                if let Some(method) = &ms.method {
+                    elem.push_attribute(("method", method.as_str()));
+                }
+            }
+            CommentType::OnlineInformation(info) => {
+                if let Some(name) = &info.name {
+                    elem.push_attribute(("name", name.as_ref()));
+                }
+            }
+            _ => (),
+        }
+        push_evidences(&mut elem, &self.evidences);
+
+        write_element(writer, elem, |w| {
+            if let Some(molecule) = &self.molecule {
+                molecule.to_xml(w)?;
+            }
+            match &self.ty {
+                CommentType::SubcellularLocation(locations) => {
+                    for location in locations {
+                        location.to_xml(w)?;
+                    }
+                }
+                CommentType::AlternativeProduct(product) => {
+                    for event in &product.events {
+                        event.to_xml(w)?;
+                    }
+                    for isoform in &product.isoforms {
+                        isoform.to_xml(w)?;
+                    }
+                }
+                CommentType::Interaction(interaction) => {
+                    interaction.interactants.0.to_xml(w)?;
+                    interaction.interactants.1.to_xml(w)?;
+                    let organisms_differ = interaction.organisms_differ.to_string();
+                    write_text_element(w, BytesStart::new("organismsDiffer"), &organisms_differ)?;
+                    let experiments = interaction.experiments.to_string();
+                    write_text_element(w, BytesStart::new("experiments"), &experiments)?;
+                }
+                CommentType::SequenceCaution(conflict) => {
+                    conflict.to_xml(w)?;
+                }
+                CommentType::Disease(Some(disease)) => {
+                    disease.to_xml(w)?;
+                }
+                CommentType::BiophysicochemicalProperties(bcp) => {
+                    if let Some(absorption) = &bcp.absorption {
+                        absorption.to_xml(w)?;
+                    }
+                    if let Some(kinetics) = &bcp.kinetics {
+                        kinetics.to_xml(w)?;
+                    }
+                    let texts = [
+                        ("phDependence", &bcp.ph_dependence),
+                        ("redoxPotential", &bcp.redox_potential),
+                        ("temperatureDependence", &bcp.temperature_dependence),
+                    ];
+                    for (tag, text) in texts.iter() {
+                        if let Some(text) = text {
+                            write_element(w, BytesStart::new(*tag), |w| {
+                                write_text_element(w, BytesStart::new("text"), text)
+                            })?;
+                        }
+                    }
+                }
+                CommentType::CatalyticActivity(act) => {
+                    act.reaction.to_xml(w)?;
+                    for reaction in &act.physiological_reactions {
+                        reaction.to_xml(w)?;
+                    }
+                }
+                CommentType::OnlineInformation(info) => {
+                    for link in &info.links {
+                        let mut elem = BytesStart::new("link");
+                        elem.push_attribute(("uri", link.as_str()));
+                        write_empty_element(w, elem)?;
+                    }
+                }
+                CommentType::Cofactor(cofactors) => {
+                    for cofactor in cofactors {
+                        cofactor.to_xml(w)?;
+                    }
+                }
+                _ => (),
+            }
+            for location in &self.locations {
+                location.to_xml(w)?;
+            }
+            for text in &self.text {
+                text.to_xml(w)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CommentType {
+    #[cfg_attr(feature = "serde", serde(rename = "allergen"))]
     Allergen,
+    #[cfg_attr(feature = "serde", serde(rename = "alternative products"))]
     AlternativeProduct(AlternativeProduct),
+    #[cfg_attr(feature = "serde", serde(rename = "biotechnology"))]
     Biotechnology,
+    #[cfg_attr(feature = "serde", serde(rename = "biophysicochemical properties"))]
     BiophysicochemicalProperties(BiophysicochemicalProperties),
+    #[cfg_attr(feature = "serde", serde(rename = "catalytic activity"))]
     CatalyticActivity(CatalyticActivity),
+    #[cfg_attr(feature = "serde", serde(rename = "caution"))]
     Caution,
+    #[cfg_attr(feature = "serde", serde(rename = "cofactor"))]
     Cofactor(Vec<Cofactor>),
+    #[cfg_attr(feature = "serde", serde(rename = "developmental stage"))]
     DevelopmentalStage,
+    #[cfg_attr(feature = "serde", serde(rename = "disease"))]
     Disease(Option<Disease>),
+    #[cfg_attr(feature = "serde", serde(rename = "domain"))]
     Domain,
+    #[cfg_attr(feature = "serde", serde(rename = "disruption phenotype"))]
     DisruptionPhenotype,
+    #[cfg_attr(feature = "serde", serde(rename = "activity regulation"))]
     ActivityRegulation,
+    #[cfg_attr(feature = "serde", serde(rename = "function"))]
     Function,
+    #[cfg_attr(feature = "serde", serde(rename = "induction"))]
     Induction,
+    #[cfg_attr(feature = "serde", serde(rename = "miscellaneous"))]
     Miscellaneous,
+    #[cfg_attr(feature = "serde", serde(rename = "pathway"))]
     Pathway,
+    #[cfg_attr(feature = "serde", serde(rename = "pharmaceutical"))]
     Pharmaceutical,
+    #[cfg_attr(feature = "serde", serde(rename = "polymorphism"))]
     Polymorphism,
+    #[cfg_attr(feature = "serde", serde(rename = "PTM"))]
     Ptm,
-    RnaEditing(Vec<FeatureLocation>), // FIXME: possible dedicated type
+    #[cfg_attr(feature = "serde", serde(rename = "RNA editing"))]
+    RnaEditing,
+    #[cfg_attr(feature = "serde", serde(rename = "similarity"))]
     Similarity,
+    #[cfg_attr(feature = "serde", serde(rename = "subcellular location"))]
     SubcellularLocation(Vec<SubcellularLocation>),
+    #[cfg_attr(feature = "serde", serde(rename = "sequence caution"))]
     SequenceCaution(Conflict),
+    #[cfg_attr(feature = "serde", serde(rename = "subunit"))]
     Subunit,
+    #[cfg_attr(feature = "serde", serde(rename = "tissue specificity"))]
     TissueSpecificity,
+    #[cfg_attr(feature = "serde", serde(rename = "toxic dose"))]
     ToxicDose,
+    #[cfg_attr(feature = "serde", serde(rename = "online information"))]
     OnlineInformation(OnlineInformation),
+    #[cfg_attr(feature = "serde", serde(rename = "mass spectrometry"))]
     MassSpectrometry(MassSpectrometry),
+    #[cfg_attr(feature = "serde", serde(rename = "interaction"))]
     Interaction(Interaction),
+    /// A comment type unknown to this crate, only created by lenient parsers.
+    Unknown(ShortString),
+}
+
+impl CommentType {
+    /// Get the string representation of the comment type.
+    pub fn as_str(&self) -> &str {
+        match self {
+            CommentType::Unknown(s) => s,
+            _ => self.kind().map_or("", |kind| kind.as_str()),
+        }
+    }
+
+    /// Get the kind of the comment type, without the data of the variant.
+    ///
+    /// Returns `None` for comment types unknown to this crate.
+    pub fn kind(&self) -> Option<CommentKind> {
+        match self {
+            CommentType::Allergen => Some(CommentKind::Allergen),
+            CommentType::AlternativeProduct(_) => Some(CommentKind::AlternativeProduct),
+            CommentType::Biotechnology => Some(CommentKind::Biotechnology),
+            CommentType::BiophysicochemicalProperties(_) => {
+                Some(CommentKind::BiophysicochemicalProperties)
+            }
+            CommentType::CatalyticActivity(_) => Some(CommentKind::CatalyticActivity),
+            CommentType::Caution => Some(CommentKind::Caution),
+            CommentType::Cofactor(_) => Some(CommentKind::Cofactor),
+            CommentType::DevelopmentalStage => Some(CommentKind::DevelopmentalStage),
+            CommentType::Disease(_) => Some(CommentKind::Disease),
+            CommentType::Domain => Some(CommentKind::Domain),
+            CommentType::DisruptionPhenotype => Some(CommentKind::DisruptionPhenotype),
+            CommentType::ActivityRegulation => Some(CommentKind::ActivityRegulation),
+            CommentType::Function => Some(CommentKind::Function),
+            CommentType::Induction => Some(CommentKind::Induction),
+            CommentType::Miscellaneous => Some(CommentKind::Miscellaneous),
+            CommentType::Pathway => Some(CommentKind::Pathway),
+            CommentType::Pharmaceutical => Some(CommentKind::Pharmaceutical),
+            CommentType::Polymorphism => Some(CommentKind::Polymorphism),
+            CommentType::Ptm => Some(CommentKind::Ptm),
+            CommentType::RnaEditing => Some(CommentKind::RnaEditing),
+            CommentType::Similarity => Some(CommentKind::Similarity),
+            CommentType::SubcellularLocation(_) => Some(CommentKind::SubcellularLocation),
+            CommentType::SequenceCaution(_) => Some(CommentKind::SequenceCaution),
+            CommentType::Subunit => Some(CommentKind::Subunit),
+            CommentType::TissueSpecificity => Some(CommentKind::TissueSpecificity),
+            CommentType::ToxicDose => Some(CommentKind::ToxicDose),
+            CommentType::OnlineInformation(_) => Some(CommentKind::OnlineInformation),
+            CommentType::MassSpectrometry(_) => Some(CommentKind::MassSpectrometry),
+            CommentType::Interaction(_) => Some(CommentKind::Interaction),
+            CommentType::Unknown(_) => None,
+        }
+    }
+}
+
+impl Display for CommentType {
+    /// Format the comment type, ignoring the data of the variant.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The kind of a [`CommentType`], without the data of the comment.
+///
+/// [`CommentType`]: ./enum.CommentType.html
+pub enum CommentKind {
+    #[cfg_attr(feature = "serde", serde(rename = "allergen"))]
+    Allergen,
+    #[cfg_attr(feature = "serde", serde(rename = "alternative products"))]
+    AlternativeProduct,
+    #[cfg_attr(feature = "serde", serde(rename = "biotechnology"))]
+    Biotechnology,
+    #[cfg_attr(feature = "serde", serde(rename = "biophysicochemical properties"))]
+    BiophysicochemicalProperties,
+    #[cfg_attr(feature = "serde", serde(rename = "catalytic activity"))]
+    CatalyticActivity,
+    #[cfg_attr(feature = "serde", serde(rename = "caution"))]
+    Caution,
+    #[cfg_attr(feature = "serde", serde(rename = "cofactor"))]
+    Cofactor,
+    #[cfg_attr(feature = "serde", serde(rename = "developmental stage"))]
+    DevelopmentalStage,
+    #[cfg_attr(feature = "serde", serde(rename = "disease"))]
+    Disease,
+    #[cfg_attr(feature = "serde", serde(rename = "domain"))]
+    Domain,
+    #[cfg_attr(feature = "serde", serde(rename = "disruption phenotype"))]
+    DisruptionPhenotype,
+    #[cfg_attr(feature = "serde", serde(rename = "activity regulation"))]
+    ActivityRegulation,
+    #[cfg_attr(feature = "serde", serde(rename = "function"))]
+    Function,
+    #[cfg_attr(feature = "serde", serde(rename = "induction"))]
+    Induction,
+    #[cfg_attr(feature = "serde", serde(rename = "miscellaneous"))]
+    Miscellaneous,
+    #[cfg_attr(feature = "serde", serde(rename = "pathway"))]
+    Pathway,
+    #[cfg_attr(feature = "serde", serde(rename = "pharmaceutical"))]
+    Pharmaceutical,
+    #[cfg_attr(feature = "serde", serde(rename = "polymorphism"))]
+    Polymorphism,
+    #[cfg_attr(feature = "serde", serde(rename = "PTM"))]
+    Ptm,
+    #[cfg_attr(feature = "serde", serde(rename = "RNA editing"))]
+    RnaEditing,
+    #[cfg_attr(feature = "serde", serde(rename = "similarity"))]
+    Similarity,
+    #[cfg_attr(feature = "serde", serde(rename = "subcellular location"))]
+    SubcellularLocation,
+    #[cfg_attr(feature = "serde", serde(rename = "sequence caution"))]
+    SequenceCaution,
+    #[cfg_attr(feature = "serde", serde(rename = "subunit"))]
+    Subunit,
+    #[cfg_attr(feature = "serde", serde(rename = "tissue specificity"))]
+    TissueSpecificity,
+    #[cfg_attr(feature = "serde", serde(rename = "toxic dose"))]
+    ToxicDose,
+    #[cfg_attr(feature = "serde", serde(rename = "online information"))]
+    OnlineInformation,
+    #[cfg_attr(feature = "serde", serde(rename = "mass spectrometry"))]
+    MassSpectrometry,
+    #[cfg_attr(feature = "serde", serde(rename = "interaction"))]
+    Interaction,
+}
+
+impl CommentKind {
+    /// Get the string representation of the comment kind.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentKind::Allergen => "allergen",
+            CommentKind::AlternativeProduct => "alternative products",
+            CommentKind::Biotechnology => "biotechnology",
+            CommentKind::BiophysicochemicalProperties => "biophysicochemical properties",
+            CommentKind::CatalyticActivity => "catalytic activity",
+            CommentKind::Caution => "caution",
+            CommentKind::Cofactor => "cofactor",
+            CommentKind::DevelopmentalStage => "developmental stage",
+            CommentKind::Disease => "disease",
+            CommentKind::Domain => "domain",
+            CommentKind::DisruptionPhenotype => "disruption phenotype",
+            CommentKind::ActivityRegulation => "activity regulation",
+            CommentKind::Function => "function",
+            CommentKind::Induction => "induction",
+            CommentKind::Miscellaneous => "miscellaneous",
+            CommentKind::Pathway => "pathway",
+            CommentKind::Pharmaceutical => "pharmaceutical",
+            CommentKind::Polymorphism => "polymorphism",
+            CommentKind::Ptm => "PTM",
+            CommentKind::RnaEditing => "RNA editing",
+            CommentKind::Similarity => "similarity",
+            CommentKind::SubcellularLocation => "subcellular location",
+            CommentKind::SequenceCaution => "sequence caution",
+            CommentKind::Subunit => "subunit",
+            CommentKind::TissueSpecificity => "tissue specificity",
+            CommentKind::ToxicDose => "toxic dose",
+            CommentKind::OnlineInformation => "online information",
+            CommentKind::MassSpectrometry => "mass spectrometry",
+            CommentKind::Interaction => "interaction",
+        }
+    }
+}
+
+impl Display for CommentKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CommentKind {
+    type Err = InvalidValue;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allergen" => Ok(CommentKind::Allergen),
+            "alternative products" => Ok(CommentKind::AlternativeProduct),
+            "biotechnology" => Ok(CommentKind::Biotechnology),
+            "biophysicochemical properties" => Ok(CommentKind::BiophysicochemicalProperties),
+            "catalytic activity" => Ok(CommentKind::CatalyticActivity),
+            "caution" => Ok(CommentKind::Caution),
+            "cofactor" => Ok(CommentKind::Cofactor),
+            "developmental stage" => Ok(CommentKind::DevelopmentalStage),
+            "disease" => Ok(CommentKind::Disease),
+            "domain" => Ok(CommentKind::Domain),
+            "disruption phenotype" => Ok(CommentKind::DisruptionPhenotype),
+            "activity regulation" => Ok(CommentKind::ActivityRegulation),
+            "function" => Ok(CommentKind::Function),
+            "induction" => Ok(CommentKind::Induction),
+            "miscellaneous" => Ok(CommentKind::Miscellaneous),
+            "pathway" => Ok(CommentKind::Pathway),
+            "pharmaceutical" => Ok(CommentKind::Pharmaceutical),
+            "polymorphism" => Ok(CommentKind::Polymorphism),
+            "PTM" => Ok(CommentKind::Ptm),
+            "RNA editing" => Ok(CommentKind::RnaEditing),
+            "similarity" => Ok(CommentKind::Similarity),
+            "subcellular location" => Ok(CommentKind::SubcellularLocation),
+            "sequence caution" => Ok(CommentKind::SequenceCaution),
+            "subunit" => Ok(CommentKind::Subunit),
+            "tissue specificity" => Ok(CommentKind::TissueSpecificity),
+            "toxic dose" => Ok(CommentKind::ToxicDose),
+            "online information" => Ok(CommentKind::OnlineInformation),
+            "mass spectrometry" => Ok(CommentKind::MassSpectrometry),
+            "interaction" => Ok(CommentKind::Interaction),
+            other => Err(InvalidValue::from(other)),
+        }
+    }
+}
+
+impl TryFrom<&str> for CommentKind {
+    type Error = InvalidValue;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KINDS: [CommentKind; 29] = [
+        CommentKind::Allergen,
+        CommentKind::AlternativeProduct,
+        CommentKind::Biotechnology,
+        CommentKind::BiophysicochemicalProperties,
+        CommentKind::CatalyticActivity,
+        CommentKind::Caution,
+        CommentKind::Cofactor,
+        CommentKind::DevelopmentalStage,
+        CommentKind::Disease,
+        CommentKind::Domain,
+        CommentKind::DisruptionPhenotype,
+        CommentKind::ActivityRegulation,
+        CommentKind::Function,
+        CommentKind::Induction,
+        CommentKind::Miscellaneous,
+        CommentKind::Pathway,
+        CommentKind::Pharmaceutical,
+        CommentKind::Polymorphism,
+        CommentKind::Ptm,
+        CommentKind::RnaEditing,
+        CommentKind::Similarity,
+        CommentKind::SubcellularLocation,
+        CommentKind::SequenceCaution,
+        CommentKind::Subunit,
+        CommentKind::TissueSpecificity,
+        CommentKind::ToxicDose,
+        CommentKind::OnlineInformation,
+        CommentKind::MassSpectrometry,
+        CommentKind::Interaction,
+    ];
+
+    #[test]
+    fn kind_try_from_str() {
+        for kind in KINDS {
+            assert_eq!(CommentKind::try_from(kind.as_str()), Ok(kind));
+            assert_eq!(kind.to_string(), kind.as_str());
+        }
+        assert_eq!(
+            CommentKind::try_from("future comment"),
+            Err(InvalidValue::from("future comment"))
+        );
+    }
+
+    #[test]
+    fn comment_type_kind() {
+        let types = [
+            CommentType::Function,
+            CommentType::Ptm,
+            CommentType::RnaEditing,
+            CommentType::Cofactor(Vec::new()),
+            CommentType::Disease(None),
+            CommentType::SubcellularLocation(Vec::new()),
+            CommentType::AlternativeProduct(AlternativeProduct::default()),
+            CommentType::BiophysicochemicalProperties(Default::default()),
+            CommentType::OnlineInformation(Default::default()),
+        ];
+        for ty in &types {
+            let kind = ty.kind().expect("comment type should be known");
+            assert_eq!(CommentKind::try_from(ty.to_string().as_str()), Ok(kind));
+        }
+        assert_eq!(CommentType::Unknown("future comment".into()).kind(), None);
+    }
 }
@@ -1,9 +1,11 @@
+use crate::common::prelude::*;
 use crate::common::ShortString;
 
 #[cfg(feature = "url-links")]
 use url::Url;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OnlineInformation {
     pub name: Option<ShortString>,
     #[cfg(feature = "url-links")]
@@ -1,40 +1,61 @@
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
-use crate::common::ShortString;
+use crate::common::prelude::*;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
-#[derive(Debug, Default, Clone)]
+use super::super::evidence_text::EvidenceText;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The subcellular location (and optionally the topology and orientation) of a molecule.
 pub struct SubcellularLocation {
-    pub locations: Vec<ShortString>, // TODO: EvidenceShortString, minOccurs = "1"
-    pub topologies: Vec<ShortString>, // TODO: EvidenceShortString,
-    pub orientations: Vec<ShortString>, // TODO: EvidenceShortString,
+    pub locations: Vec<EvidenceText>, // minOccurs = "1"
+    pub topologies: Vec<EvidenceText>,
+    pub orientations: Vec<EvidenceText>,
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for SubcellularLocation {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"subcellularLocation");
 
         let mut subloc = SubcellularLocation::default();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"location" => {
-                subloc.locations.push(parse_text!(e, reader, buffer));
+                subloc.locations.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"topology" => {
-                subloc.topologies.push(parse_text!(e, reader, buffer));
+                subloc.topologies.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"orientation" => {
-                subloc.orientations.push(parse_text!(e, reader, buffer));
+                subloc.orientations.push(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
@@ -45,3 +66,21 @@ impl FromXml for SubcellularLocation {
         Ok(subloc)
     }
 }
+
+#[cfg(feature = "parser")]
+impl ToXml for SubcellularLocation {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        write_element(writer, BytesStart::new("subcellularLocation"), |w| {
+            for location in &self.locations {
+                location.write_xml(w, "location")?;
+            }
+            for topology in &self.topologies {
+                topology.write_xml(w, "topology")?;
+            }
+            for orientation in &self.orientations {
+                orientation.write_xml(w, "orientation")?;
+            }
+            Ok(())
+        })
+    }
+}
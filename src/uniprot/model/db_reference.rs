@@ -1,18 +1,40 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::property::Property;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
+use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::push_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 use super::molecule::Molecule;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A database cross-reference.
 pub struct DbReference {
     pub molecule: Option<Molecule>,
@@ -22,28 +44,67 @@ pub struct DbReference {
     pub evidences: Vec<usize>,
 }
 
+impl DbReference {
+    /// Get the value of the first property with the given type, if any.
+    pub fn property_value(&self, ty: &str) -> Option<&str> {
+        self.property
+            .iter()
+            .find(|p| p.ty == ty)
+            .map(|p| p.value.as_str())
+    }
+
+    /// Get the Gene Ontology term of the cross-reference, if any.
+    ///
+    /// Returns `None` if the cross-reference is not to the `GO` database,
+    /// or if its `term` property does not start with a known aspect prefix.
+    /// The evidence code is left empty when the `evidence` property is
+    /// missing.
+    pub fn go_term(&self) -> Option<GoTerm> {
+        if self.ty != "GO" {
+            return None;
+        }
+        let term = self.property_value("term")?;
+        let (prefix, name) = term.split_once(':')?;
+        let aspect = GoAspect::from_prefix(prefix)?;
+        let evidence_code = match self.property_value("evidence") {
+            Some(evidence) => match evidence.split_once(':') {
+                Some((code, _)) if code != "ECO" => code,
+                _ => evidence,
+            },
+            None => "",
+        };
+        Some(GoTerm {
+            aspect,
+            name: name.into(),
+            evidence_code: evidence_code.into(),
+        })
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for DbReference {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"dbReference");
 
         let mut db_reference = DbReference::default();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"property" => {
-                db_reference.property.push(Property::from_xml(&e, reader, buffer)?);
+                db_reference.property.push(Property::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"molecule" => {
-                let molecule = Molecule::from_xml(&e, reader, buffer)?;
+                let molecule = Molecule::from_xml(&e, reader, buffer, options)?;
                 if db_reference.molecule.replace(molecule).is_some() {
                     return Err(Error::DuplicateElement("molecule", "dbReference"))
                 }
             }
         }
 
-        // let attr = attributes_to_hashmap(event)?;
+        db_reference.evidences = get_evidences(reader, event)?;
         db_reference.ty = extract_attribute(event, "type")?
             .ok_or(Error::MissingAttribute("type", "dbReference"))?
             .decode_and_unescape_value(reader)?
@@ -56,3 +117,123 @@ impl FromXml for DbReference {
         Ok(db_reference)
     }
 }
+
+#[cfg(feature = "parser")]
+impl ToXml for DbReference {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("dbReference");
+        elem.push_attribute(("type", self.ty.as_ref()));
+        elem.push_attribute(("id", self.id.as_ref()));
+        push_evidences(&mut elem, &self.evidences);
+        write_element(writer, elem, |w| {
+            if let Some(molecule) = &self.molecule {
+                molecule.to_xml(w)?;
+            }
+            for property in &self.property {
+                property.to_xml(w)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The aspect of a Gene Ontology term.
+pub enum GoAspect {
+    #[cfg_attr(feature = "serde", serde(rename = "C"))]
+    CellularComponent,
+    #[cfg_attr(feature = "serde", serde(rename = "F"))]
+    MolecularFunction,
+    #[cfg_attr(feature = "serde", serde(rename = "P"))]
+    BiologicalProcess,
+}
+
+impl GoAspect {
+    /// Get the aspect corresponding to a one-letter term prefix, if any.
+    pub fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "C" => Some(GoAspect::CellularComponent),
+            "F" => Some(GoAspect::MolecularFunction),
+            "P" => Some(GoAspect::BiologicalProcess),
+            _ => None,
+        }
+    }
+
+    /// Get the one-letter prefix of the aspect, as used in term properties.
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            GoAspect::CellularComponent => "C",
+            GoAspect::MolecularFunction => "F",
+            GoAspect::BiologicalProcess => "P",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A Gene Ontology term, as annotated by a `GO` cross-reference.
+pub struct GoTerm {
+    /// The aspect of the term.
+    pub aspect: GoAspect,
+    /// The name of the term, without the aspect prefix.
+    pub name: ShortString,
+    /// The evidence code of the annotation, e.g. `IEA` or `ECO:0007669`.
+    pub evidence_code: ShortString,
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn go(term: &str, evidence: Option<&str>) -> DbReference {
+        let mut db_reference = DbReference {
+            ty: "GO".into(),
+            id: "GO:0005886".into(),
+            ..Default::default()
+        };
+        db_reference
+            .property
+            .push(Property::new("term".into(), term.into()));
+        if let Some(evidence) = evidence {
+            db_reference
+                .property
+                .push(Property::new("evidence".into(), evidence.into()));
+        }
+        db_reference
+    }
+
+    #[test]
+    fn go_term() {
+        let term = go("C:plasma membrane", Some("IEA:InterPro"))
+            .go_term()
+            .unwrap();
+        assert_eq!(term.aspect, GoAspect::CellularComponent);
+        assert_eq!(term.name, "plasma membrane");
+        assert_eq!(term.evidence_code, "IEA");
+
+        let term = go("F:ATP binding", Some("ECO:0007669")).go_term().unwrap();
+        assert_eq!(term.aspect, GoAspect::MolecularFunction);
+        assert_eq!(term.evidence_code, "ECO:0007669");
+    }
+
+    #[test]
+    fn go_term_missing_evidence() {
+        let term = go("P:translation", None).go_term().unwrap();
+        assert_eq!(term.aspect, GoAspect::BiologicalProcess);
+        assert_eq!(term.name, "translation");
+        assert_eq!(term.evidence_code, "");
+    }
+
+    #[test]
+    fn go_term_invalid() {
+        assert!(go("X:unknown", None).go_term().is_none());
+        assert!(go("no prefix", None).go_term().is_none());
+        let mut pfam = go("C:plasma membrane", None);
+        pfam.ty = "Pfam".into();
+        assert!(pfam.go_term().is_none());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::common::prelude::*;
+
+use super::comment::Comment;
+use super::DbReference;
+use super::Entry;
+use super::Feature;
+use super::Keyword;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+/// The annotation changes between two versions of an entry.
+///
+/// Elements are compared regardless of their order, so an entry with
+/// its keywords shuffled has no added or removed keywords. Elements
+/// appearing several times are matched one-to-one.
+///
+/// See [`Entry::diff`] to compute the changes between two entries.
+///
+/// [`Entry::diff`]: ./struct.Entry.html#method.diff
+pub struct EntryDiff<'a> {
+    pub added_comments: Vec<&'a Comment>,
+    pub removed_comments: Vec<&'a Comment>,
+    pub added_features: Vec<&'a Feature>,
+    pub removed_features: Vec<&'a Feature>,
+    pub added_keywords: Vec<&'a Keyword>,
+    pub removed_keywords: Vec<&'a Keyword>,
+    pub added_db_references: Vec<&'a DbReference>,
+    pub removed_db_references: Vec<&'a DbReference>,
+    /// Whether the residues of the entry sequence changed.
+    pub sequence_changed: bool,
+}
+
+impl<'a> EntryDiff<'a> {
+    pub(super) fn new(old: &'a Entry, new: &'a Entry) -> Self {
+        let old_residues = old.sequence.as_ref().map(|s| s.value.as_str());
+        let new_residues = new.sequence.as_ref().map(|s| s.value.as_str());
+        Self {
+            added_comments: difference(&new.comments, &old.comments),
+            removed_comments: difference(&old.comments, &new.comments),
+            added_features: difference(&new.features, &old.features),
+            removed_features: difference(&old.features, &new.features),
+            added_keywords: difference(&new.keywords, &old.keywords),
+            removed_keywords: difference(&old.keywords, &new.keywords),
+            added_db_references: difference(&new.db_references, &old.db_references),
+            removed_db_references: difference(&old.db_references, &new.db_references),
+            sequence_changed: old_residues != new_residues,
+        }
+    }
+
+    /// Check whether the two entries have the same annotations.
+    pub fn is_empty(&self) -> bool {
+        self.added_comments.is_empty()
+            && self.removed_comments.is_empty()
+            && self.added_features.is_empty()
+            && self.removed_features.is_empty()
+            && self.added_keywords.is_empty()
+            && self.removed_keywords.is_empty()
+            && self.added_db_references.is_empty()
+            && self.removed_db_references.is_empty()
+            && !self.sequence_changed
+    }
+}
+
+/// Get the elements of `left` without a matching element in `right`.
+fn difference<'a, T: PartialEq>(left: &'a [T], right: &'a [T]) -> Vec<&'a T> {
+    let mut matched = vec![false; right.len()];
+    let mut result = Vec::new();
+    for x in left {
+        match (0..right.len()).find(|&i| !matched[i] && right[i] == *x) {
+            Some(i) => matched[i] = true,
+            None => result.push(x),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn difference() {
+        let left = [1, 2, 2, 3];
+        let right = [3, 2, 1];
+        assert_eq!(super::difference(&left, &right), vec![&2]);
+        assert!(super::difference(&right, &left).is_empty());
+    }
+}
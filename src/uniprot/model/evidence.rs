@@ -1,19 +1,40 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_empty_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 use super::db_reference::DbReference;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The evidence for an annotation.
 pub struct Evidence {
     pub key: usize,
@@ -31,13 +52,57 @@ impl Evidence {
             imported_from: None,
         }
     }
+
+    /// Get the Evidence & Conclusion Ontology code of the evidence, if any.
+    ///
+    /// Returns `None` when the evidence type is not an ECO identifier, as
+    /// in older files where evidences were typed with a free-text category.
+    pub fn eco_code(&self) -> Option<&str> {
+        let code = self.ty.strip_prefix("ECO:")?;
+        if !code.is_empty() && code.bytes().all(|b| b.is_ascii_digit()) {
+            Some(&self.ty)
+        } else {
+            None
+        }
+    }
+
+    /// Check whether the evidence is backed by an experiment.
+    ///
+    /// ECO codes are compared against the experimental evidence terms used
+    /// by UniProt, and free-text types such as `experimental evidence` are
+    /// recognized as well. Combinatorial evidences, mixing computational
+    /// and experimental data, are not considered experimental.
+    pub fn is_experimental(&self) -> bool {
+        match self.eco_code() {
+            Some(code) => EXPERIMENTAL_ECO_CODES.contains(&code),
+            None => self
+                .ty
+                .trim()
+                .to_ascii_lowercase()
+                .starts_with("experimental"),
+        }
+    }
 }
 
+/// The ECO terms denoting experimental evidence.
+const EXPERIMENTAL_ECO_CODES: &[&str] = &[
+    "ECO:0000006", // experimental evidence
+    "ECO:0000269", // experimental evidence used in manual assertion
+    "ECO:0000270", // expression pattern evidence
+    "ECO:0000314", // direct assay evidence used in manual assertion
+    "ECO:0000315", // mutant phenotype evidence used in manual assertion
+    "ECO:0000316", // genetic interaction evidence used in manual assertion
+    "ECO:0000353", // physical interaction evidence used in manual assertion
+    "ECO:0007005", // high throughput direct assay evidence
+];
+
+#[cfg(feature = "parser")]
 impl FromXml for Evidence {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"evidence");
 
@@ -48,17 +113,17 @@ impl FromXml for Evidence {
             .into();
 
         let mut evidence = Self::new(key, ty);
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"source" => {
-                let source = FromXml::from_xml(&e, reader, buffer)?;
+                let source = FromXml::from_xml(&e, reader, buffer, options)?;
                 if evidence.source.replace(source).is_some() {
                     return Err(Error::DuplicateElement("source", "evidence"));
                 }
             },
             e @ b"importedFrom" => {
-                parse_inner!{e, reader, buffer,
+                parse_inner!{e, reader, buffer, options,
                     d @ b"dbReference" => {
-                        let dbref = FromXml::from_xml(&d, reader, buffer)?;
+                        let dbref = FromXml::from_xml(&d, reader, buffer, options)?;
                         if evidence.imported_from.replace(dbref).is_some() {
                             return Err(Error::DuplicateElement("importedFrom", "evidence"));
                         }
@@ -71,9 +136,33 @@ impl FromXml for Evidence {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Evidence {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("evidence");
+        elem.push_attribute(("type", self.ty.as_ref()));
+        elem.push_attribute(("key", self.key.to_string().as_str()));
+        if self.source.is_none() && self.imported_from.is_none() {
+            return write_empty_element(writer, elem);
+        }
+        write_element(writer, elem, |w| {
+            if let Some(source) = &self.source {
+                source.to_xml(w)?;
+            }
+            if let Some(db_reference) = &self.imported_from {
+                write_element(w, BytesStart::new("importedFrom"), |w| {
+                    db_reference.to_xml(w)
+                })?;
+            }
+            Ok(())
+        })
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A reference to the source of the data.
 pub enum Source {
     /// A cross-reference to another database, such as PubMed.
@@ -82,18 +171,20 @@ pub enum Source {
     Ref(usize),
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Source {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"source");
 
         let mut optdbref = None;
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"dbReference" => {
-                let db_reference = FromXml::from_xml(&e, reader, buffer)?;
+                let db_reference = FromXml::from_xml(&e, reader, buffer, options)?;
                 if optdbref.replace(db_reference).is_some() {
                     return Err(Error::DuplicateElement("dbReference", "source"));
                 }
@@ -107,3 +198,45 @@ impl FromXml for Source {
         }
     }
 }
+
+#[cfg(feature = "parser")]
+impl ToXml for Source {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        match self {
+            Source::DbRef(db_reference) => write_element(writer, BytesStart::new("source"), |w| {
+                db_reference.to_xml(w)
+            }),
+            Source::Ref(key) => {
+                let mut elem = BytesStart::new("source");
+                elem.push_attribute(("ref", key.to_string().as_str()));
+                write_empty_element(writer, elem)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eco_code() {
+        let evidence = Evidence::new(1, "ECO:0000269".into());
+        assert_eq!(evidence.eco_code(), Some("ECO:0000269"));
+        let evidence = Evidence::new(1, "experimental evidence".into());
+        assert_eq!(evidence.eco_code(), None);
+        let evidence = Evidence::new(1, "ECO:".into());
+        assert_eq!(evidence.eco_code(), None);
+    }
+
+    #[test]
+    fn is_experimental() {
+        assert!(Evidence::new(1, "ECO:0000269".into()).is_experimental());
+        assert!(Evidence::new(1, "ECO:0000314".into()).is_experimental());
+        assert!(!Evidence::new(1, "ECO:0000305".into()).is_experimental());
+        assert!(!Evidence::new(1, "ECO:0007744".into()).is_experimental());
+        assert!(Evidence::new(1, "experimental evidence".into()).is_experimental());
+        assert!(Evidence::new(1, "Experimental evidence".into()).is_experimental());
+        assert!(!Evidence::new(1, "inferred from homology".into()).is_experimental());
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(feature = "parser")]
+use std::io::BufRead;
+#[cfg(feature = "parser")]
+use std::io::Write;
+
+#[cfg(feature = "parser")]
+use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
+use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
+
+use crate::common::prelude::*;
+use crate::common::ShortString;
+#[cfg(feature = "parser")]
+use crate::error::Error;
+#[cfg(feature = "parser")]
+use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::push_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
+use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A text value supported by evidences.
+pub struct EvidenceText {
+    pub value: ShortString,
+    pub evidences: Vec<usize>,
+}
+
+impl EvidenceText {
+    pub fn new(value: ShortString) -> Self {
+        Self::new_with_evidences(value, Vec::new())
+    }
+
+    pub fn new_with_evidences(value: ShortString, evidences: Vec<usize>) -> Self {
+        Self { value, evidences }
+    }
+
+    /// Write the text as an XML element with the given tag name.
+    #[cfg(feature = "parser")]
+    pub(crate) fn write_xml<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        tag: &str,
+    ) -> Result<(), Error> {
+        let mut elem = BytesStart::new(tag);
+        push_evidences(&mut elem, &self.evidences);
+        write_text_element(writer, elem, &self.value)
+    }
+}
+
+impl AsRef<str> for EvidenceText {
+    fn as_ref(&self) -> &str {
+        self.value.as_ref()
+    }
+}
+
+#[cfg(feature = "parser")]
+impl FromXml for EvidenceText {
+    fn from_xml<B: BufRead>(
+        event: &BytesStart,
+        reader: &mut Reader<B>,
+        buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
+    ) -> Result<Self, Error> {
+        let evidences = get_evidences(reader, event)?;
+        let value = parse_text!(event, reader, buffer);
+        Ok(EvidenceText::new_with_evidences(value, evidences))
+    }
+}
+
+#[cfg(feature = "parser")]
+impl ToXml for EvidenceText {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        self.write_xml(writer, "text")
+    }
+}
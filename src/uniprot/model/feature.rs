@@ -1,23 +1,51 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use core::convert::TryFrom;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
 use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::push_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 use super::feature_location::FeatureLocation;
 use super::ligand::Ligand;
 use super::ligand_part::LigandPart;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Describes different types of sequence annotations
 pub struct Feature {
     // fields
@@ -54,13 +82,74 @@ impl Feature {
             ligand_part: Default::default(),
         }
     }
+
+    /// Check whether the feature certainly covers the given 1-based position.
+    ///
+    /// Features with an uncertain or unknown endpoint never contain any
+    /// position.
+    pub fn contains(&self, pos: usize) -> bool {
+        self.location.contains(pos)
+    }
+
+    /// Get the key of the reference cited by the feature, if any.
+    ///
+    /// Returns `None` if the feature has no `ref` attribute, or if it is
+    /// not an integer reference key.
+    pub fn reference_key(&self) -> Option<usize> {
+        self.reference.as_ref()?.trim().parse().ok()
+    }
+
+    /// Get the sequence variation described by the feature, if any.
+    ///
+    /// Only sequence variants, mutagenesis sites and sequence conflicts
+    /// describe variations. `None` is returned for other feature types,
+    /// for features without an original sequence (such as deletions), and
+    /// for features with an unknown start position.
+    pub fn variant(&self) -> Option<Variant> {
+        match self.ty {
+            FeatureType::SequenceVariant
+            | FeatureType::MutagenesisSite
+            | FeatureType::SequenceConflict => (),
+            _ => return None,
+        }
+        let (position, _) = self.location.bounds()?;
+        Some(Variant {
+            position,
+            original: self.original.clone()?,
+            variation: self.variation.clone(),
+        })
+    }
+
+    /// Get the name of the ligand bound by the feature, if any.
+    ///
+    /// Only binding site features have a ligand, and older UniProtKB
+    /// releases describe binding sites without any ligand element.
+    pub fn ligand_name(&self) -> Option<&str> {
+        self.ligand.as_ref().map(|l| l.name.as_str())
+    }
+
+    /// Get the ChEBI identifier of the ligand bound by the feature, if any.
+    ///
+    /// Returns `None` if the feature has no ligand, or if the ligand is
+    /// not cross-referenced to ChEBI, as is the case for generic ligands
+    /// such as `substrate`.
+    pub fn ligand_chebi(&self) -> Option<&str> {
+        self.ligand
+            .as_ref()?
+            .db_reference
+            .as_ref()
+            .filter(|dbref| dbref.ty == "ChEBI")
+            .map(|dbref| dbref.id.as_str())
+    }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Feature {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"feature");
 
@@ -72,9 +161,9 @@ impl FromXml for Feature {
         let mut optloc: Option<FeatureLocation> = None;
         let mut optligand: Option<Ligand> = None;
         let mut optligandpart: Option<LigandPart> = None;
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"location" => {
-                let loc = FeatureLocation::from_xml(&e, reader, buffer)?;
+                let loc = FeatureLocation::from_xml(&e, reader, buffer, options)?;
                 if optloc.replace(loc).is_some() {
                     return Err(Error::DuplicateElement("location", "feature"));
                 }
@@ -86,13 +175,13 @@ impl FromXml for Feature {
                 variation.push(parse_text!(e, reader, buffer));
             },
             e @ b"ligand" => {
-                let ligand = Ligand::from_xml(&e, reader, buffer)?;
+                let ligand = Ligand::from_xml(&e, reader, buffer, options)?;
                 if optligand.replace(ligand).is_some() {
                     return Err(Error::DuplicateElement("ligand", "feature"));
                 }
             },
             e @ b"ligandPart" => {
-                let ligandpart = LigandPart::from_xml(&e, reader, buffer)?;
+                let ligandpart = LigandPart::from_xml(&e, reader, buffer, options)?;
                 if optligandpart.replace(ligandpart).is_some() {
                     return Err(Error::DuplicateElement("ligandPart", "feature"));
                 }
@@ -103,8 +192,13 @@ impl FromXml for Feature {
         let location = optloc.ok_or(Error::MissingAttribute("location", "feature"))?;
 
         // create a new Feature with the right `type`
-        let mut feature = decode_attribute(event, reader, "type", "feature")
-            .map(|ty| Feature::new(ty, location))?;
+        let ty = match decode_attribute(event, reader, "type", "feature") {
+            Err(Error::InvalidValue(_, _, InvalidValue(s))) if options.is_lenient() => {
+                FeatureType::Unknown(s.as_str().into())
+            }
+            other => other?,
+        };
+        let mut feature = Feature::new(ty, location);
 
         // extract optional attributes
         feature.id = extract_attribute(event, "id")?
@@ -119,7 +213,7 @@ impl FromXml for Feature {
             .map(|a| a.decode_and_unescape_value(reader))
             .transpose()?
             .map(From::from);
-        feature.evidences = get_evidences(reader, &event)?;
+        feature.evidences = get_evidences(reader, event)?;
         feature.original = original;
         feature.variation = variation;
         feature.ligand = optligand;
@@ -129,47 +223,191 @@ impl FromXml for Feature {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Feature {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("feature");
+        elem.push_attribute(("type", self.ty.as_str()));
+        if let Some(id) = &self.id {
+            elem.push_attribute(("id", id.as_ref()));
+        }
+        if let Some(description) = &self.description {
+            elem.push_attribute(("description", description.as_ref()));
+        }
+        push_evidences(&mut elem, &self.evidences);
+        if let Some(reference) = &self.reference {
+            elem.push_attribute(("ref", reference.as_ref()));
+        }
+        write_element(writer, elem, |w| {
+            if let Some(original) = &self.original {
+                write_text_element(w, BytesStart::new("original"), original)?;
+            }
+            for variation in &self.variation {
+                write_text_element(w, BytesStart::new("variation"), variation)?;
+            }
+            self.location.to_xml(w)?;
+            if let Some(ligand) = &self.ligand {
+                ligand.to_xml(w)?;
+            }
+            if let Some(ligand_part) = &self.ligand_part {
+                ligand_part.to_xml(w)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// A sequence variation described by a feature.
+pub struct Variant {
+    /// The 1-based position of the first residue of the original sequence.
+    pub position: usize,
+    /// The original sequence, which may span several residues.
+    pub original: ShortString,
+    /// The alternative sequences replacing the original sequence.
+    pub variation: Vec<ShortString>,
+}
+
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The type of annotations that can be attached to a sequence.
 pub enum FeatureType {
+    #[cfg_attr(feature = "serde", serde(rename = "active site"))]
     ActiveSite,
+    #[cfg_attr(feature = "serde", serde(rename = "binding site"))]
     BindingSite,
+    #[cfg_attr(feature = "serde", serde(rename = "calcium-binding region"))]
+    CalciumBindingRegion,
+    #[cfg_attr(feature = "serde", serde(rename = "chain"))]
     Chain,
+    #[cfg_attr(feature = "serde", serde(rename = "coiled-coil region"))]
     CoiledCoilRegion,
+    #[cfg_attr(feature = "serde", serde(rename = "compositionally biased region"))]
     CompositionallyBiasedRegion,
+    #[cfg_attr(feature = "serde", serde(rename = "cross-link"))]
     CrossLink,
+    #[cfg_attr(feature = "serde", serde(rename = "disulfide bond"))]
     DisulfideBond,
+    #[cfg_attr(feature = "serde", serde(rename = "DNA-binding region"))]
     DnaBindingRegion,
+    #[cfg_attr(feature = "serde", serde(rename = "domain"))]
     Domain,
+    #[cfg_attr(feature = "serde", serde(rename = "glycosylation site"))]
     GlycosylationSite,
+    #[cfg_attr(feature = "serde", serde(rename = "helix"))]
     Helix,
+    #[cfg_attr(feature = "serde", serde(rename = "initiator methionine"))]
     InitiatorMethionine,
+    #[cfg_attr(feature = "serde", serde(rename = "lipid moiety-binding region"))]
     LipidMoietyBindingRegion,
+    #[cfg_attr(feature = "serde", serde(rename = "metal ion-binding site"))]
+    MetalIonBindingSite,
+    #[cfg_attr(feature = "serde", serde(rename = "modified residue"))]
     ModifiedResidue,
+    #[cfg_attr(feature = "serde", serde(rename = "mutagenesis site"))]
     MutagenesisSite,
+    #[cfg_attr(feature = "serde", serde(rename = "non-consecutive residues"))]
     NonConsecutiveResidues,
+    #[cfg_attr(feature = "serde", serde(rename = "non-terminal residue"))]
     NonTerminalResidue,
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "nucleotide phosphate-binding region")
+    )]
+    NucleotidePhosphateBindingRegion,
+    #[cfg_attr(feature = "serde", serde(rename = "peptide"))]
     Peptide,
+    #[cfg_attr(feature = "serde", serde(rename = "propeptide"))]
     Propeptide,
+    #[cfg_attr(feature = "serde", serde(rename = "region of interest"))]
     RegionOfInterest,
+    #[cfg_attr(feature = "serde", serde(rename = "repeat"))]
     Repeat,
+    #[cfg_attr(feature = "serde", serde(rename = "non-standard amino acid"))]
     NonStandardAminoAcid,
+    #[cfg_attr(feature = "serde", serde(rename = "sequence conflict"))]
     SequenceConflict,
+    #[cfg_attr(feature = "serde", serde(rename = "sequence variant"))]
     SequenceVariant,
+    #[cfg_attr(feature = "serde", serde(rename = "short sequence motif"))]
     ShortSequenceMotif,
+    #[cfg_attr(feature = "serde", serde(rename = "signal peptide"))]
     SignalPeptide,
+    #[cfg_attr(feature = "serde", serde(rename = "site"))]
     Site,
+    #[cfg_attr(feature = "serde", serde(rename = "splice variant"))]
     SpliceVariant,
+    #[cfg_attr(feature = "serde", serde(rename = "strand"))]
     Strand,
+    #[cfg_attr(feature = "serde", serde(rename = "topological domain"))]
     TopologicalDomain,
+    #[cfg_attr(feature = "serde", serde(rename = "transit peptide"))]
     TransitPeptide,
+    #[cfg_attr(feature = "serde", serde(rename = "transmembrane region"))]
     TransmembraneRegion,
+    #[cfg_attr(feature = "serde", serde(rename = "turn"))]
     Turn,
+    #[cfg_attr(feature = "serde", serde(rename = "unsure residue"))]
     UnsureResidue,
+    #[cfg_attr(feature = "serde", serde(rename = "zinc finger region"))]
     ZincFingerRegion,
+    #[cfg_attr(feature = "serde", serde(rename = "intramembrane region"))]
     IntramembraneRegion,
+    /// A feature type unknown to this crate, only created by lenient parsers.
+    Unknown(ShortString),
+}
+
+impl FeatureType {
+    /// Get the string representation of the feature type.
+    pub fn as_str(&self) -> &str {
+        match self {
+            FeatureType::ActiveSite => "active site",
+            FeatureType::BindingSite => "binding site",
+            FeatureType::CalciumBindingRegion => "calcium-binding region",
+            FeatureType::Chain => "chain",
+            FeatureType::CoiledCoilRegion => "coiled-coil region",
+            FeatureType::CompositionallyBiasedRegion => "compositionally biased region",
+            FeatureType::CrossLink => "cross-link",
+            FeatureType::DisulfideBond => "disulfide bond",
+            FeatureType::DnaBindingRegion => "DNA-binding region",
+            FeatureType::Domain => "domain",
+            FeatureType::GlycosylationSite => "glycosylation site",
+            FeatureType::Helix => "helix",
+            FeatureType::InitiatorMethionine => "initiator methionine",
+            FeatureType::LipidMoietyBindingRegion => "lipid moiety-binding region",
+            FeatureType::MetalIonBindingSite => "metal ion-binding site",
+            FeatureType::ModifiedResidue => "modified residue",
+            FeatureType::MutagenesisSite => "mutagenesis site",
+            FeatureType::NonConsecutiveResidues => "non-consecutive residues",
+            FeatureType::NonTerminalResidue => "non-terminal residue",
+            FeatureType::NucleotidePhosphateBindingRegion => "nucleotide phosphate-binding region",
+            FeatureType::Peptide => "peptide",
+            FeatureType::Propeptide => "propeptide",
+            FeatureType::RegionOfInterest => "region of interest",
+            FeatureType::Repeat => "repeat",
+            FeatureType::NonStandardAminoAcid => "non-standard amino acid",
+            FeatureType::SequenceConflict => "sequence conflict",
+            FeatureType::SequenceVariant => "sequence variant",
+            FeatureType::ShortSequenceMotif => "short sequence motif",
+            FeatureType::SignalPeptide => "signal peptide",
+            FeatureType::Site => "site",
+            FeatureType::SpliceVariant => "splice variant",
+            FeatureType::Strand => "strand",
+            FeatureType::TopologicalDomain => "topological domain",
+            FeatureType::TransitPeptide => "transit peptide",
+            FeatureType::TransmembraneRegion => "transmembrane region",
+            FeatureType::Turn => "turn",
+            FeatureType::UnsureResidue => "unsure residue",
+            FeatureType::ZincFingerRegion => "zinc finger region",
+            FeatureType::IntramembraneRegion => "intramembrane region",
+            FeatureType::Unknown(s) => s,
+        }
+    }
 }
 
 impl FromStr for FeatureType {
@@ -179,6 +417,7 @@ impl FromStr for FeatureType {
         match s {
             "active site" => Ok(ActiveSite),
             "binding site" => Ok(BindingSite),
+            "calcium-binding region" => Ok(CalciumBindingRegion),
             "chain" => Ok(Chain),
             "coiled-coil region" => Ok(CoiledCoilRegion),
             "compositionally biased region" => Ok(CompositionallyBiasedRegion),
@@ -190,10 +429,12 @@ impl FromStr for FeatureType {
             "helix" => Ok(Helix),
             "initiator methionine" => Ok(InitiatorMethionine),
             "lipid moiety-binding region" => Ok(LipidMoietyBindingRegion),
+            "metal ion-binding site" => Ok(MetalIonBindingSite),
             "modified residue" => Ok(ModifiedResidue),
             "mutagenesis site" => Ok(MutagenesisSite),
             "non-consecutive residues" => Ok(NonConsecutiveResidues),
             "non-terminal residue" => Ok(NonTerminalResidue),
+            "nucleotide phosphate-binding region" => Ok(NucleotidePhosphateBindingRegion),
             "peptide" => Ok(Peptide),
             "propeptide" => Ok(Propeptide),
             "region of interest" => Ok(RegionOfInterest),
@@ -204,7 +445,7 @@ impl FromStr for FeatureType {
             "short sequence motif" => Ok(ShortSequenceMotif),
             "signal peptide" => Ok(SignalPeptide),
             "site" => Ok(Site),
-            "splice variant" => Ok(Site),
+            "splice variant" => Ok(SpliceVariant),
             "strand" => Ok(Strand),
             "topological domain" => Ok(TopologicalDomain),
             "transit peptide" => Ok(TransitPeptide),
@@ -217,3 +458,80 @@ impl FromStr for FeatureType {
         }
     }
 }
+
+impl TryFrom<&str> for FeatureType {
+    type Error = InvalidValue;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
+
+impl Display for FeatureType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn feature_type_round_trip() {
+        use self::FeatureType::*;
+        let types = [
+            ActiveSite,
+            BindingSite,
+            CalciumBindingRegion,
+            Chain,
+            CoiledCoilRegion,
+            CompositionallyBiasedRegion,
+            CrossLink,
+            DisulfideBond,
+            DnaBindingRegion,
+            Domain,
+            GlycosylationSite,
+            Helix,
+            InitiatorMethionine,
+            LipidMoietyBindingRegion,
+            MetalIonBindingSite,
+            ModifiedResidue,
+            MutagenesisSite,
+            NonConsecutiveResidues,
+            NonTerminalResidue,
+            NucleotidePhosphateBindingRegion,
+            Peptide,
+            Propeptide,
+            RegionOfInterest,
+            Repeat,
+            NonStandardAminoAcid,
+            SequenceConflict,
+            SequenceVariant,
+            ShortSequenceMotif,
+            SignalPeptide,
+            Site,
+            SpliceVariant,
+            Strand,
+            TopologicalDomain,
+            TransitPeptide,
+            TransmembraneRegion,
+            Turn,
+            UnsureResidue,
+            ZincFingerRegion,
+            IntramembraneRegion,
+        ];
+        for ty in types {
+            assert_eq!(FeatureType::from_str(&ty.to_string()), Ok(ty));
+        }
+    }
+
+    #[test]
+    fn splice_variant() {
+        assert_eq!(
+            FeatureType::from_str("splice variant"),
+            Ok(FeatureType::SpliceVariant)
+        );
+        assert_eq!(FeatureType::from_str("site"), Ok(FeatureType::Site));
+    }
+}
@@ -1,29 +1,94 @@
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
 use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::push_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_empty_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Describes the location where a feature can be found within a sequence.
 pub enum FeatureLocation {
     Range(Position, Position),
     Position(Position),
 }
 
+impl FeatureLocation {
+    /// Get the 1-based inclusive bounds of the location, if known.
+    ///
+    /// A single `Position` spans from and to itself. Positions with a
+    /// `LessThan` or `GreaterThan` status are used as the bound as-is,
+    /// while `None` is returned if any endpoint has an `Unknown` status
+    /// or no position.
+    pub fn bounds(&self) -> Option<(usize, usize)> {
+        match self {
+            FeatureLocation::Range(begin, end) => Some((begin.known()?, end.known()?)),
+            FeatureLocation::Position(pos) => pos.known().map(|p| (p, p)),
+        }
+    }
+
+    /// Check whether the location certainly contains the given 1-based position.
+    ///
+    /// Contrary to [`bounds`](#method.bounds), this conservatively returns
+    /// `false` when an endpoint has an `Uncertain` status as well.
+    pub fn contains(&self, pos: usize) -> bool {
+        let uncertain = |p: &Position| p.status == Status::Uncertain;
+        let certain = match self {
+            FeatureLocation::Range(begin, end) => !uncertain(begin) && !uncertain(end),
+            FeatureLocation::Position(p) => !uncertain(p),
+        };
+        certain
+            && self
+                .bounds()
+                .is_some_and(|(begin, end)| begin <= pos && pos <= end)
+    }
+
+    /// Get the inclusive length of the location, if known.
+    #[allow(clippy::len_without_is_empty)] // a location spans at least one residue
+    pub fn len(&self) -> Option<usize> {
+        self.bounds()
+            .and_then(|(begin, end)| end.checked_sub(begin))
+            .map(|l| l + 1)
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for FeatureLocation {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"location");
 
@@ -31,21 +96,21 @@ impl FromXml for FeatureLocation {
         let mut optend: Option<Position> = None;
         let mut optposition: Option<Position> = None;
 
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"begin" => {
-                let pos = Position::from_xml(&e, reader, buffer)?;
+                let pos = Position::from_xml(&e, reader, buffer, options)?;
                 if optbegin.replace(pos).is_some() {
                     return Err(Error::DuplicateElement("begin", "location"));
                 }
             },
             e @ b"end" => {
-                let pos = Position::from_xml(&e, reader, buffer)?;
+                let pos = Position::from_xml(&e, reader, buffer, options)?;
                 if optend.replace(pos).is_some() {
                     return Err(Error::DuplicateElement("end", "location"));
                 }
             },
             e @ b"position" => {
-                let pos = Position::from_xml(&e, reader, buffer)?;
+                let pos = Position::from_xml(&e, reader, buffer, options)?;
                 if optposition.replace(pos).is_some() {
                     return Err(Error::DuplicateElement("position", "location"));
                 }
@@ -68,20 +133,64 @@ impl FromXml for FeatureLocation {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for FeatureLocation {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        write_element(writer, BytesStart::new("location"), |w| match self {
+            FeatureLocation::Range(begin, end) => {
+                begin.write_xml(w, "begin")?;
+                end.write_xml(w, "end")
+            }
+            FeatureLocation::Position(pos) => pos.write_xml(w, "position"),
+        })
+    }
+}
+
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub pos: Option<usize>,
     pub status: Status,
     pub evidence: Vec<usize>,
 }
 
+impl Position {
+    /// Get the position, unless it is missing or has an `Unknown` status.
+    fn known(&self) -> Option<usize> {
+        match self.status {
+            Status::Unknown => None,
+            _ => self.pos,
+        }
+    }
+
+    /// Write the position as an XML element with the given tag name.
+    #[cfg(feature = "parser")]
+    pub(crate) fn write_xml<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        tag: &str,
+    ) -> Result<(), Error> {
+        let mut elem = BytesStart::new(tag);
+        if let Some(pos) = self.pos {
+            elem.push_attribute(("position", pos.to_string().as_str()));
+        }
+        if self.status != Status::Certain {
+            elem.push_attribute(("status", self.status.as_str()));
+        }
+        push_evidences(&mut elem, &self.evidence);
+        write_empty_element(writer, elem)
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Position {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert!(
             event.local_name().as_ref() == b"begin"
@@ -94,7 +203,7 @@ impl FromXml for Position {
             Err(Error::MissingAttribute(_, _)) => Status::default(),
             Err(other) => return Err(other),
         };
-        let evidence = get_evidences(reader, &event)?;
+        let evidence = get_evidences(reader, event)?;
         let pos = extract_attribute(event, "position")?
             .map(|x| x.decode_and_unescape_value(reader))
             .transpose()?
@@ -112,18 +221,32 @@ impl FromXml for Position {
 
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Status {
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "certain"))]
     Certain,
+    #[cfg_attr(feature = "serde", serde(rename = "uncertain"))]
     Uncertain,
+    #[cfg_attr(feature = "serde", serde(rename = "less than"))]
     LessThan,
+    #[cfg_attr(feature = "serde", serde(rename = "greater than"))]
     GreaterThan,
+    #[cfg_attr(feature = "serde", serde(rename = "unknown"))]
     Unknown,
 }
 
-impl Default for Status {
-    fn default() -> Self {
-        Status::Certain
+impl Status {
+    /// Get the string representation of the status.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Certain => "certain",
+            Status::Uncertain => "uncertain",
+            Status::LessThan => "less than",
+            Status::GreaterThan => "greater than",
+            Status::Unknown => "unknown",
+        }
     }
 }
 
@@ -140,3 +263,82 @@ impl FromStr for Status {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn position(pos: Option<usize>, status: Status) -> Position {
+        Position {
+            pos,
+            status,
+            evidence: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn bounds_range() {
+        let loc = FeatureLocation::Range(
+            position(Some(12), Status::Certain),
+            position(Some(41), Status::GreaterThan),
+        );
+        assert_eq!(loc.bounds(), Some((12, 41)));
+        assert_eq!(loc.len(), Some(30));
+    }
+
+    #[test]
+    fn bounds_position() {
+        let loc = FeatureLocation::Position(position(Some(7), Status::Certain));
+        assert_eq!(loc.bounds(), Some((7, 7)));
+        assert_eq!(loc.len(), Some(1));
+    }
+
+    #[test]
+    fn contains() {
+        let loc = FeatureLocation::Range(
+            position(Some(12), Status::Certain),
+            position(Some(41), Status::GreaterThan),
+        );
+        assert!(!loc.contains(11));
+        assert!(loc.contains(12));
+        assert!(loc.contains(41));
+        assert!(!loc.contains(42));
+
+        let loc = FeatureLocation::Position(position(Some(7), Status::Certain));
+        assert!(loc.contains(7));
+        assert!(!loc.contains(8));
+
+        let loc = FeatureLocation::Range(
+            position(Some(12), Status::Uncertain),
+            position(Some(41), Status::Certain),
+        );
+        assert!(!loc.contains(20));
+        let loc = FeatureLocation::Range(
+            position(None, Status::Unknown),
+            position(Some(41), Status::Certain),
+        );
+        assert!(!loc.contains(20));
+        let loc = FeatureLocation::Position(position(Some(7), Status::Uncertain));
+        assert!(!loc.contains(7));
+    }
+
+    #[test]
+    fn bounds_unknown() {
+        let loc = FeatureLocation::Range(
+            position(None, Status::Unknown),
+            position(Some(41), Status::Certain),
+        );
+        assert_eq!(loc.bounds(), None);
+        assert_eq!(loc.len(), None);
+
+        let loc = FeatureLocation::Range(
+            position(Some(1), Status::Unknown),
+            position(Some(41), Status::Certain),
+        );
+        assert_eq!(loc.bounds(), None);
+
+        let loc = FeatureLocation::Position(position(None, Status::Certain));
+        assert_eq!(loc.bounds(), None);
+    }
+}
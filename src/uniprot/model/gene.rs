@@ -1,34 +1,92 @@
+use core::convert::TryFrom;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
 use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::push_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Describes a gene.
 pub struct Gene {
     pub names: Vec<Name>,
 }
 
+impl Gene {
+    /// Get the primary name of the gene, if any.
+    ///
+    /// If the gene has several primary names, which should not happen in
+    /// valid files, the first one is returned.
+    pub fn primary(&self) -> Option<&str> {
+        self.names_of_type(NameType::Primary).next()
+    }
+
+    /// Get the synonyms of the gene.
+    pub fn synonyms(&self) -> Vec<&str> {
+        self.names_of_type(NameType::Synonym).collect()
+    }
+
+    /// Get the ordered locus names of the gene.
+    pub fn ordered_loci(&self) -> Vec<&str> {
+        self.names_of_type(NameType::OrderedLocus).collect()
+    }
+
+    /// Get the ORF names of the gene.
+    pub fn orf_names(&self) -> Vec<&str> {
+        self.names_of_type(NameType::Orf).collect()
+    }
+
+    fn names_of_type(&self, ty: NameType) -> impl Iterator<Item = &str> {
+        self.names
+            .iter()
+            .filter(move |name| name.ty == ty)
+            .map(|name| name.value.as_ref())
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Gene {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"gene");
 
         let mut gene = Gene::default();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"name" => {
-                gene.names.push(FromXml::from_xml(&e, reader, buffer)?);
+                gene.names.push(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
@@ -36,9 +94,22 @@ impl FromXml for Gene {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Gene {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        write_element(writer, BytesStart::new("gene"), |w| {
+            for name in &self.names {
+                name.to_xml(w)?;
+            }
+            Ok(())
+        })
+    }
+}
+
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Describes different types of gene designations.
 pub struct Name {
     pub value: ShortString,
@@ -62,32 +133,61 @@ impl Name {
 
 // ---------------------------------------------------------------------------
 
+#[cfg(feature = "parser")]
 impl FromXml for Name {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"name");
 
         let name = parse_text!(event, reader, buffer);
-        let evidence = get_evidences(reader, &event)?;
+        let evidence = get_evidences(reader, event)?;
         let ty = decode_attribute(event, reader, "type", "name")?;
 
         Ok(Self::new_with_evidence(name, ty, evidence))
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Name {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("name");
+        push_evidences(&mut elem, &self.evidence);
+        elem.push_attribute(("type", self.ty.as_str()));
+        write_text_element(writer, elem, &self.value)
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NameType {
+    #[cfg_attr(feature = "serde", serde(rename = "primary"))]
     Primary,
+    #[cfg_attr(feature = "serde", serde(rename = "synonym"))]
     Synonym,
+    #[cfg_attr(feature = "serde", serde(rename = "ordered locus"))]
     OrderedLocus,
+    #[cfg_attr(feature = "serde", serde(rename = "ORF"))]
     Orf,
 }
 
+impl NameType {
+    /// Get the string representation of the name type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NameType::Primary => "primary",
+            NameType::Synonym => "synonym",
+            NameType::OrderedLocus => "ordered locus",
+            NameType::Orf => "ORF",
+        }
+    }
+}
+
 impl FromStr for NameType {
     type Err = InvalidValue;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -100,3 +200,10 @@ impl FromStr for NameType {
         }
     }
 }
+
+impl TryFrom<&str> for NameType {
+    type Error = InvalidValue;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
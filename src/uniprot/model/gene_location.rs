@@ -1,18 +1,42 @@
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
 use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::push_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Describes non-nuclear gene locations (organelles and plasmids).
 pub struct GeneLocation {
     // name: Vec<Status>,
@@ -31,20 +55,22 @@ impl GeneLocation {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for GeneLocation {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"geneLocation");
 
         let mut geneloc = decode_attribute(event, reader, "type", "geneLocation").map(Self::new)?;
 
-        geneloc.evidences = get_evidences(reader, &event)?;
-        parse_inner! {event, reader, buffer,
+        geneloc.evidences = get_evidences(reader, event)?;
+        parse_inner! {event, reader, buffer, options,
             e @ b"name" => {
-                geneloc.names.push(FromXml::from_xml(&e, reader, buffer)?);
+                geneloc.names.push(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
@@ -52,22 +78,71 @@ impl FromXml for GeneLocation {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for GeneLocation {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("geneLocation");
+        elem.push_attribute(("type", self.ty.as_str()));
+        push_evidences(&mut elem, &self.evidences);
+        write_element(writer, elem, |w| {
+            for name in &self.names {
+                name.to_xml(w)?;
+            }
+            Ok(())
+        })
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LocationType {
+    #[cfg_attr(feature = "serde", serde(rename = "apicoplast"))]
     Apicoplast,
+    #[cfg_attr(feature = "serde", serde(rename = "chloroplast"))]
     Chloroplast,
+    #[cfg_attr(feature = "serde", serde(rename = "organellar chromatophore"))]
     OrganellarChromatophore,
+    #[cfg_attr(feature = "serde", serde(rename = "cyanelle"))]
     Cyanelle,
+    #[cfg_attr(feature = "serde", serde(rename = "hydrogenosome"))]
     Hydrogenosome,
+    #[cfg_attr(feature = "serde", serde(rename = "mitochondrion"))]
     Mitochondrion,
+    #[cfg_attr(feature = "serde", serde(rename = "non-photosynthetic plastid"))]
     NonPhotosyntheticPlasmid,
+    #[cfg_attr(feature = "serde", serde(rename = "nucleomorph"))]
     Nucleomorph,
+    #[cfg_attr(feature = "serde", serde(rename = "plasmid"))]
     Plasmid,
+    #[cfg_attr(feature = "serde", serde(rename = "plastid"))]
     Plastid,
 }
 
+impl LocationType {
+    /// Get the string representation of the location type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LocationType::Apicoplast => "apicoplast",
+            LocationType::Chloroplast => "chloroplast",
+            LocationType::OrganellarChromatophore => "organellar chromatophore",
+            LocationType::Cyanelle => "cyanelle",
+            LocationType::Hydrogenosome => "hydrogenosome",
+            LocationType::Mitochondrion => "mitochondrion",
+            LocationType::NonPhotosyntheticPlasmid => "non-photosynthetic plastid",
+            LocationType::Nucleomorph => "nucleomorph",
+            LocationType::Plasmid => "plasmid",
+            LocationType::Plastid => "plastid",
+        }
+    }
+
+    /// Check whether the location is an organelle, i.e. not a plasmid.
+    pub fn is_organelle(&self) -> bool {
+        *self != LocationType::Plasmid
+    }
+}
+
 impl FromStr for LocationType {
     type Err = InvalidValue;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -91,6 +166,7 @@ impl FromStr for LocationType {
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LocationName {
     pub value: ShortString,
     pub status: LocationStatus,
@@ -108,11 +184,13 @@ impl LocationName {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for LocationName {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"name");
 
@@ -127,18 +205,37 @@ impl FromXml for LocationName {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for LocationName {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("name");
+        if self.status != LocationStatus::Known {
+            elem.push_attribute(("status", self.status.as_str()));
+        }
+        write_text_element(writer, elem, &self.value)
+    }
+}
+
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Indicates whether the name of a plasmid is known or unknown.
 pub enum LocationStatus {
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "known"))]
     Known,
+    #[cfg_attr(feature = "serde", serde(rename = "unknown"))]
     Unknown,
 }
 
-impl Default for LocationStatus {
-    fn default() -> Self {
-        LocationStatus::Known
+impl LocationStatus {
+    /// Get the string representation of the location status.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LocationStatus::Known => "known",
+            LocationStatus::Unknown => "unknown",
+        }
     }
 }
 
@@ -1,39 +1,549 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::push_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Keyword {
     pub id: ShortString,
     pub value: ShortString,
     pub evidence: Vec<usize>,
 }
 
+impl Keyword {
+    /// Get the category of the keyword, if known.
+    ///
+    /// Keyword identifiers are not allocated by category, so the category
+    /// is looked up in a static table of the controlled vocabulary. The
+    /// table only covers a subset of the UniProt keywords, the most common
+    /// ones of every category: keywords missing from the table, or
+    /// without an identifier, have no category. Use the `keywlist.txt` file
+    /// of the UniProt release to categorize any other keyword.
+    pub fn category(&self) -> Option<KeywordCategory> {
+        let id = self.id.strip_prefix("KW-")?.parse::<u16>().ok()?;
+        if let Some(category) = KeywordCategory::from_id(id) {
+            return Some(category);
+        }
+        CATEGORIES
+            .binary_search_by_key(&id, |&(kw, _)| kw)
+            .ok()
+            .map(|i| CATEGORIES[i].1)
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Keyword {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"keyword");
 
-        let mut keyword = Keyword::default();
-        keyword.value = parse_text!(event, reader, buffer);
-        keyword.evidence = get_evidences(reader, &event)?;
-        keyword.id = extract_attribute(event, "id")?
+        let value = parse_text!(event, reader, buffer);
+        let evidence = get_evidences(reader, event)?;
+        let id = extract_attribute(event, "id")?
             .ok_or(Error::MissingAttribute("id", "keyword"))?
             .decode_and_unescape_value(reader)?
             .into();
 
-        Ok(keyword)
+        Ok(Keyword {
+            id,
+            value,
+            evidence,
+        })
+    }
+}
+
+#[cfg(feature = "parser")]
+impl ToXml for Keyword {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("keyword");
+        push_evidences(&mut elem, &self.evidence);
+        elem.push_attribute(("id", self.id.as_ref()));
+        write_text_element(writer, elem, &self.value)
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The category of a UniProt keyword.
+pub enum KeywordCategory {
+    #[cfg_attr(feature = "serde", serde(rename = "Technical term"))]
+    TechnicalTerm,
+    #[cfg_attr(feature = "serde", serde(rename = "PTM"))]
+    Ptm,
+    #[cfg_attr(feature = "serde", serde(rename = "Molecular function"))]
+    MolecularFunction,
+    #[cfg_attr(feature = "serde", serde(rename = "Ligand"))]
+    Ligand,
+    #[cfg_attr(feature = "serde", serde(rename = "Domain"))]
+    Domain,
+    #[cfg_attr(feature = "serde", serde(rename = "Disease"))]
+    Disease,
+    #[cfg_attr(feature = "serde", serde(rename = "Developmental stage"))]
+    DevelopmentalStage,
+    #[cfg_attr(feature = "serde", serde(rename = "Coding sequence diversity"))]
+    CodingSequenceDiversity,
+    #[cfg_attr(feature = "serde", serde(rename = "Cellular component"))]
+    CellularComponent,
+    #[cfg_attr(feature = "serde", serde(rename = "Biological process"))]
+    BiologicalProcess,
+}
+
+impl KeywordCategory {
+    /// Get the string representation of the keyword category.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeywordCategory::TechnicalTerm => "Technical term",
+            KeywordCategory::Ptm => "PTM",
+            KeywordCategory::MolecularFunction => "Molecular function",
+            KeywordCategory::Ligand => "Ligand",
+            KeywordCategory::Domain => "Domain",
+            KeywordCategory::Disease => "Disease",
+            KeywordCategory::DevelopmentalStage => "Developmental stage",
+            KeywordCategory::CodingSequenceDiversity => "Coding sequence diversity",
+            KeywordCategory::CellularComponent => "Cellular component",
+            KeywordCategory::BiologicalProcess => "Biological process",
+        }
+    }
+
+    /// Get the keyword identifier of the category itself, e.g. `KW-9992`.
+    pub fn id(&self) -> &'static str {
+        match self {
+            KeywordCategory::TechnicalTerm => "KW-9990",
+            KeywordCategory::Ptm => "KW-9991",
+            KeywordCategory::MolecularFunction => "KW-9992",
+            KeywordCategory::Ligand => "KW-9993",
+            KeywordCategory::Domain => "KW-9994",
+            KeywordCategory::Disease => "KW-9995",
+            KeywordCategory::DevelopmentalStage => "KW-9996",
+            KeywordCategory::CodingSequenceDiversity => "KW-9997",
+            KeywordCategory::CellularComponent => "KW-9998",
+            KeywordCategory::BiologicalProcess => "KW-9999",
+        }
+    }
+
+    /// Get the category with the given numeric keyword identifier.
+    fn from_id(id: u16) -> Option<Self> {
+        use self::KeywordCategory::*;
+        match id {
+            9990 => Some(TechnicalTerm),
+            9991 => Some(Ptm),
+            9992 => Some(MolecularFunction),
+            9993 => Some(Ligand),
+            9994 => Some(Domain),
+            9995 => Some(Disease),
+            9996 => Some(DevelopmentalStage),
+            9997 => Some(CodingSequenceDiversity),
+            9998 => Some(CellularComponent),
+            9999 => Some(BiologicalProcess),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for KeywordCategory {
+    type Err = InvalidValue;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::KeywordCategory::*;
+        match s {
+            "Technical term" => Ok(TechnicalTerm),
+            "PTM" => Ok(Ptm),
+            "Molecular function" => Ok(MolecularFunction),
+            "Ligand" => Ok(Ligand),
+            "Domain" => Ok(Domain),
+            "Disease" => Ok(Disease),
+            "Developmental stage" => Ok(DevelopmentalStage),
+            "Coding sequence diversity" => Ok(CodingSequenceDiversity),
+            "Cellular component" => Ok(CellularComponent),
+            "Biological process" => Ok(BiologicalProcess),
+            other => Err(InvalidValue::from(other)),
+        }
+    }
+}
+
+/// The categories of known keywords, sorted by numeric identifier.
+///
+/// This is a subset of the UniProt keyword list. To add keywords, get the
+/// `AC` and `CA` lines of their records in the `keywlist.txt` file of the
+/// UniProt release, e.g. for `KW-0001` and `Ligand` add `(1, Ligand)` at
+/// its sorted position. The table of a whole release can be generated with:
+///
+/// ```text
+/// awk '/^ID/ { name = substr($0, 6, length($0) - 6) }
+///      /^AC/ { id = substr($2, 4) + 0 }
+///      /^CA/ { n = split(substr($0, 6), w, /[ .]+/); ca = "";
+///              for (i = 1; i <= n; i++) ca = ca toupper(substr(w[i], 1, 1)) tolower(substr(w[i], 2)) }
+///      /^\/\// { if (ca) printf "(%d, %s), // %s\n", id, ca, name; ca = "" }' \
+///     keywlist.txt | sort -t'(' -k2 -n
+/// ```
+///
+/// The `categories_sorted` test checks that the table stays sorted for
+/// the binary search in [`Keyword::category`].
+const CATEGORIES: &[(u16, KeywordCategory)] = {
+    use self::KeywordCategory::*;
+    &[
+        (1, Ligand),                    // 2Fe-2S
+        (2, TechnicalTerm),             // 3D-structure
+        (3, Ligand),                    // 3Fe-4S
+        (4, Ligand),                    // 4Fe-4S
+        (7, Ptm),                       // Acetylation
+        (8, MolecularFunction),         // Acetylcholine receptor inhibiting toxin
+        (10, MolecularFunction),        // Activator
+        (12, MolecularFunction),        // Acyltransferase
+        (13, Ptm),                      // ADP-ribosylation
+        (15, Disease),                  // Albinism
+        (24, CodingSequenceDiversity),  // Alternative initiation
+        (25, CodingSequenceDiversity),  // Alternative splicing
+        (26, Disease),                  // Alzheimer disease
+        (27, Ptm),                      // Amidation
+        (28, BiologicalProcess),        // Amino-acid biosynthesis
+        (35, CellularComponent),        // Amyloplast
+        (36, Disease),                  // Amyotrophic lateral sclerosis
+        (40, Domain),                   // ANK repeat
+        (44, BiologicalProcess),        // Antibiotic
+        (49, MolecularFunction),        // Antioxidant
+        (53, BiologicalProcess),        // Apoptosis
+        (64, MolecularFunction),        // Aspartyl protease
+        (67, Ligand),                   // ATP-binding
+        (68, Ptm),                      // Autocatalytic cleavage
+        (90, BiologicalProcess),        // Biological rhythms
+        (92, Ligand),                   // Biotin
+        (106, Ligand),                  // Calcium
+        (108, MolecularFunction),       // Calcium channel impairing toxin
+        (119, BiologicalProcess),       // Carbohydrate metabolism
+        (122, Disease),                 // Cardiomyopathy
+        (123, MolecularFunction),       // Cardiotoxin
+        (130, BiologicalProcess),       // Cell adhesion
+        (131, BiologicalProcess),       // Cell cycle
+        (132, BiologicalProcess),       // Cell division
+        (134, CellularComponent),       // Cell wall
+        (143, MolecularFunction),       // Chaperone
+        (145, BiologicalProcess),       // Chemotaxis
+        (150, CellularComponent),       // Chloroplast
+        (156, MolecularFunction),       // Chromatin regulator
+        (158, CellularComponent),       // Chromosome
+        (170, Ligand),                  // Cobalt
+        (175, Domain),                  // Coiled coil
+        (181, TechnicalTerm),           // Complete proteome
+        (186, Ligand),                  // Copper
+        (202, MolecularFunction),       // Cytokine
+        (206, CellularComponent),       // Cytoskeleton
+        (209, Disease),                 // Deafness
+        (219, Disease),                 // Diabetes mellitus
+        (221, BiologicalProcess),       // Differentiation
+        (225, Disease),                 // Disease variant
+        (227, BiologicalProcess),       // DNA damage
+        (233, BiologicalProcess),       // DNA recombination
+        (234, BiologicalProcess),       // DNA repair
+        (235, BiologicalProcess),       // DNA replication
+        (238, MolecularFunction),       // DNA-binding
+        (239, MolecularFunction),       // DNA-directed DNA polymerase
+        (240, MolecularFunction),       // DNA-directed RNA polymerase
+        (242, Disease),                 // Dwarfism
+        (244, DevelopmentalStage),      // Early protein
+        (245, Domain),                  // EGF-like domain
+        (249, BiologicalProcess),       // Electron transport
+        (251, MolecularFunction),       // Elongation factor
+        (255, MolecularFunction),       // Endonuclease
+        (256, CellularComponent),       // Endoplasmic reticulum
+        (266, BiologicalProcess),       // Ethylene biosynthesis
+        (269, MolecularFunction),       // Exonuclease
+        (274, Ligand),                  // FAD
+        (275, BiologicalProcess),       // Fatty acid biosynthesis
+        (276, BiologicalProcess),       // Fatty acid metabolism
+        (282, CellularComponent),       // Flagellum
+        (285, Ligand),                  // Flavoprotein
+        (288, Ligand),                  // FMN
+        (292, BiologicalProcess),       // Fruit ripening
+        (297, MolecularFunction),       // G-protein coupled receptor
+        (301, Ptm),                     // Gamma-carboxyglutamic acid
+        (325, Ptm),                     // Glycoprotein
+        (326, MolecularFunction),       // Glycosidase
+        (328, MolecularFunction),       // Glycosyltransferase
+        (333, CellularComponent),       // Golgi apparatus
+        (336, Ptm),                     // GPI-anchor
+        (339, MolecularFunction),       // Growth factor
+        (342, Ligand),                  // GTP-binding
+        (346, BiologicalProcess),       // Stress response
+        (347, MolecularFunction),       // Helicase
+        (349, Ligand),                  // Heme
+        (372, MolecularFunction),       // Hormone
+        (378, MolecularFunction),       // Hydrolase
+        (379, Ptm),                     // Hydroxylation
+        (391, BiologicalProcess),       // Immunity
+        (393, Domain),                  // Immunoglobulin domain
+        (395, BiologicalProcess),       // Inflammatory response
+        (396, MolecularFunction),       // Initiation factor
+        (399, BiologicalProcess),       // Innate immunity
+        (406, BiologicalProcess),       // Ion transport
+        (407, MolecularFunction),       // Ion channel
+        (408, Ligand),                  // Iron
+        (411, Ligand),                  // Iron-sulfur
+        (413, MolecularFunction),       // Isomerase
+        (418, MolecularFunction),       // Kinase
+        (426, DevelopmentalStage),      // Late protein
+        (433, Domain),                  // Leucine-rich repeat
+        (436, MolecularFunction),       // Ligase
+        (442, BiologicalProcess),       // Lipid degradation
+        (443, BiologicalProcess),       // Lipid metabolism
+        (444, BiologicalProcess),       // Lipid biosynthesis
+        (446, Ligand),                  // Lipid-binding
+        (449, Ptm),                     // Lipoprotein
+        (456, MolecularFunction),       // Lyase
+        (458, CellularComponent),       // Lysosome
+        (460, Ligand),                  // Magnesium
+        (464, Ligand),                  // Manganese
+        (469, BiologicalProcess),       // Meiosis
+        (472, CellularComponent),       // Membrane
+        (479, Ligand),                  // Metal-binding
+        (482, MolecularFunction),       // Metalloprotease
+        (488, Ptm),                     // Methylation
+        (489, MolecularFunction),       // Methyltransferase
+        (493, CellularComponent),       // Microtubule
+        (496, CellularComponent),       // Mitochondrion
+        (498, BiologicalProcess),       // Mitosis
+        (500, Ligand),                  // Molybdenum
+        (507, BiologicalProcess),       // mRNA processing
+        (508, BiologicalProcess),       // mRNA splicing
+        (519, Ptm),                     // Myristate
+        (520, Ligand),                  // NAD
+        (521, Ligand),                  // NADP
+        (523, Disease),                 // Neurodegeneration
+        (524, BiologicalProcess),       // Neurogenesis
+        (528, MolecularFunction),       // Neurotoxin
+        (533, Ligand),                  // Nickel
+        (539, CellularComponent),       // Nucleus
+        (540, MolecularFunction),       // Nuclease
+        (546, BiologicalProcess),       // Nucleotide metabolism
+        (547, Ligand),                  // Nucleotide-binding
+        (548, MolecularFunction),       // Nucleotidyltransferase
+        (560, MolecularFunction),       // Oxidoreductase
+        (564, Ptm),                     // Palmitate
+        (574, CellularComponent),       // Periplasm
+        (575, MolecularFunction),       // Peroxidase
+        (576, CellularComponent),       // Peroxisome
+        (582, TechnicalTerm),           // Pharmaceutical
+        (587, BiologicalProcess),       // Phenylpropanoid metabolism
+        (597, Ptm),                     // Phosphoprotein
+        (602, BiologicalProcess),       // Photosynthesis
+        (611, BiologicalProcess),       // Plant defense
+        (621, CodingSequenceDiversity), // Polymorphism
+        (624, BiologicalProcess),       // Polysaccharide degradation
+        (629, MolecularFunction),       // Postsynaptic neurotoxin
+        (630, Ligand),                  // Potassium
+        (636, Ptm),                     // Prenylation
+        (645, MolecularFunction),       // Protease
+        (648, BiologicalProcess),       // Protein biosynthesis
+        (653, BiologicalProcess),       // Protein transport
+        (663, Ligand),                  // Pyridoxal phosphate
+        (672, BiologicalProcess),       // Quinate metabolism
+        (675, MolecularFunction),       // Receptor
+        (677, Domain),                  // Repeat
+        (678, MolecularFunction),       // Repressor
+        (687, MolecularFunction),       // Ribonucleoprotein
+        (688, CodingSequenceDiversity), // Ribosomal frameshifting
+        (689, MolecularFunction),       // Ribosomal protein
+        (691, CodingSequenceDiversity), // RNA editing
+        (694, MolecularFunction),       // RNA-binding
+        (695, MolecularFunction),       // RNA-directed DNA polymerase
+        (696, MolecularFunction),       // RNA-directed RNA polymerase
+        (698, BiologicalProcess),       // rRNA processing
+        (703, CellularComponent),       // Sarcoplasmic reticulum
+        (704, Ptm),                     // Schiff base
+        (716, BiologicalProcess),       // Sensory transduction
+        (720, MolecularFunction),       // Serine protease
+        (723, MolecularFunction),       // Serine/threonine-protein kinase
+        (727, Domain),                  // SH2 domain
+        (728, Domain),                  // SH3 domain
+        (732, Domain),                  // Signal
+        (744, BiologicalProcess),       // Spermatogenesis
+        (748, DevelopmentalStage),      // Sporozoite
+        (749, BiologicalProcess),       // Sporulation
+        (768, Domain),                  // Sushi
+        (770, CellularComponent),       // Synapse
+        (786, Ligand),                  // Thiamine pyrophosphate
+        (788, MolecularFunction),       // Thiol protease
+        (793, CellularComponent),       // Thylakoid
+        (797, CellularComponent),       // Tight junction
+        (799, MolecularFunction),       // Topoisomerase
+        (800, MolecularFunction),       // Toxin
+        (802, Domain),                  // TPR repeat
+        (804, BiologicalProcess),       // Transcription
+        (805, BiologicalProcess),       // Transcription regulation
+        (807, MolecularFunction),       // Transducer
+        (808, MolecularFunction),       // Transferase
+        (810, BiologicalProcess),       // Translation regulation
+        (811, BiologicalProcess),       // Translocation
+        (812, Domain),                  // Transmembrane
+        (813, BiologicalProcess),       // Transport
+        (819, BiologicalProcess),       // tRNA processing
+        (829, MolecularFunction),       // Tyrosine-protein kinase
+        (832, Ptm),                     // Ubl conjugation
+        (833, BiologicalProcess),       // Ubl conjugation pathway
+        (843, BiologicalProcess),       // Virulence
+        (853, Domain),                  // WD repeat
+        (858, BiologicalProcess),       // Xylan degradation
+        (862, Ligand),                  // Zinc
+        (863, Domain),                  // Zinc-finger
+        (865, Ptm),                     // Zymogen
+        (872, MolecularFunction),       // Ion channel impairing toxin
+        (873, Ptm),                     // Pyrrolidone carboxylic acid
+        (877, CodingSequenceDiversity), // Alternative promoter usage
+        (887, Disease),                 // Epilepsy
+        (903, TechnicalTerm),           // Direct protein sequencing
+        (904, MolecularFunction),       // Protein phosphatase
+        (907, Disease),                 // Parkinson disease
+        (915, Ligand),                  // Sodium
+        (926, CellularComponent),       // Vacuole
+        (929, BiologicalProcess),       // Antimicrobial
+        (934, CellularComponent),       // Plastid
+        (945, BiologicalProcess),       // Host-virus interaction
+        (946, CellularComponent),       // Virion
+        (949, Ligand),                  // S-adenosyl-L-methionine
+        (961, BiologicalProcess),       // Cell wall biogenesis/degradation
+        (963, CellularComponent),       // Cytoplasm
+        (964, CellularComponent),       // Secreted
+        (965, CellularComponent),       // Cell junction
+        (966, CellularComponent),       // Cell projection
+        (967, CellularComponent),       // Endosome
+        (968, CellularComponent),       // Cytoplasmic vesicle
+        (969, CellularComponent),       // Cilium
+        (991, Disease),                 // Intellectual disability
+        (998, CellularComponent),       // Cell outer membrane
+        (999, CellularComponent),       // Mitochondrion inner membrane
+        (1000, CellularComponent),      // Mitochondrion outer membrane
+        (1003, CellularComponent),      // Cell membrane
+        (1015, Ptm),                    // Disulfide bond
+        (1017, Ptm),                    // Isopeptide bond
+        (1035, CellularComponent),      // Host cytoplasm
+        (1038, CellularComponent),      // Host endoplasmic reticulum
+        (1043, CellularComponent),      // Host membrane
+        (1048, CellularComponent),      // Host nucleus
+        (1064, BiologicalProcess),      // Adaptive immunity
+        (1133, Domain),                 // Transmembrane helix
+        (1185, TechnicalTerm),          // Reference proteome
+        (1199, MolecularFunction),      // Hemostasis impairing toxin
+        (1201, MolecularFunction),      // Platelet aggregation inhibiting toxin
+        (1203, MolecularFunction),      // Blood coagulation cascade inhibiting toxin
+        (1213, MolecularFunction),      // G-protein coupled receptor impairing toxin
+        (1214, MolecularFunction),      // G-protein coupled acetylcholine receptor impairing toxin
+        (1217, MolecularFunction),      // Cell adhesion impairing toxin
+        (1218, MolecularFunction),      // Voltage-gated calcium channel impairing toxin
+        (1277, BiologicalProcess),      // Toxin-antitoxin system
+    ]
+};
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn keyword(id: &str) -> Keyword {
+        Keyword {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn hash() {
+        let mut keywords = std::collections::HashSet::new();
+        keywords.insert(keyword("KW-0732"));
+        keywords.insert(keyword("KW-0732"));
+        assert_eq!(keywords.len(), 1);
+
+        let mut kw = keyword("KW-0732");
+        kw.evidence.push(1);
+        keywords.insert(kw);
+        assert_eq!(keywords.len(), 2);
+    }
+
+    #[test]
+    fn categories_sorted() {
+        assert!(CATEGORIES.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn category() {
+        let kw = keyword("KW-0732");
+        assert_eq!(kw.category(), Some(KeywordCategory::Domain));
+        let kw = keyword("KW-0472");
+        assert_eq!(kw.category(), Some(KeywordCategory::CellularComponent));
+        let kw = keyword("KW-9992");
+        assert_eq!(kw.category(), Some(KeywordCategory::MolecularFunction));
+    }
+
+    #[test]
+    fn category_all() {
+        use self::KeywordCategory::*;
+        let samples = [
+            ("KW-0002", TechnicalTerm),
+            ("KW-0582", TechnicalTerm),
+            ("KW-0597", Ptm),
+            ("KW-0488", Ptm),
+            ("KW-0010", MolecularFunction),
+            ("KW-0238", MolecularFunction),
+            ("KW-0106", Ligand),
+            ("KW-0862", Ligand),
+            ("KW-0863", Domain),
+            ("KW-0853", Domain),
+            ("KW-0225", Disease),
+            ("KW-0907", Disease),
+            ("KW-0244", DevelopmentalStage),
+            ("KW-0426", DevelopmentalStage),
+            ("KW-0025", CodingSequenceDiversity),
+            ("KW-0621", CodingSequenceDiversity),
+            ("KW-0539", CellularComponent),
+            ("KW-0496", CellularComponent),
+            ("KW-0805", BiologicalProcess),
+            ("KW-0053", BiologicalProcess),
+        ];
+        for (id, category) in samples {
+            assert_eq!(keyword(id).category(), Some(category), "{}", id);
+        }
+    }
+
+    #[test]
+    fn category_unknown() {
+        assert_eq!(keyword("KW-0000").category(), None);
+        assert_eq!(keyword("KW-12345").category(), None);
+        assert_eq!(keyword("GO:0005515").category(), None);
+        assert_eq!(keyword("").category(), None);
     }
 }
@@ -1,16 +1,35 @@
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 use super::db_reference::DbReference;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Describes a ligand.
 pub struct Ligand {
     pub name: ShortString,
@@ -30,11 +49,13 @@ impl Ligand {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Ligand {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"ligand");
 
@@ -43,9 +64,9 @@ impl FromXml for Ligand {
         let mut label: Option<ShortString> = None;
         let mut note: Option<ShortString> = None;
         let mut optname: Option<ShortString> = None;
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"dbReference" => {
-                let dbref = DbReference::from_xml(&e, reader, buffer)?;
+                let dbref = DbReference::from_xml(&e, reader, buffer, options)?;
                 if db_reference.replace(dbref).is_some() {
                     return Err(Error::DuplicateElement("dbReference", "ligand"));
                 }
@@ -80,3 +101,22 @@ impl FromXml for Ligand {
         })
     }
 }
+
+#[cfg(feature = "parser")]
+impl ToXml for Ligand {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        write_element(writer, BytesStart::new("ligand"), |w| {
+            write_text_element(w, BytesStart::new("name"), &self.name)?;
+            if let Some(db_reference) = &self.db_reference {
+                db_reference.to_xml(w)?;
+            }
+            if let Some(label) = &self.label {
+                write_text_element(w, BytesStart::new("label"), label)?;
+            }
+            if let Some(note) = &self.note {
+                write_text_element(w, BytesStart::new("note"), note)?;
+            }
+            Ok(())
+        })
+    }
+}
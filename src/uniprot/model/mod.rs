@@ -14,57 +14,105 @@ pub mod protein;
 pub mod reference;
 
 mod db_reference;
+mod diff;
 mod evidence;
+mod evidence_text;
 mod feature;
 mod keyword;
 mod ligand;
 mod ligand_part;
 mod molecule;
 mod sequence;
+mod validation;
 
 pub use self::db_reference::DbReference;
+pub use self::db_reference::GoAspect;
+pub use self::db_reference::GoTerm;
+pub use self::diff::EntryDiff;
 pub use self::evidence::Evidence;
 pub use self::evidence::Source;
+pub use self::evidence_text::EvidenceText;
 pub use self::feature::Feature;
 pub use self::feature::FeatureType;
+pub use self::feature::Variant;
 pub use self::keyword::Keyword;
+pub use self::keyword::KeywordCategory;
 pub use self::ligand::Ligand;
 pub use self::ligand_part::LigandPart;
 pub use self::molecule::Molecule;
 pub use self::sequence::FragmentType;
 pub use self::sequence::Sequence;
+pub use self::validation::ValidationWarning;
+pub use crate::common::accession::Accession;
 pub use crate::common::date::Date;
+pub use crate::common::date::DatePrecision;
 pub use crate::common::property::Property;
 
+use core::iter::FromIterator;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::str::FromStr;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
 use std::collections::HashSet;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::iter::FromIterator;
-use std::ops::Deref;
-use std::ops::DerefMut;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
+use crate::common::sequence::format_fasta;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
+#[cfg(feature = "parser")]
 use crate::parser::UniprotDatabase;
 
 use self::comment::Comment;
+use self::comment::Isoform;
 use self::gene::Gene;
 use self::gene_location::GeneLocation;
+use self::gene_location::LocationType;
 use self::organism::Organism;
 use self::protein::Protein;
 use self::protein::ProteinExistence;
 use self::reference::Reference;
+use self::reference::SourceType;
 
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A UniProtKB entry.
+///
+/// The `organism`, `protein_existence` and `sequence` elements are required
+/// by the schema, but may be missing from partial entries, such as the ones
+/// returned by the UniProt REST API when only some fields are requested:
+/// use [`Entry::validate`] to check an entry is complete.
+///
+/// [`Entry::validate`]: #method.validate
 pub struct Entry {
     // attributes
     pub dataset: Dataset,
@@ -76,17 +124,17 @@ pub struct Entry {
     pub names: Vec<ShortString>,      // minOccurs = 1
     pub protein: Protein,
     pub genes: Vec<Gene>,
-    pub organism: Organism,
+    pub organism: Option<Organism>, // minOccurs = 1
     pub organism_hosts: Vec<Organism>,
     pub gene_location: Vec<GeneLocation>,
     pub references: Vec<Reference>, // minOccurs = 1
     pub comments: Vec<Comment>,     // nillable
     pub db_references: Vec<DbReference>,
-    pub protein_existence: ProteinExistence,
+    pub protein_existence: Option<ProteinExistence>, // minOccurs = 1
     pub keywords: Vec<Keyword>,
     pub features: Vec<Feature>,
     pub evidences: Vec<Evidence>,
-    pub sequence: Sequence,
+    pub sequence: Option<Sequence>, // minOccurs = 1
 }
 
 impl Entry {
@@ -113,14 +161,606 @@ impl Entry {
             evidences: Default::default(),
         }
     }
+
+    /// Get the primary accession of the entry, if any.
+    ///
+    /// The primary accession is the first `accession` element of the entry,
+    /// the following ones being secondary accessions.
+    pub fn primary_accession(&self) -> Option<&str> {
+        self.accessions.first().map(|acc| acc.as_str())
+    }
+
+    /// Get the full recommended name of the protein, if any.
+    ///
+    /// TrEMBL entries usually have no recommended name: in that case, the
+    /// full name of the first submitted name is returned instead.
+    pub fn recommended_name(&self) -> Option<&str> {
+        self.protein
+            .name
+            .recommended
+            .as_ref()
+            .or_else(|| self.protein.name.submitted.first())
+            .map(|name| name.full.as_str())
+    }
+
+    /// Get an iterator over the primary names of the genes of the entry.
+    pub fn gene_names(&self) -> impl Iterator<Item = &str> {
+        self.genes
+            .iter()
+            .flat_map(|gene| gene.names.iter())
+            .filter(|name| name.ty == gene::NameType::Primary)
+            .map(|name| name.value.as_str())
+    }
+
+    /// Check whether the gene of the entry is located in an organelle.
+    ///
+    /// Plasmid-borne genes are not considered organellar, and entries
+    /// without any gene location are assumed to be nuclear.
+    pub fn is_organellar(&self) -> bool {
+        self.gene_location.iter().any(|loc| loc.ty.is_organelle())
+    }
+
+    /// Get the types of the organelles the gene of the entry is located in.
+    ///
+    /// Plasmid locations are skipped, and each type is only returned once,
+    /// in the order of the gene locations.
+    pub fn organelle_types(&self) -> Vec<LocationType> {
+        let mut types = Vec::new();
+        for loc in &self.gene_location {
+            if loc.ty.is_organelle() && !types.contains(&loc.ty) {
+                types.push(loc.ty);
+            }
+        }
+        types
+    }
+
+    /// Check whether the entry has the keyword with the given identifier.
+    ///
+    /// The identifier is matched exactly, as spelled by UniProt
+    /// (e.g. `KW-0472`).
+    pub fn has_keyword(&self, id: &str) -> bool {
+        self.keywords.iter().any(|kw| kw.id == id)
+    }
+
+    /// Get the values of the keywords of the entry.
+    pub fn keyword_values(&self) -> Vec<&str> {
+        self.keywords.iter().map(|kw| kw.value.as_str()).collect()
+    }
+
+    /// Check whether the entry is likely a membrane protein.
+    ///
+    /// An entry is considered a membrane protein if it has one of the
+    /// `Membrane`, `Transmembrane`, `Transmembrane helix` or `Transmembrane
+    /// beta strand` keywords, or a transmembrane or intramembrane region
+    /// feature, so that entries annotated with membrane features but no
+    /// keyword are still detected.
+    pub fn is_membrane_protein(&self) -> bool {
+        const KEYWORDS: [&str; 4] = ["KW-0472", "KW-0812", "KW-1133", "KW-1134"];
+        KEYWORDS.iter().any(|id| self.has_keyword(id))
+            || self.features.iter().any(|f| {
+                matches!(
+                    f.ty,
+                    FeatureType::TransmembraneRegion | FeatureType::IntramembraneRegion
+                )
+            })
+    }
+
+    /// Get a map of the evidences of the entry, indexed by key.
+    #[cfg(feature = "std")]
+    pub fn evidence_map(&self) -> HashMap<usize, &Evidence> {
+        self.evidences.iter().map(|ev| (ev.key, ev)).collect()
+    }
+
+    /// Get a map of the references of the entry, indexed by key.
+    ///
+    /// If several references share the same key, which only happens in
+    /// malformed entries, the first one is kept.
+    #[cfg(feature = "std")]
+    pub fn reference_map(&self) -> HashMap<usize, &Reference> {
+        let mut map = HashMap::with_capacity(self.references.len());
+        for reference in &self.references {
+            map.entry(reference.key).or_insert(reference);
+        }
+        map
+    }
+
+    /// Get the reference of the entry with the given key, if any.
+    ///
+    /// If several references share the same key, the first one is returned.
+    pub fn reference_by_key(&self, key: usize) -> Option<&Reference> {
+        self.references.iter().find(|r| r.key == key)
+    }
+
+    /// Get the evidences of the entry corresponding to the given keys.
+    ///
+    /// Keys that do not reference any evidence of the entry are skipped.
+    pub fn resolve_evidences(&self, keys: &[usize]) -> Vec<&Evidence> {
+        keys.iter()
+            .filter_map(|key| self.evidences.iter().find(|ev| ev.key == *key))
+            .collect()
+    }
+
+    /// Get an iterator over the cross-references to the given database.
+    ///
+    /// The database name is matched exactly, as spelled by UniProt
+    /// (e.g. `PDB`, `Pfam` or `EC`).
+    pub fn cross_references<'a>(
+        &'a self,
+        database: &'a str,
+    ) -> impl Iterator<Item = &'a DbReference> + 'a {
+        self.db_references
+            .iter()
+            .filter(move |dbref| dbref.ty == database)
+    }
+
+    /// Get the identifiers of the PDB structures of the protein.
+    pub fn pdb_ids(&self) -> Vec<&str> {
+        self.cross_references("PDB")
+            .map(|dbref| dbref.id.as_str())
+            .collect()
+    }
+
+    /// Check whether the entry sequence is a fragment.
+    ///
+    /// Entries without a sequence are not considered fragments.
+    pub fn is_fragment(&self) -> bool {
+        self.sequence.as_ref().is_some_and(Sequence::is_fragment)
+    }
+
+    /// Get the features of the entry covering the given 1-based position.
+    pub fn features_at(&self, pos: usize) -> Vec<&Feature> {
+        self.features.iter().filter(|f| f.contains(pos)).collect()
+    }
+
+    /// Get the reference cited by the given feature, if any.
+    ///
+    /// Returns `None` if the feature cites no reference, or if its
+    /// reference key does not match any reference of the entry.
+    pub fn feature_source(&self, feature: &Feature) -> Option<&Reference> {
+        let key = feature.reference_key()?;
+        self.references.iter().find(|r| r.key == key)
+    }
+
+    /// Get the residues of the entry sequence spanned by the given feature.
+    ///
+    /// Returns `None` if the entry has no sequence, if the feature location
+    /// has an unknown endpoint, or if it lies outside of the sequence.
+    pub fn feature_sequence(&self, feature: &Feature) -> Option<&str> {
+        let (begin, end) = feature.location.bounds()?;
+        self.sequence.as_ref()?.subseq(begin..=end)
+    }
+
+    /// Get the sequence sources of all the references of the entry.
+    ///
+    /// Sources are returned in the order of the references, each reference
+    /// possibly contributing sources of different types.
+    pub fn sources(&self) -> Vec<&reference::Source> {
+        self.references
+            .iter()
+            .flat_map(|r| r.sources.iter())
+            .collect()
+    }
+
+    /// Get the names of the strains cited as sequence sources of the entry.
+    pub fn strains(&self) -> Vec<&str> {
+        self.references
+            .iter()
+            .flat_map(|r| r.sources.iter())
+            .filter(|s| s.ty == SourceType::Strain)
+            .map(|s| s.value.as_str())
+            .collect()
+    }
+
+    /// Get the annotation changes from this entry to another one.
+    ///
+    /// Comments, features, keywords and cross-references are compared
+    /// irrespective of their order, so that reordered elements are not
+    /// reported as changes.
+    pub fn diff<'a>(&'a self, other: &'a Entry) -> EntryDiff<'a> {
+        EntryDiff::new(self, other)
+    }
+
+    /// Get the features describing the sequence of the given isoform.
+    ///
+    /// The `ref` of the isoform sequence lists the identifiers of the
+    /// splice variant features applied to the displayed sequence. Isoforms
+    /// without a `ref`, such as the displayed isoform, have no features.
+    pub fn isoform_features(&self, isoform: &Isoform) -> Vec<&Feature> {
+        let ids = match &isoform.sequence.reference {
+            Some(reference) => reference.split_whitespace().collect::<Vec<_>>(),
+            None => return Vec::new(),
+        };
+        self.features
+            .iter()
+            .filter(|f| f.id.as_ref().is_some_and(|id| ids.contains(&id.as_str())))
+            .collect()
+    }
+
+    /// Get the EC numbers of the entry.
+    ///
+    /// EC numbers are collected from all the names of the protein, including
+    /// those of its domains and components, and then from the `EC`
+    /// cross-references. Each EC number is only returned once.
+    pub fn ec_numbers(&self) -> Vec<&str> {
+        let mut ec_numbers = self.protein.ec_numbers();
+        for dbref in self.cross_references("EC") {
+            if !ec_numbers.contains(&dbref.id.as_str()) {
+                ec_numbers.push(dbref.id.as_str());
+            }
+        }
+        ec_numbers
+    }
+
+    /// Get the NCBI Taxonomy identifiers of the hosts of the organism.
+    ///
+    /// Hosts without an NCBI Taxonomy cross-reference are skipped.
+    pub fn host_taxon_ids(&self) -> Vec<u32> {
+        self.organism_hosts
+            .iter()
+            .filter_map(Organism::taxon_id)
+            .collect()
+    }
+
+    /// Compute a heuristic annotation score for the entry, from 1 to 5.
+    ///
+    /// This is only an approximation of the score computed by UniProt,
+    /// which is not available in the XML files. Points are given for a
+    /// reviewed entry, for a protein existence at protein or transcript
+    /// level, for the number of experimental evidences, and for the number
+    /// of comments, features and keywords. An unreviewed entry without any
+    /// annotation scores 1.
+    pub fn annotation_score(&self) -> u8 {
+        let mut score = 1;
+        if matches!(self.dataset, Dataset::SwissProt) {
+            score += 1;
+        }
+        score += match self.protein_existence.as_ref().map(|pe| pe.rank()) {
+            Some(1) => 2,
+            Some(2) => 1,
+            _ => 0,
+        };
+        score += match self
+            .evidences
+            .iter()
+            .filter(|e| e.is_experimental())
+            .count()
+        {
+            0 => 0,
+            1..=4 => 1,
+            _ => 2,
+        };
+        score += match self.comments.len() + self.features.len() + self.keywords.len() {
+            0..=9 => 0,
+            10..=29 => 1,
+            _ => 2,
+        };
+        score.min(5)
+    }
+
+    /// Check the entry against the constraints of the UniProt XML schema.
+    ///
+    /// The parser is lenient and accepts entries with missing required
+    /// elements, so this method can be used to detect incomplete entries:
+    /// it checks that the entry has at least one accession, name and
+    /// reference, a non-empty sequence, and a non-zero version.
+    pub fn validate(&self) -> Result<(), Vec<ValidationWarning>> {
+        let mut warnings = Vec::new();
+        if self.version == 0 {
+            warnings.push(ValidationWarning::InvalidValue("version", "entry"));
+        }
+        if self.accessions.is_empty() {
+            warnings.push(ValidationWarning::MissingElement("accession", "entry"));
+        }
+        if self.names.is_empty() {
+            warnings.push(ValidationWarning::MissingElement("name", "entry"));
+        }
+        if self.references.is_empty() {
+            warnings.push(ValidationWarning::MissingElement("reference", "entry"));
+        }
+        if self.organism.is_none() {
+            warnings.push(ValidationWarning::MissingElement("organism", "entry"));
+        }
+        if self.protein_existence.is_none() {
+            warnings.push(ValidationWarning::MissingElement(
+                "proteinExistence",
+                "entry",
+            ));
+        }
+        match &self.sequence {
+            None => warnings.push(ValidationWarning::MissingElement("sequence", "entry")),
+            Some(s) if s.value.is_empty() => {
+                warnings.push(ValidationWarning::EmptyElement("sequence", "entry"))
+            }
+            Some(_) => (),
+        }
+
+        if warnings.is_empty() {
+            Ok(())
+        } else {
+            Err(warnings)
+        }
+    }
+
+    /// Format the entry sequence as a FASTA record.
+    ///
+    /// The header follows the UniProtKB layout (`>db|accession|name
+    /// description OS=... OX=... GN=... PE=... SV=...`), where the fields
+    /// missing from the entry, such as the organism taxon identifier, are
+    /// omitted.
+    pub fn to_fasta(&self) -> String {
+        let mut header = String::from(match self.dataset {
+            Dataset::SwissProt => "sp",
+            Dataset::TrEmbl => "tr",
+        });
+        header.push('|');
+        header.push_str(self.primary_accession().unwrap_or_default());
+        header.push('|');
+        header.push_str(self.names.first().map(|n| n.as_str()).unwrap_or_default());
+
+        if let Some(name) = self.recommended_name() {
+            header.push(' ');
+            header.push_str(name);
+        }
+        if let Some(organism) = &self.organism {
+            if let Some(name) = organism.scientific_name() {
+                header.push_str(" OS=");
+                header.push_str(name);
+            }
+            if let Some(taxon_id) = organism.taxon_id() {
+                header.push_str(&format!(" OX={}", taxon_id));
+            }
+        }
+        let gene_name = [
+            gene::NameType::Primary,
+            gene::NameType::OrderedLocus,
+            gene::NameType::Orf,
+        ]
+        .iter()
+        .find_map(|ty| {
+            self.genes
+                .first()
+                .and_then(|gene| gene.names.iter().find(|name| name.ty == *ty))
+        });
+        if let Some(name) = gene_name {
+            header.push_str(" GN=");
+            header.push_str(&name.value);
+        }
+        if let Some(protein_existence) = &self.protein_existence {
+            header.push_str(&format!(" PE={}", protein_existence.rank()));
+        }
+        match &self.sequence {
+            Some(sequence) => {
+                header.push_str(&format!(" SV={}", sequence.version));
+                sequence.to_fasta(&header)
+            }
+            None => format_fasta(&header, ""),
+        }
+    }
+
+    /// Format the entry as an approximate UniProtKB flat-file record.
+    ///
+    /// Only the `ID`, `AC`, `DE`, `GN`, `OS`, `OC`, `OX`, `OH`, `PE` and
+    /// `SQ` lines are written, wrapped at 80 columns like in the flat-file
+    /// distribution. Evidences are omitted. The record can be read back
+    /// with [`parse_dat`](crate::uniprot::parse_dat).
+    pub fn to_flat_string(&self) -> String {
+        let mut flat = String::new();
+
+        let length = self.sequence.as_ref().map(|s| s.length).unwrap_or_default();
+        let status = match self.dataset {
+            Dataset::SwissProt => "Reviewed;",
+            Dataset::TrEmbl => "Unreviewed;",
+        };
+        let name = self.names.first().map(|n| n.as_str()).unwrap_or_default();
+        flat.push_str(&format!(
+            "ID   {:<24}{:<11}{:>10} AA.\n",
+            name, status, length
+        ));
+        push_wrapped(
+            &mut flat,
+            "AC",
+            self.accessions.iter().map(|a| format!("{};", a)),
+        );
+
+        let mut description = Vec::new();
+        push_nomenclature(&mut description, &self.protein.name, "");
+        for domain in &self.protein.domains {
+            description.push(String::from("Includes:"));
+            push_nomenclature(&mut description, domain, "  ");
+        }
+        for component in &self.protein.components {
+            description.push(String::from("Contains:"));
+            push_nomenclature(&mut description, component, "  ");
+        }
+        if let Some(sequence) = &self.sequence {
+            let mut flags = Vec::new();
+            if sequence.precursor == Some(true) {
+                flags.push("Precursor;");
+            }
+            match sequence.fragment {
+                Some(FragmentType::Single) => flags.push("Fragment;"),
+                Some(FragmentType::Multiple) => flags.push("Fragments;"),
+                None => (),
+            }
+            if !flags.is_empty() {
+                description.push(format!("Flags: {}", flags.join(" ")));
+            }
+        }
+        for line in description {
+            flat.push_str("DE   ");
+            flat.push_str(&line);
+            flat.push('\n');
+        }
+
+        for (i, gene) in self.genes.iter().enumerate() {
+            if i > 0 {
+                flat.push_str("GN   and\n");
+            }
+            let mut fields = Vec::new();
+            if let Some(name) = gene.primary() {
+                fields.push(format!("Name={};", name));
+            }
+            for (key, values) in [
+                ("Synonyms", gene.synonyms()),
+                ("OrderedLocusNames", gene.ordered_loci()),
+                ("ORFNames", gene.orf_names()),
+            ] {
+                if !values.is_empty() {
+                    fields.push(format!("{}={};", key, values.join(", ")));
+                }
+            }
+            let words = fields.iter().flat_map(|f| f.split(' ')).map(String::from);
+            push_wrapped(&mut flat, "GN", words);
+        }
+
+        if let Some(organism) = &self.organism {
+            let species = format!("{}.", format_organism_names(organism));
+            push_wrapped(&mut flat, "OS", species.split(' ').map(String::from));
+            for lineage in &organism.lineages {
+                let taxons = lineage.taxons.iter().enumerate().map(|(i, taxon)| {
+                    let sep = if i + 1 == lineage.taxons.len() {
+                        '.'
+                    } else {
+                        ';'
+                    };
+                    format!("{}{}", taxon, sep)
+                });
+                push_wrapped(&mut flat, "OC", taxons);
+            }
+            if let Some(taxon_id) = organism.taxon_id() {
+                flat.push_str(&format!("OX   NCBI_TaxID={};\n", taxon_id));
+            }
+        }
+        for host in &self.organism_hosts {
+            let mut text = String::new();
+            if let Some(taxon_id) = host.taxon_id() {
+                text.push_str(&format!("NCBI_TaxID={}; ", taxon_id));
+            }
+            text.push_str(&format_organism_names(host));
+            text.push('.');
+            push_wrapped(&mut flat, "OH", text.split(' ').map(String::from));
+        }
+
+        if let Some(protein_existence) = &self.protein_existence {
+            let level = protein_existence.as_str();
+            flat.push_str(&format!(
+                "PE   {}: {}{};\n",
+                protein_existence.rank(),
+                level[..1].to_uppercase(),
+                &level[1..]
+            ));
+        }
+
+        if let Some(sequence) = &self.sequence {
+            flat.push_str(&format!(
+                "SQ   SEQUENCE {:>5} AA; {:>6} MW;  {:016X} CRC64;\n",
+                sequence.length, sequence.mass, sequence.checksum
+            ));
+            let residues = sequence.value.as_bytes();
+            for line in residues.chunks(60) {
+                flat.push_str("    ");
+                for block in line.chunks(10) {
+                    flat.push(' ');
+                    flat.push_str(&String::from_utf8_lossy(block));
+                }
+                flat.push('\n');
+            }
+        }
+
+        flat.push_str("//\n");
+        flat
+    }
+}
+
+/// Append the words of a line to a flat-file record, wrapping at 80 columns.
+fn push_wrapped<I: IntoIterator<Item = String>>(flat: &mut String, code: &str, words: I) {
+    let mut line = String::new();
+    for word in words {
+        if !line.is_empty() && line.len() + word.len() + 1 > 75 {
+            flat.push_str(&format!("{}   {}\n", code, line));
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&word);
+    }
+    if !line.is_empty() {
+        flat.push_str(&format!("{}   {}\n", code, line));
+    }
+}
+
+/// Append the `DE` lines describing the names of a protein or a section.
+fn push_nomenclature(lines: &mut Vec<String>, nomenclature: &protein::Nomenclature, indent: &str) {
+    let names = nomenclature
+        .recommended
+        .iter()
+        .map(|name| ("RecName:", name))
+        .chain(
+            nomenclature
+                .alternative
+                .iter()
+                .map(|name| ("AltName:", name)),
+        )
+        .chain(nomenclature.submitted.iter().map(|name| ("SubName:", name)));
+    for (kind, name) in names {
+        lines.push(format!("{}{} Full={};", indent, kind, name.full));
+        let pad = " ".repeat(indent.len() + kind.len() + 1);
+        for short in &name.short {
+            lines.push(format!("{}Short={};", pad, short));
+        }
+        for ec_number in &name.ec_number {
+            lines.push(format!("{}EC={};", pad, ec_number));
+        }
+    }
+}
+
+/// Format the names of an organism as `scientific (common) (synonym)`.
+fn format_organism_names(organism: &Organism) -> String {
+    let mut names = String::from(organism.scientific_name().unwrap_or_default());
+    let others = organism
+        .common_name()
+        .into_iter()
+        .chain(organism.synonyms());
+    for name in others {
+        if !names.is_empty() {
+            names.push(' ');
+        }
+        names.push('(');
+        names.push_str(name);
+        names.push(')');
+    }
+    names
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Entry {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
+        let mut entry = Entry::new(Dataset::SwissProt);
+        entry.read_xml(event, reader, buffer, options)?;
+        Ok(entry)
+    }
+}
+
+#[cfg(feature = "parser")]
+impl Entry {
+    /// Parse an entry in place, reusing the allocations of the current one.
+    ///
+    /// The fields of `self` are cleared first, but the vectors keep their
+    /// capacity, so that parsing many entries into the same value avoids
+    /// reallocating them for every entry.
+    pub(crate) fn read_xml<B: BufRead>(
+        &mut self,
+        event: &BytesStart,
+        reader: &mut Reader<B>,
+        buffer: &mut Vec<u8>,
+        options: &ParseOptions,
+    ) -> Result<(), Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"entry");
 
         let dataset = match extract_attribute(event, "dataset")?
@@ -138,12 +778,13 @@ impl FromXml for Entry {
                 ))
             }
         };
-        let mut entry = Entry::new(dataset);
+        let entry = self;
+        entry.clear(dataset);
 
         entry.modified = decode_attribute(event, reader, "modified", "entry")?;
         entry.created = decode_attribute(event, reader, "created", "entry")?;
         entry.version = decode_attribute(event, reader, "version", "entry")?;
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"accession" => {
                 entry.accessions.push(parse_text!(e, reader, buffer));
             },
@@ -151,47 +792,127 @@ impl FromXml for Entry {
                 entry.names.push(parse_text!(e, reader, buffer));
             },
             e @ b"protein" => {
-                entry.protein = FromXml::from_xml(&e, reader, buffer)?;
+                entry.protein = FromXml::from_xml(&e, reader, buffer, options)?;
             },
             e @ b"gene" => {
-                entry.genes.push(FromXml::from_xml(&e, reader, buffer)?);
+                entry.genes.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"organism" => {
-                entry.organism = FromXml::from_xml(&e, reader, buffer)?;
+                entry.organism = FromXml::from_xml(&e, reader, buffer, options).map(Some)?;
             },
             e @ b"organismHost" => {
-                entry.organism_hosts.push(FromXml::from_xml(&e, reader, buffer)?);
+                entry.organism_hosts.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"reference" => {
-                entry.references.push(FromXml::from_xml(&e, reader, buffer)?);
+                entry.references.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"comment" => {
-                entry.comments.push(FromXml::from_xml(&e, reader, buffer)?);
+                entry.comments.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"dbReference" => {
-                entry.db_references.push(FromXml::from_xml(&e, reader, buffer)?);
+                entry.db_references.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"proteinExistence" => {
-                entry.protein_existence = FromXml::from_xml(&e, reader, buffer)?;
+                entry.protein_existence = FromXml::from_xml(&e, reader, buffer, options).map(Some)?;
             },
             e @ b"keyword" => {
-                entry.keywords.push(FromXml::from_xml(&e, reader, buffer)?);
+                entry.keywords.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"feature" => {
-                entry.features.push(FromXml::from_xml(&e, reader, buffer)?);
+                entry.features.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"evidence" => {
-                entry.evidences.push(FromXml::from_xml(&e, reader, buffer)?);
+                entry.evidences.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"sequence" => {
-                entry.sequence = Sequence::from_xml(&e, reader, buffer)?;
+                entry.sequence = Sequence::from_xml(&e, reader, buffer, options).map(Some)?;
             },
             e @ b"geneLocation" => {
-                entry.gene_location.push(FromXml::from_xml(&e, reader, buffer)?);
+                entry.gene_location.push(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
-        Ok(entry)
+        Ok(())
+    }
+
+    /// Reset all the fields of the entry, keeping the capacity of its vectors.
+    fn clear(&mut self, dataset: Dataset) {
+        self.dataset = dataset;
+        self.created = Default::default();
+        self.modified = Default::default();
+        self.version = 1;
+        self.accessions.clear();
+        self.names.clear();
+        self.protein = Default::default();
+        self.genes.clear();
+        self.organism = None;
+        self.organism_hosts.clear();
+        self.gene_location.clear();
+        self.references.clear();
+        self.comments.clear();
+        self.db_references.clear();
+        self.protein_existence = None;
+        self.keywords.clear();
+        self.features.clear();
+        self.evidences.clear();
+        self.sequence = None;
+    }
+}
+
+#[cfg(feature = "parser")]
+impl ToXml for Entry {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("entry");
+        elem.push_attribute(("dataset", self.dataset.as_str()));
+        elem.push_attribute(("created", self.created.to_string().as_str()));
+        elem.push_attribute(("modified", self.modified.to_string().as_str()));
+        elem.push_attribute(("version", self.version.to_string().as_str()));
+        write_element(writer, elem, |w| {
+            for accession in &self.accessions {
+                write_text_element(w, BytesStart::new("accession"), accession)?;
+            }
+            for name in &self.names {
+                write_text_element(w, BytesStart::new("name"), name)?;
+            }
+            self.protein.to_xml(w)?;
+            for gene in &self.genes {
+                gene.to_xml(w)?;
+            }
+            if let Some(organism) = &self.organism {
+                organism.to_xml(w)?;
+            }
+            for host in &self.organism_hosts {
+                host.write_xml(w, "organismHost")?;
+            }
+            for location in &self.gene_location {
+                location.to_xml(w)?;
+            }
+            for reference in &self.references {
+                reference.to_xml(w)?;
+            }
+            for comment in &self.comments {
+                comment.to_xml(w)?;
+            }
+            for db_reference in &self.db_references {
+                db_reference.to_xml(w)?;
+            }
+            if let Some(protein_existence) = &self.protein_existence {
+                protein_existence.to_xml(w)?;
+            }
+            for keyword in &self.keywords {
+                keyword.to_xml(w)?;
+            }
+            for feature in &self.features {
+                feature.to_xml(w)?;
+            }
+            for evidence in &self.evidences {
+                evidence.to_xml(w)?;
+            }
+            if let Some(sequence) = &self.sequence {
+                sequence.to_xml(w)?;
+            }
+            Ok(())
+        })
     }
 }
 
@@ -199,6 +920,7 @@ impl FromXml for Entry {
 
 /// A UniProtKB database.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UniProt {
     entries: Vec<Entry>,
 }
@@ -241,6 +963,16 @@ impl From<UniProt> for Vec<Entry> {
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<'a> rayon::iter::IntoParallelIterator for &'a UniProt {
+    type Iter = rayon::slice::Iter<'a, Entry>;
+    type Item = &'a Entry;
+    fn into_par_iter(self) -> Self::Iter {
+        rayon::iter::IntoParallelIterator::into_par_iter(&self.entries)
+    }
+}
+
+#[cfg(feature = "parser")]
 impl UniprotDatabase for UniProt {
     type Entry = Entry;
     const ROOTS: &'static [&'static [u8]] = &[b"uniprot"];
@@ -249,8 +981,21 @@ impl UniprotDatabase for UniProt {
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The differents datasets an `Entry` can be part of.
 pub enum Dataset {
+    #[cfg_attr(feature = "serde", serde(rename = "Swiss-Prot"))]
     SwissProt,
+    #[cfg_attr(feature = "serde", serde(rename = "TrEMBL"))]
     TrEmbl,
 }
+
+impl Dataset {
+    /// Get the string representation of the dataset.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Dataset::SwissProt => "Swiss-Prot",
+            Dataset::TrEmbl => "TrEMBL",
+        }
+    }
+}
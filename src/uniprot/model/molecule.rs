@@ -1,30 +1,52 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_empty_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Describes a molecule by name or unique identifier.
 pub enum Molecule {
     Id(ShortString),
     Name(ShortString),
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Molecule {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"molecule");
 
-        match extract_attribute(event, "type")? {
+        match extract_attribute(event, "id")? {
             None => Ok(Molecule::Name(parse_text!(event, reader, buffer))),
             Some(attr) => {
                 reader.read_to_end_into(event.name(), buffer)?;
@@ -36,3 +58,17 @@ impl FromXml for Molecule {
         }
     }
 }
+
+#[cfg(feature = "parser")]
+impl ToXml for Molecule {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        match self {
+            Molecule::Id(id) => {
+                let mut elem = BytesStart::new("molecule");
+                elem.push_attribute(("id", id.as_ref()));
+                write_empty_element(writer, elem)
+            }
+            Molecule::Name(name) => write_text_element(writer, BytesStart::new("molecule"), name),
+        }
+    }
+}
@@ -1,20 +1,45 @@
+use core::convert::TryFrom;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
 use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::push_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 use super::db_reference::DbReference;
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Describes the source organism.
 pub struct Organism {
     pub names: Vec<Name>,
@@ -23,28 +48,116 @@ pub struct Organism {
     pub evidences: Vec<usize>,
 }
 
+impl Organism {
+    /// Get the NCBI Taxonomy identifier of the organism, if any.
+    ///
+    /// If the organism has several NCBI Taxonomy cross-references, the
+    /// identifier of the first one is returned.
+    pub fn taxon_id(&self) -> Option<u32> {
+        self.db_references
+            .iter()
+            .find(|db| db.ty == "NCBI Taxonomy")
+            .and_then(|db| db.id.parse().ok())
+    }
+
+    /// Get the first name of the organism with the given type, if any.
+    fn name(&self, ty: NameType) -> Option<&str> {
+        self.names
+            .iter()
+            .find(|name| name.ty == ty)
+            .map(|name| name.value.as_str())
+    }
+
+    /// Get the scientific name of the organism, if any.
+    pub fn scientific_name(&self) -> Option<&str> {
+        self.name(NameType::Scientific)
+    }
+
+    /// Get the common name of the organism, if any.
+    ///
+    /// Many organisms, such as most bacteria, only have a scientific name.
+    pub fn common_name(&self) -> Option<&str> {
+        self.name(NameType::Common)
+    }
+
+    /// Get the synonyms of the organism name.
+    pub fn synonyms(&self) -> Vec<&str> {
+        self.names
+            .iter()
+            .filter(|name| name.ty == NameType::Synonym)
+            .map(|name| name.value.as_str())
+            .collect()
+    }
+
+    /// Get the domain of the organism, from its first lineage.
+    fn domain(&self) -> Option<&str> {
+        self.lineages.first().and_then(Lineage::domain)
+    }
+
+    /// Check whether the organism belongs to the Bacteria domain.
+    ///
+    /// Organisms without a lineage are not considered bacterial.
+    pub fn is_bacterial(&self) -> bool {
+        self.domain() == Some("Bacteria")
+    }
+
+    /// Check whether the organism belongs to the Eukaryota domain.
+    ///
+    /// Organisms without a lineage are not considered eukaryotic.
+    pub fn is_eukaryotic(&self) -> bool {
+        self.domain() == Some("Eukaryota")
+    }
+
+    /// Write the organism as an XML element with the given tag name.
+    #[cfg(feature = "parser")]
+    pub(crate) fn write_xml<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        tag: &str,
+    ) -> Result<(), Error> {
+        let mut elem = BytesStart::new(tag);
+        push_evidences(&mut elem, &self.evidences);
+        write_element(writer, elem, |w| {
+            for name in &self.names {
+                name.to_xml(w)?;
+            }
+            for db_reference in &self.db_references {
+                db_reference.to_xml(w)?;
+            }
+            for lineage in &self.lineages {
+                lineage.to_xml(w)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Organism {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert!(
             event.local_name().as_ref() == b"organism"
                 || event.local_name().as_ref() == b"organismHost"
         );
 
-        let mut organism = Organism::default();
-        organism.evidences = get_evidences(reader, &event)?;
-        parse_inner! {event, reader, buffer,
+        let mut organism = Organism {
+            evidences: get_evidences(reader, event)?,
+            ..Default::default()
+        };
+        parse_inner! {event, reader, buffer, options,
             e @ b"name" => {
-                organism.names.push(FromXml::from_xml(&e, reader, buffer)?);
+                organism.names.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"dbReference" => {
-                organism.db_references.push(FromXml::from_xml(&e, reader, buffer)?);
+                organism.db_references.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"lineage" => {
-                organism.lineages.push(FromXml::from_xml(&e, reader, buffer)?);
+                organism.lineages.push(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
@@ -52,9 +165,17 @@ impl FromXml for Organism {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Organism {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        self.write_xml(writer, "organism")
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Name {
     pub value: ShortString,
     pub ty: NameType,
@@ -66,11 +187,13 @@ impl Name {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Name {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"name");
 
@@ -80,15 +203,43 @@ impl FromXml for Name {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Name {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("name");
+        elem.push_attribute(("type", self.ty.as_str()));
+        write_text_element(writer, elem, &self.value)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum NameType {
+    #[cfg_attr(feature = "serde", serde(rename = "common"))]
     Common,
+    #[cfg_attr(feature = "serde", serde(rename = "full"))]
     Full,
+    #[cfg_attr(feature = "serde", serde(rename = "scientific"))]
     Scientific,
+    #[cfg_attr(feature = "serde", serde(rename = "synonym"))]
     Synonym,
+    #[cfg_attr(feature = "serde", serde(rename = "abbreviation"))]
     Abbreviation,
 }
 
+impl NameType {
+    /// Get the string representation of the name type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NameType::Common => "common",
+            NameType::Full => "full",
+            NameType::Scientific => "scientific",
+            NameType::Synonym => "synonym",
+            NameType::Abbreviation => "abbreviation",
+        }
+    }
+}
+
 impl FromStr for NameType {
     type Err = InvalidValue;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -103,23 +254,45 @@ impl FromStr for NameType {
     }
 }
 
+impl TryFrom<&str> for NameType {
+    type Error = InvalidValue;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Lineage {
     pub taxons: Vec<ShortString>,
 }
 
+impl Lineage {
+    /// Get the first taxon of the lineage, e.g. `Bacteria` or `Viruses`.
+    pub fn domain(&self) -> Option<&str> {
+        self.taxons.first().map(|taxon| taxon.as_str())
+    }
+
+    /// Check whether the lineage contains the given taxon.
+    pub fn contains(&self, taxon: &str) -> bool {
+        self.taxons.iter().any(|t| t == taxon)
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Lineage {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"lineage");
 
         let mut lineage = Lineage::default();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"taxon" => {
                 lineage.taxons.push(parse_text!(e, reader, buffer));
             }
@@ -128,3 +301,15 @@ impl FromXml for Lineage {
         Ok(lineage)
     }
 }
+
+#[cfg(feature = "parser")]
+impl ToXml for Lineage {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        write_element(writer, BytesStart::new("lineage"), |w| {
+            for taxon in &self.taxons {
+                write_text_element(w, BytesStart::new("taxon"), taxon)?;
+            }
+            Ok(())
+        })
+    }
+}
@@ -1,18 +1,42 @@
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
 use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_empty_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Describes the names for the protein and parts thereof.
 pub struct Protein {
     pub name: Nomenclature,
@@ -20,30 +44,55 @@ pub struct Protein {
     pub components: Vec<Nomenclature>,
 }
 
+impl Protein {
+    /// Get the EC numbers of all the names of the protein.
+    ///
+    /// The names of the protein, of its domains and of its components are
+    /// all visited, and each EC number is only returned once.
+    pub fn ec_numbers(&self) -> Vec<&str> {
+        let mut ec_numbers = Vec::new();
+        let nomenclatures = core::iter::once(&self.name)
+            .chain(&self.domains)
+            .chain(&self.components);
+        for nomenclature in nomenclatures {
+            for name in nomenclature.names() {
+                for ec_number in &name.ec_number {
+                    if !ec_numbers.contains(&ec_number.as_str()) {
+                        ec_numbers.push(ec_number.as_str());
+                    }
+                }
+            }
+        }
+        ec_numbers
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Protein {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         let mut protein = Protein::default();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"recommendedName" => {
-                protein.name.recommended = FromXml::from_xml(&e, reader, buffer).map(Some)?;
+                protein.name.recommended = FromXml::from_xml(&e, reader, buffer, options).map(Some)?;
             },
             e @ b"alternativeName" => {
-                protein.name.alternative.push(FromXml::from_xml(&e, reader, buffer)?);
+                protein.name.alternative.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"submittedName" => {
-                protein.name.submitted.push(FromXml::from_xml(&e, reader, buffer)?);
+                protein.name.submitted.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"component" => {
                 // TODO: proper fix to avoid nested `component` in `component`
-                protein.components.push(Self::from_xml(&e, reader, buffer)?.name);
+                protein.components.push(Self::from_xml(&e, reader, buffer, options)?.name);
             },
             e @ b"domain" => {
                 // TODO: proper fix to avoid nested `domain` in `component`
-                protein.domains.push(Self::from_xml(&e, reader, buffer)?.name);
+                protein.domains.push(Self::from_xml(&e, reader, buffer, options)?.name);
             },
             e @ b"allergenName" => {
                 let value = parse_text!(e, reader, buffer);
@@ -72,7 +121,24 @@ impl FromXml for Protein {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Protein {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        write_element(writer, BytesStart::new("protein"), |w| {
+            self.name.write_xml(w)?;
+            for domain in &self.domains {
+                write_element(w, BytesStart::new("domain"), |w| domain.write_xml(w))?;
+            }
+            for component in &self.components {
+                write_element(w, BytesStart::new("component"), |w| component.write_xml(w))?;
+            }
+            Ok(())
+        })
+    }
+}
+
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The different names that can be attached to a single protein.
 pub struct Nomenclature {
     pub recommended: Option<Name>,
@@ -84,7 +150,45 @@ pub struct Nomenclature {
     pub inn: Vec<ShortString>,
 }
 
+impl Nomenclature {
+    /// Get an iterator over the recommended, alternative and submitted names.
+    pub fn names(&self) -> impl Iterator<Item = &Name> {
+        self.recommended
+            .iter()
+            .chain(&self.alternative)
+            .chain(&self.submitted)
+    }
+
+    /// Write the names as a sequence of XML elements.
+    #[cfg(feature = "parser")]
+    pub(crate) fn write_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        if let Some(name) = &self.recommended {
+            name.write_xml(writer, "recommendedName")?;
+        }
+        for name in &self.alternative {
+            name.write_xml(writer, "alternativeName")?;
+        }
+        for name in &self.submitted {
+            name.write_xml(writer, "submittedName")?;
+        }
+        if let Some(allergen) = &self.allergen {
+            write_text_element(writer, BytesStart::new("allergenName"), allergen)?;
+        }
+        if let Some(biotech) = &self.biotech {
+            write_text_element(writer, BytesStart::new("biotechName"), biotech)?;
+        }
+        for cd_antigen in &self.cd_antigen {
+            write_text_element(writer, BytesStart::new("cdAntigenName"), cd_antigen)?;
+        }
+        for inn in &self.inn {
+            write_text_element(writer, BytesStart::new("innName"), inn)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A single name in use for a protein.
 pub struct Name {
     pub full: ShortString,
@@ -92,15 +196,38 @@ pub struct Name {
     pub ec_number: Vec<ShortString>,
 }
 
+impl Name {
+    /// Write the name as an XML element with the given tag name.
+    #[cfg(feature = "parser")]
+    pub(crate) fn write_xml<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        tag: &str,
+    ) -> Result<(), Error> {
+        write_element(writer, BytesStart::new(tag), |w| {
+            write_text_element(w, BytesStart::new("fullName"), &self.full)?;
+            for short in &self.short {
+                write_text_element(w, BytesStart::new("shortName"), short)?;
+            }
+            for ec_number in &self.ec_number {
+                write_text_element(w, BytesStart::new("ecNumber"), ec_number)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Name {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         let mut group = Self::default();
 
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"fullName" => {
                 group.full = parse_text!(e, reader, buffer);
             },
@@ -116,19 +243,48 @@ impl FromXml for Name {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The evidence supporting the existence of a protein.
+///
+/// Variants are ordered by decreasing strength of evidence, so that
+/// stronger evidence compares as *lower*, following the numbering of the
+/// `PE` line of the flat-file format.
 pub enum ProteinExistence {
+    #[cfg_attr(feature = "serde", serde(rename = "evidence at protein level"))]
     ProteinLevelEvidence,
+    #[cfg_attr(feature = "serde", serde(rename = "evidence at transcript level"))]
     TranscriptLevelEvidence,
+    #[cfg_attr(feature = "serde", serde(rename = "inferred from homology"))]
     HomologyInferred,
+    #[cfg_attr(feature = "serde", serde(rename = "predicted"))]
     Predicted,
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "uncertain"))]
     Uncertain,
 }
 
-impl Default for ProteinExistence {
-    fn default() -> Self {
-        ProteinExistence::Uncertain
+impl ProteinExistence {
+    /// Get the string representation of the protein existence.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProteinExistence::ProteinLevelEvidence => "evidence at protein level",
+            ProteinExistence::TranscriptLevelEvidence => "evidence at transcript level",
+            ProteinExistence::HomologyInferred => "inferred from homology",
+            ProteinExistence::Predicted => "predicted",
+            ProteinExistence::Uncertain => "uncertain",
+        }
+    }
+
+    /// Get the numeric level of the protein existence, from 1 to 5.
+    pub fn rank(&self) -> u8 {
+        match self {
+            ProteinExistence::ProteinLevelEvidence => 1,
+            ProteinExistence::TranscriptLevelEvidence => 2,
+            ProteinExistence::HomologyInferred => 3,
+            ProteinExistence::Predicted => 4,
+            ProteinExistence::Uncertain => 5,
+        }
     }
 }
 
@@ -147,14 +303,79 @@ impl FromStr for ProteinExistence {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for ProteinExistence {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"proteinExistence");
         reader.read_to_end_into(event.name(), buffer)?;
         decode_attribute(event, reader, "type", "proteinExistence")
     }
 }
+
+#[cfg(feature = "parser")]
+impl ToXml for ProteinExistence {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("proteinExistence");
+        elem.push_attribute(("type", self.as_str()));
+        write_empty_element(writer, elem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn protein_existence_rank() {
+        use self::ProteinExistence::*;
+        let levels = [
+            ProteinLevelEvidence,
+            TranscriptLevelEvidence,
+            HomologyInferred,
+            Predicted,
+            Uncertain,
+        ];
+        for (i, pe) in levels.iter().enumerate() {
+            assert_eq!(pe.rank() as usize, i + 1);
+        }
+        for pair in levels.windows(2) {
+            assert!(pair[0] < pair[1]);
+        }
+        assert!(ProteinLevelEvidence <= TranscriptLevelEvidence);
+        assert!(Predicted > TranscriptLevelEvidence);
+    }
+
+    #[test]
+    fn ec_numbers() {
+        let name = |full: &str, ec_number: &[&str]| Name {
+            full: full.into(),
+            short: Vec::new(),
+            ec_number: ec_number.iter().map(|&ec| ec.into()).collect(),
+        };
+
+        let mut protein = Protein::default();
+        protein.name.recommended = Some(name("Bifunctional enzyme", &["2.7.7.7"]));
+        protein
+            .name
+            .alternative
+            .push(name("Polymerase", &["2.7.7.7"]));
+        protein.components.push(Nomenclature {
+            recommended: Some(name("Exonuclease", &["3.1.11.1"])),
+            ..Default::default()
+        });
+        protein.domains.push(Nomenclature {
+            submitted: vec![name("Kinase", &["2.7.11.1", "3.1.11.1"])],
+            ..Default::default()
+        });
+        assert_eq!(
+            protein.ec_numbers(),
+            vec!["2.7.7.7", "2.7.11.1", "3.1.11.1"]
+        );
+    }
+}
@@ -1,21 +1,52 @@
-use std::borrow::Cow;
+use alloc::borrow::Cow;
+use core::convert::TryFrom;
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::date::Date;
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
 use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::get_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::push_evidences;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_empty_element;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 use super::db_reference::DbReference;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A citation, also contain a summary of its content.
 pub struct Reference {
     pub key: usize,
@@ -35,13 +66,36 @@ impl Reference {
             sources: Default::default(),
         }
     }
+
+    /// Get the categories of the scope of the reference.
+    ///
+    /// Each scope is classified with [`ReferenceScope::from_scope`], and
+    /// scopes that are not recognized are skipped. Categories are returned
+    /// once, in the order they first appear.
+    ///
+    /// [`ReferenceScope::from_scope`]: ./enum.ReferenceScope.html#method.from_scope
+    pub fn scope_categories(&self) -> Vec<ReferenceScope> {
+        let mut categories = Vec::new();
+        for category in self
+            .scope
+            .iter()
+            .filter_map(|s| ReferenceScope::from_scope(s))
+        {
+            if !categories.contains(&category) {
+                categories.push(category);
+            }
+        }
+        categories
+    }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Reference {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"reference");
 
@@ -49,25 +103,27 @@ impl FromXml for Reference {
         let mut scope = Vec::new();
         let mut optcit = None;
 
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"scope" => {
                 scope.push(parse_text!(e, reader, buffer));
             },
             e @ b"citation" => {
-                let citation = FromXml::from_xml(&e, reader, buffer)?;
+                let citation = FromXml::from_xml(&e, reader, buffer, options)?;
                 if optcit.replace(citation).is_some() {
                     return Err(Error::DuplicateElement("citation", "reference"));
                 }
             },
             e @ b"source" => {
-                sources.extend(Vec::<Source>::from_xml(&e, reader, buffer)?);
+                sources.extend(Vec::<Source>::from_xml(&e, reader, buffer, options)?);
             }
         }
 
         let citation = optcit.ok_or(Error::MissingAttribute("citation", "reference"))?;
         let mut reference = Reference::new(citation, 0);
+        reference.scope = scope;
+        reference.sources = sources;
 
-        reference.evidences = get_evidences(reader, &event)?;
+        reference.evidences = get_evidences(reader, event)?;
         reference.key = extract_attribute(event, "key")?
             .map(|a| a.decode_and_unescape_value(reader))
             .ok_or(Error::MissingAttribute("key", "reference"))?
@@ -77,15 +133,44 @@ impl FromXml for Reference {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Reference {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("reference");
+        push_evidences(&mut elem, &self.evidences);
+        elem.push_attribute(("key", self.key.to_string().as_str()));
+        write_element(writer, elem, |w| {
+            self.citation.to_xml(w)?;
+            for scope in &self.scope {
+                write_text_element(w, BytesStart::new("scope"), scope)?;
+            }
+            if !self.sources.is_empty() {
+                write_element(w, BytesStart::new("source"), |w| {
+                    for source in &self.sources {
+                        source.to_xml(w)?;
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A single citation.
 pub struct Citation {
     // attributes
     /// Describe the type of this citation.
     pub ty: CitationType,
-    // date: Option<NaiveDate>,
+    /// Describes the publication date of the citation.
+    ///
+    /// UniProt mostly gives only the year, or the year and month, of
+    /// publication: missing components are set to the first month or day.
+    pub date: Option<Date>,
     /// Describes the name of an (online) journal or book.
     pub name: Option<ShortString>,
     /// Describes the volume of a journal or book.
@@ -120,6 +205,7 @@ impl Citation {
     pub fn new(ty: CitationType) -> Self {
         Self {
             ty,
+            date: None,
             name: None,
             volume: None,
             first: None,
@@ -135,13 +221,137 @@ impl Citation {
             db_references: Vec::new(),
         }
     }
+
+    /// Get the PubMed identifier of the citation, if any.
+    pub fn pubmed_id(&self) -> Option<&str> {
+        self.db_reference_id("PubMed")
+    }
+
+    /// Get the Digital Object Identifier of the citation, if any.
+    pub fn doi(&self) -> Option<&str> {
+        self.db_reference_id("DOI")
+    }
+
+    /// Format the citation as a bibliography entry.
+    ///
+    /// The layout depends on the citation type, and loosely follows the
+    /// `RL` lines of the UniProtKB flat files, e.g. `Smith J., Doe J.
+    /// A protein. J. Virol. 64:2073-2081(1990).` for a journal article.
+    /// Consortium authors are rendered with the consortium name, and the
+    /// fields missing from the citation are omitted.
+    pub fn format(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.authors.is_empty() {
+            let authors = self.authors.iter().map(Creator::name).collect::<Vec<_>>();
+            parts.push(authors.join(", "));
+        }
+        if let Some(title) = self.titles.first() {
+            parts.push(title.to_string());
+        }
+
+        let year = self.date.as_ref().map(|date| format!("({})", date.year()));
+        let pages = match (&self.first, &self.last) {
+            (Some(first), Some(last)) => Some(format!("{}-{}", first, last)),
+            (Some(first), None) => Some(first.to_string()),
+            (None, _) => None,
+        };
+
+        let source = match self.ty {
+            CitationType::JournalArticle | CitationType::OnlineJournalArticle => {
+                let mut source = self.name.as_deref().unwrap_or_default().to_string();
+                if let Some(volume) = &self.volume {
+                    source.push(' ');
+                    source.push_str(volume);
+                }
+                if let Some(pages) = pages {
+                    source.push(':');
+                    source.push_str(&pages);
+                }
+                source.push_str(year.as_deref().unwrap_or_default());
+                source
+            }
+            CitationType::Book => {
+                let mut fields = Vec::new();
+                if !self.editors.is_empty() {
+                    let editors = self.editors.iter().map(Creator::name).collect::<Vec<_>>();
+                    fields.push(format!("(In) {} (eds.)", editors.join(", ")));
+                }
+                let mut book = self.name.as_deref().unwrap_or_default().to_string();
+                if let Some(pages) = pages {
+                    book.push_str(&format!(", pp.{}", pages));
+                }
+                fields.push(book);
+                let place = [&self.publisher, &self.city]
+                    .iter()
+                    .filter_map(|field| field.as_deref())
+                    .collect::<Vec<_>>();
+                fields.push(format!("{} {}", place.join(", "), year.unwrap_or_default()));
+                fields.join("; ")
+            }
+            CitationType::Patent => {
+                let mut patent = String::from("Patent number");
+                if let Some(number) = &self.number {
+                    patent.push(' ');
+                    patent.push_str(number);
+                }
+                if let Some(date) = &self.date {
+                    patent.push_str(&format!(", {}", date));
+                }
+                patent
+            }
+            CitationType::Submission => {
+                let mut submission = format!("Submitted {}", year.unwrap_or_default());
+                if let Some(db) = &self.db {
+                    submission.push_str(&format!(" to the {} database", db));
+                }
+                submission
+            }
+            CitationType::Thesis => {
+                let mut thesis = format!("Thesis {}", year.unwrap_or_default());
+                let place = [&self.name, &self.city]
+                    .iter()
+                    .filter_map(|field| field.as_deref())
+                    .collect::<Vec<_>>();
+                if !place.is_empty() {
+                    thesis.push_str(&format!(", {}", place.join(", ")));
+                }
+                thesis
+            }
+            CitationType::UnpublishedObservations => {
+                format!("Unpublished observations {}", year.unwrap_or_default())
+            }
+        };
+        parts.push(source);
+
+        let mut formatted = String::new();
+        for part in parts.iter().map(|part| part.trim().trim_end_matches('.')) {
+            if part.is_empty() {
+                continue;
+            }
+            if !formatted.is_empty() {
+                formatted.push(' ');
+            }
+            formatted.push_str(part);
+            formatted.push('.');
+        }
+        formatted
+    }
+
+    fn db_reference_id(&self, ty: &str) -> Option<&str> {
+        self.db_references
+            .iter()
+            .find(|dbref| dbref.ty == ty)
+            .map(|dbref| dbref.id.as_ref())
+    }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Citation {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"citation");
 
@@ -154,23 +364,34 @@ impl FromXml for Citation {
         // create the citation
         let mut citation = Citation::new(ty);
 
-        // update attributes on citation (TODO)
-        // citation.date = attr.get(&b"date"[..])
-        //     .map(|v| v.decode_and_unescape_value(&mut self.xml))
-        //     .transpose()?;
-        citation.name = extract_attribute(event, "name")?
-            .map(|v| v.decode_and_unescape_value(reader))
-            .transpose()?
-            .map(ShortString::from);
-        citation.db = extract_attribute(event, "db")?
-            .map(|v| v.decode_and_unescape_value(reader))
-            .transpose()?
-            .map(ShortString::from);
+        // update attributes on citation
+        if let Some(attr) = extract_attribute(event, "date")? {
+            let value = attr.decode_and_unescape_value(reader)?;
+            let date = Date::from_partial(&value)
+                .map_err(|e| Error::InvalidValue("date", "citation", e))?;
+            citation.date = Some(date);
+        }
+        let attributes = [
+            ("name", &mut citation.name),
+            ("volume", &mut citation.volume),
+            ("first", &mut citation.first),
+            ("last", &mut citation.last),
+            ("publisher", &mut citation.publisher),
+            ("city", &mut citation.city),
+            ("db", &mut citation.db),
+            ("number", &mut citation.number),
+        ];
+        for (key, value) in attributes {
+            *value = extract_attribute(event, key)?
+                .map(|v| v.decode_and_unescape_value(reader))
+                .transpose()?
+                .map(ShortString::from);
+        }
 
         // update citation with children elements
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"authorList" => {
-                parse_inner!{e, reader, buffer,
+                parse_inner!{e, reader, buffer, options,
                     x @ b"person" => {
                         reader.read_to_end_into(x.name(), buffer)?;
                         let name = extract_attribute(&x, "name")?
@@ -190,14 +411,14 @@ impl FromXml for Citation {
                 }
             },
             e @ b"editorList" => {
-                parse_inner!{e, reader, buffer,
+                parse_inner!{e, reader, buffer, options,
                     x @ b"person" => {
                         reader.read_to_end_into(x.name(), buffer)?;
                         let name = extract_attribute(&x, "name")?
                             .ok_or(Error::MissingAttribute("name", "person"))?
                             .decode_and_unescape_value(reader)?
                             .into();
-                        citation.authors.push(Person(name));
+                        citation.editors.push(Person(name));
                     },
                     x @ b"consortium" => {
                         reader.read_to_end_into(x.name(), buffer)?;
@@ -205,7 +426,7 @@ impl FromXml for Citation {
                             .ok_or(Error::MissingAttribute("name", "consortium"))?
                             .decode_and_unescape_value(reader)?
                             .into();
-                        citation.authors.push(Consortium(name));
+                        citation.editors.push(Consortium(name));
                     }
                 }
             },
@@ -216,7 +437,7 @@ impl FromXml for Citation {
                 citation.locators.push(parse_text!(e, reader, buffer));
             },
             e @ b"dbReference" => {
-                citation.db_references.push(FromXml::from_xml(&e, reader, buffer)?);
+                citation.db_references.push(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
@@ -224,20 +445,197 @@ impl FromXml for Citation {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Citation {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("citation");
+        elem.push_attribute(("type", self.ty.as_str()));
+        if let Some(date) = &self.date {
+            elem.push_attribute(("date", date.to_string().as_str()));
+        }
+        let attributes = [
+            ("name", &self.name),
+            ("volume", &self.volume),
+            ("first", &self.first),
+            ("last", &self.last),
+            ("publisher", &self.publisher),
+            ("city", &self.city),
+            ("db", &self.db),
+            ("number", &self.number),
+        ];
+        for (key, value) in attributes.iter() {
+            if let Some(value) = value {
+                elem.push_attribute((*key, value.as_ref()));
+            }
+        }
+        write_element(writer, elem, |w| {
+            for title in &self.titles {
+                write_text_element(w, BytesStart::new("title"), title)?;
+            }
+            if !self.editors.is_empty() {
+                write_element(w, BytesStart::new("editorList"), |w| {
+                    for editor in &self.editors {
+                        editor.to_xml(w)?;
+                    }
+                    Ok(())
+                })?;
+            }
+            if !self.authors.is_empty() {
+                write_element(w, BytesStart::new("authorList"), |w| {
+                    for author in &self.authors {
+                        author.to_xml(w)?;
+                    }
+                    Ok(())
+                })?;
+            }
+            for locator in &self.locators {
+                write_text_element(w, BytesStart::new("locator"), locator)?;
+            }
+            for db_reference in &self.db_references {
+                db_reference.to_xml(w)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// A category of the scope of a reference.
+pub enum ReferenceScope {
+    /// The reference reports a nucleotide sequence.
+    NucleotideSequence,
+    /// The reference reports a protein sequence, e.g. from Edman degradation.
+    ProteinSequence,
+    /// The reference reannotates a previously published genome.
+    GenomeReannotation,
+    /// The reference revises a previously reported sequence.
+    SequenceRevision,
+    /// The reference describes a three-dimensional structure.
+    Structure,
+    /// The reference describes mutagenesis experiments.
+    Mutagenesis,
+    /// The reference describes interactions with other molecules.
+    Interaction,
+    /// The reference describes the function of the protein.
+    Function,
+    /// The reference describes a catalytic or enzyme activity.
+    CatalyticActivity,
+    /// The reference describes the subcellular location of the protein.
+    SubcellularLocation,
+    /// The reference describes the tissue specificity of the protein.
+    TissueSpecificity,
+    /// The reference describes the expression of the protein during development.
+    DevelopmentalStage,
+    /// The reference describes the induction of the protein.
+    Induction,
+    /// The reference describes the quaternary structure of the protein.
+    Subunit,
+    /// The reference describes post-translational modifications.
+    Ptm,
+    /// The reference identifies the protein by mass spectrometry.
+    MassSpectrometry,
+    /// The reference describes sequence variants.
+    Variants,
+    /// The reference describes the involvement of the protein in a disease.
+    Disease,
+    /// The reference describes the phenotype of a disruption of the gene.
+    DisruptionPhenotype,
+}
+
+impl ReferenceScope {
+    /// Classify a single scope of a reference.
+    ///
+    /// Bracketed qualifiers such as `[MRNA]` are ignored, and the scope is
+    /// classified using its leading phrase, so that both `"NUCLEOTIDE SEQUENCE
+    /// [LARGE SCALE GENOMIC DNA]"` and `"PROTEIN SEQUENCE OF 22-87"` are
+    /// recognized. Returns `None` for a scope that is not recognized.
+    pub fn from_scope(scope: &str) -> Option<Self> {
+        use self::ReferenceScope::*;
+        const PHRASES: &[(&str, ReferenceScope)] = &[
+            ("NUCLEOTIDE SEQUENCE", NucleotideSequence),
+            ("PARTIAL NUCLEOTIDE SEQUENCE", NucleotideSequence),
+            ("PROTEIN SEQUENCE", ProteinSequence),
+            ("PARTIAL PROTEIN SEQUENCE", ProteinSequence),
+            ("GENOME REANNOTATION", GenomeReannotation),
+            ("SEQUENCE REVISION", SequenceRevision),
+            ("X-RAY CRYSTALLOGRAPHY", Structure),
+            ("STRUCTURE BY NMR", Structure),
+            ("STRUCTURE BY ELECTRON MICROSCOPY", Structure),
+            ("3D-STRUCTURE MODELING", Structure),
+            ("MUTAGENESIS", Mutagenesis),
+            ("INTERACTION", Interaction),
+            ("FUNCTION", Function),
+            ("CATALYTIC ACTIVITY", CatalyticActivity),
+            ("ENZYME ACTIVITY", CatalyticActivity),
+            ("SUBCELLULAR LOCATION", SubcellularLocation),
+            ("TISSUE SPECIFICITY", TissueSpecificity),
+            ("DEVELOPMENTAL STAGE", DevelopmentalStage),
+            ("INDUCTION", Induction),
+            ("SUBUNIT", Subunit),
+            ("PTM", Ptm),
+            ("PHOSPHORYLATION", Ptm),
+            ("GLYCOSYLATION", Ptm),
+            ("UBIQUITINATION", Ptm),
+            ("ACETYLATION", Ptm),
+            ("MASS SPECTROMETRY", MassSpectrometry),
+            ("IDENTIFICATION BY MASS SPECTROMETRY", MassSpectrometry),
+            ("VARIANT", Variants),
+            ("VARIANTS", Variants),
+            ("INVOLVEMENT IN", Disease),
+            ("DISRUPTION PHENOTYPE", DisruptionPhenotype),
+        ];
+
+        // remove bracketed qualifiers, which may span the end of the scope
+        let phrase = scope.split('[').next().unwrap_or(scope).trim();
+        PHRASES
+            .iter()
+            .find(|(prefix, _)| {
+                phrase.starts_with(prefix)
+                    && !phrase[prefix.len()..].starts_with(|c: char| c.is_alphanumeric())
+            })
+            .map(|(_, category)| *category)
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The type of a citation.
 pub enum CitationType {
+    #[cfg_attr(feature = "serde", serde(rename = "book"))]
     Book,
+    #[cfg_attr(feature = "serde", serde(rename = "journal article"))]
     JournalArticle,
+    #[cfg_attr(feature = "serde", serde(rename = "online journal article"))]
     OnlineJournalArticle,
+    #[cfg_attr(feature = "serde", serde(rename = "patent"))]
     Patent,
+    #[cfg_attr(feature = "serde", serde(rename = "submission"))]
     Submission,
+    #[cfg_attr(feature = "serde", serde(rename = "thesis"))]
     Thesis,
+    #[cfg_attr(feature = "serde", serde(rename = "unpublished observations"))]
     UnpublishedObservations,
 }
 
+impl CitationType {
+    /// Get the string representation of the citation type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CitationType::Book => "book",
+            CitationType::JournalArticle => "journal article",
+            CitationType::OnlineJournalArticle => "online journal article",
+            CitationType::Patent => "patent",
+            CitationType::Submission => "submission",
+            CitationType::Thesis => "thesis",
+            CitationType::UnpublishedObservations => "unpublished observations",
+        }
+    }
+}
+
 impl FromStr for CitationType {
     type Err = crate::error::InvalidValue;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -250,14 +648,28 @@ impl FromStr for CitationType {
             "submission" => Ok(Submission),
             "thesis" => Ok(Thesis),
             "unpublished observations" => Ok(UnpublishedObservations),
-            other => Err(InvalidValue(std::string::String::from(other))),
+            other => Err(InvalidValue(String::from(other))),
         }
     }
 }
 
+impl TryFrom<&str> for CitationType {
+    type Error = InvalidValue;
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_str(s)
+    }
+}
+
+impl Display for CitationType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A single author in a citation.
 pub enum Creator {
     /// The author of a citation when these are represented by a consortium.
@@ -266,9 +678,32 @@ pub enum Creator {
     Person(ShortString),
 }
 
+impl Creator {
+    /// Get the name of the person or of the consortium.
+    pub fn name(&self) -> &str {
+        match self {
+            Creator::Consortium(name) | Creator::Person(name) => name,
+        }
+    }
+}
+
+#[cfg(feature = "parser")]
+impl ToXml for Creator {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let (tag, name) = match self {
+            Creator::Consortium(name) => ("consortium", name),
+            Creator::Person(name) => ("person", name),
+        };
+        let mut elem = BytesStart::new(tag);
+        elem.push_attribute(("name", name.as_ref()));
+        write_empty_element(writer, elem)
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The source of the protein sequence according to the citation.
 pub struct Source {
     pub value: ShortString,
@@ -290,18 +725,20 @@ impl Source {
     }
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Vec<Source> {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"source");
 
         use self::SourceType::*;
 
         let mut sources = Vec::new();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"strain" => {
                 let value = parse_text!(e, reader, buffer);
                 let evidences = get_evidences(reader, &e)?;
@@ -328,13 +765,176 @@ impl FromXml for Vec<Source> {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Source {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new(self.ty.as_str());
+        push_evidences(&mut elem, &self.evidences);
+        write_text_element(writer, elem, &self.value)
+    }
+}
+
 // ---------------------------------------------------------------------------
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The kind of sources where a sequence can originate from.
 pub enum SourceType {
+    #[cfg_attr(feature = "serde", serde(rename = "strain"))]
     Strain,
+    #[cfg_attr(feature = "serde", serde(rename = "plasmid"))]
     Plasmid,
+    #[cfg_attr(feature = "serde", serde(rename = "transposon"))]
     Transposon,
+    #[cfg_attr(feature = "serde", serde(rename = "tissue"))]
     Tissue,
 }
+
+impl SourceType {
+    /// Get the name of the XML element for the source type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceType::Strain => "strain",
+            SourceType::Plasmid => "plasmid",
+            SourceType::Transposon => "transposon",
+            SourceType::Tissue => "tissue",
+        }
+    }
+}
+
+impl FromStr for SourceType {
+    type Err = InvalidValue;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strain" => Ok(SourceType::Strain),
+            "plasmid" => Ok(SourceType::Plasmid),
+            "transposon" => Ok(SourceType::Transposon),
+            "tissue" => Ok(SourceType::Tissue),
+            other => Err(InvalidValue::from(other)),
+        }
+    }
+}
+
+impl Display for SourceType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn citation_type_round_trip() {
+        use self::CitationType::*;
+        let types = [
+            Book,
+            JournalArticle,
+            OnlineJournalArticle,
+            Patent,
+            Submission,
+            Thesis,
+            UnpublishedObservations,
+        ];
+        for ty in types {
+            assert_eq!(CitationType::from_str(&ty.to_string()), Ok(ty));
+        }
+    }
+
+    #[test]
+    fn reference_scope() {
+        use self::ReferenceScope::*;
+        let scopes = [
+            (
+                "NUCLEOTIDE SEQUENCE [MRNA] (ISOFORMS 1 AND 2)",
+                Some(NucleotideSequence),
+            ),
+            ("PROTEIN SEQUENCE OF 22-87", Some(ProteinSequence)),
+            ("PARTIAL PROTEIN SEQUENCE", Some(ProteinSequence)),
+            (
+                "X-RAY CRYSTALLOGRAPHY (2.95 ANGSTROMS) OF 2-146",
+                Some(Structure),
+            ),
+            ("MUTAGENESIS OF LYS-53 AND THR-214", Some(Mutagenesis)),
+            ("INTERACTION WITH ETO1 AND EOL1", Some(Interaction)),
+            (
+                "IDENTIFICATION BY MASS SPECTROMETRY [LARGE SCALE ANALYSIS]",
+                Some(MassSpectrometry),
+            ),
+            ("VARIANTS ARG-12 AND CYS-40", Some(Variants)),
+            ("FUNCTIONAL CHARACTERIZATION", None),
+            ("NOMENCLATURE", None),
+        ];
+        for (scope, category) in scopes {
+            assert_eq!(ReferenceScope::from_scope(scope), category, "{}", scope);
+        }
+
+        let mut reference = Reference::new(Citation::new(CitationType::JournalArticle), 1);
+        reference.scope = vec![
+            "NUCLEOTIDE SEQUENCE [GENOMIC DNA]".into(),
+            "FUNCTION".into(),
+            "NUCLEOTIDE SEQUENCE OF 50-81".into(),
+            "GENE FAMILY ORGANIZATION".into(),
+        ];
+        assert_eq!(
+            reference.scope_categories(),
+            vec![NucleotideSequence, Function]
+        );
+    }
+
+    #[test]
+    fn citation_identifiers() {
+        let mut citation = Citation::new(CitationType::OnlineJournalArticle);
+        assert_eq!(citation.pubmed_id(), None);
+        assert_eq!(citation.doi(), None);
+
+        citation.db_references.push(DbReference {
+            ty: "DOI".into(),
+            id: "10.1000/xyz123".into(),
+            ..Default::default()
+        });
+        assert_eq!(citation.pubmed_id(), None);
+        assert_eq!(citation.doi(), Some("10.1000/xyz123"));
+    }
+
+    #[test]
+    fn citation_format() {
+        let mut citation = Citation::new(CitationType::Submission);
+        citation.date = Some(Date::from_partial("2003-03").unwrap());
+        citation.db = Some("EMBL/GenBank/DDBJ".into());
+        citation.titles.push("A protein.".into());
+        citation
+            .authors
+            .push(Creator::Consortium("The ZGC project".into()));
+        citation.authors.push(Creator::Person("Doe J.".into()));
+        assert_eq!(
+            citation.format(),
+            "The ZGC project, Doe J. A protein. Submitted (2003) to the EMBL/GenBank/DDBJ database."
+        );
+
+        citation.ty = CitationType::Book;
+        citation.name = Some("Protein handbook".into());
+        citation.editors.push(Creator::Person("Smith J.".into()));
+        citation.publisher = Some("Academic Press".into());
+        citation.city = Some("London".into());
+        citation.first = Some("12".into());
+        citation.last = Some("34".into());
+        assert_eq!(
+            citation.format(),
+            "The ZGC project, Doe J. A protein. (In) Smith J. (eds.); Protein handbook, pp.12-34; Academic Press, London (2003)."
+        );
+
+        let citation = Citation::new(CitationType::UnpublishedObservations);
+        assert_eq!(citation.format(), "Unpublished observations.");
+    }
+
+    #[test]
+    fn source_type_round_trip() {
+        use self::SourceType::*;
+        for ty in [Strain, Plasmid, Transposon, Tissue] {
+            assert_eq!(SourceType::from_str(&ty.to_string()), Ok(ty));
+        }
+    }
+}
@@ -1,19 +1,43 @@
+use core::ops::RangeInclusive;
+use core::str::FromStr;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::str::FromStr;
+#[cfg(feature = "parser")]
+use std::io::Write;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
+use quick_xml::Writer;
 
+use crate::common::prelude::*;
+use crate::common::sequence::crc64;
+use crate::common::sequence::format_fasta;
+#[cfg(feature = "parser")]
+use crate::common::sequence::strip_whitespace;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
 use crate::error::InvalidValue;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
+use crate::parser::utils::write_text_element;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
+use crate::parser::ToXml;
 
 use super::Date;
 
 #[derive(Debug, Default, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The sequence of a protein.
 pub struct Sequence {
     pub value: ShortString,
@@ -26,19 +50,119 @@ pub struct Sequence {
     pub fragment: Option<FragmentType>,
 }
 
+impl Sequence {
+    /// Format the sequence as a FASTA record with the given header.
+    ///
+    /// The header is written after the leading `>`, and the sequence is
+    /// wrapped at 60 columns.
+    pub fn to_fasta(&self, header: &str) -> String {
+        format_fasta(header, &self.value)
+    }
+
+    /// Compute the CRC64 checksum of the sequence.
+    pub fn crc64(&self) -> u64 {
+        crc64(&self.value)
+    }
+
+    /// Check that the stored checksum matches the sequence.
+    pub fn verify_checksum(&self) -> bool {
+        self.crc64() == self.checksum
+    }
+
+    /// Compute the average molecular mass of the sequence, in Daltons.
+    ///
+    /// The mass is the sum of the average masses of the residues, plus one
+    /// water molecule for the termini. The ambiguous `B` and `Z` codes use
+    /// the mean mass of the two residues they stand for, `J` the mass of
+    /// leucine, and `X` the mean residue mass weighted by the amino acid
+    /// frequencies of UniProtKB/Swiss-Prot. Any other character is ignored.
+    /// An empty sequence has a mass of zero.
+    pub fn computed_mass(&self) -> f64 {
+        if self.value.is_empty() {
+            return 0.0;
+        }
+        self.value
+            .bytes()
+            .filter_map(residue_mass)
+            .fold(WATER_MASS, |mass, residue| mass + residue)
+    }
+
+    /// Get the residues of the sequence within the given 1-based inclusive range.
+    ///
+    /// Returns `None` if the range is empty or out of the bounds of the
+    /// sequence, e.g. `subseq(1..=3)` returns the first three residues.
+    pub fn subseq(&self, range: RangeInclusive<usize>) -> Option<&str> {
+        let (start, end) = range.into_inner();
+        if start == 0 || start > end {
+            return None;
+        }
+        self.value.get(start - 1..end)
+    }
+
+    /// Check whether the sequence is a fragment of the complete protein.
+    pub fn is_fragment(&self) -> bool {
+        self.fragment.is_some()
+    }
+
+    /// Check whether the sequence is complete, i.e. not a fragment.
+    ///
+    /// Sequences without a `fragment` attribute are complete.
+    pub fn is_complete(&self) -> bool {
+        !self.is_fragment()
+    }
+}
+
+/// The average mass of a water molecule, in Daltons.
+const WATER_MASS: f64 = 18.01524;
+
+/// Get the average mass of a residue, in Daltons.
+fn residue_mass(residue: u8) -> Option<f64> {
+    match residue.to_ascii_uppercase() {
+        b'A' => Some(71.0788),
+        b'R' => Some(156.1875),
+        b'N' => Some(114.1038),
+        b'D' => Some(115.0886),
+        b'C' => Some(103.1388),
+        b'E' => Some(129.1155),
+        b'Q' => Some(128.1307),
+        b'G' => Some(57.0519),
+        b'H' => Some(137.1411),
+        b'I' => Some(113.1594),
+        b'L' => Some(113.1594),
+        b'K' => Some(128.1741),
+        b'M' => Some(131.1926),
+        b'F' => Some(147.1766),
+        b'P' => Some(97.1167),
+        b'S' => Some(87.0782),
+        b'T' => Some(101.1051),
+        b'W' => Some(186.2132),
+        b'Y' => Some(163.1760),
+        b'V' => Some(99.1326),
+        b'U' => Some(150.0388),
+        b'O' => Some(237.3018),
+        b'B' => Some(114.5962),
+        b'Z' => Some(128.6231),
+        b'J' => Some(113.1594),
+        b'X' => Some(111.0922),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Sequence {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        _options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"sequence");
 
-        let length = decode_attribute(event, reader, "length", "sequence")?;
+        // the length is the number of residues, but the attribute is required
+        decode_attribute::<_, usize>(event, reader, "length", "sequence")?;
         let mass = decode_attribute(event, reader, "mass", "sequence")?;
         let version = decode_attribute(event, reader, "version", "sequence")?;
         let modified = decode_attribute(event, reader, "modified", "sequence")?;
-        // let modified = TODO
         let precursor = extract_attribute(event, "precursor")?
             .map(|x| x.decode_and_unescape_value(reader))
             .transpose()?
@@ -55,10 +179,10 @@ impl FromXml for Sequence {
             Err(other) => return Err(other),
         };
 
-        let value = parse_text!(event, reader, buffer);
+        let value = strip_whitespace(parse_text!(event, reader, buffer));
         Ok(Sequence {
+            length: value.len(),
             value,
-            length,
             mass,
             checksum,
             modified,
@@ -69,18 +193,45 @@ impl FromXml for Sequence {
     }
 }
 
+#[cfg(feature = "parser")]
+impl ToXml for Sequence {
+    fn to_xml<W: Write>(&self, writer: &mut Writer<W>) -> Result<(), Error> {
+        let mut elem = BytesStart::new("sequence");
+        elem.push_attribute(("length", self.length.to_string().as_str()));
+        elem.push_attribute(("mass", self.mass.to_string().as_str()));
+        elem.push_attribute(("checksum", format!("{:016X}", self.checksum).as_str()));
+        elem.push_attribute(("modified", self.modified.to_string().as_str()));
+        elem.push_attribute(("version", self.version.to_string().as_str()));
+        if let Some(precursor) = self.precursor {
+            elem.push_attribute(("precursor", precursor.to_string().as_str()));
+        }
+        if let Some(fragment) = self.fragment {
+            elem.push_attribute(("fragment", fragment.as_str()));
+        }
+        write_text_element(writer, elem, &self.value)
+    }
+}
+
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// A marker indicating whether a protein sequence is fragmented.
 pub enum FragmentType {
+    #[default]
+    #[cfg_attr(feature = "serde", serde(rename = "single"))]
     Single,
+    #[cfg_attr(feature = "serde", serde(rename = "multiple"))]
     Multiple,
 }
 
-impl Default for FragmentType {
-    fn default() -> Self {
-        FragmentType::Single
+impl FragmentType {
+    /// Get the string representation of the fragment type.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FragmentType::Single => "single",
+            FragmentType::Multiple => "multiple",
+        }
     }
 }
 
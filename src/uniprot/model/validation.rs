@@ -0,0 +1,31 @@
+use core::fmt::Display;
+use core::fmt::Formatter;
+use core::fmt::Result as FmtResult;
+
+use crate::common::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// A schema violation found in an entry that was otherwise parsed.
+///
+/// See [`Entry::validate`] for the constraints being checked.
+///
+/// [`Entry::validate`]: ./struct.Entry.html#method.validate
+pub enum ValidationWarning {
+    /// A required element is missing.
+    MissingElement(&'static str, &'static str),
+    /// An element which should have some content is empty.
+    EmptyElement(&'static str, &'static str),
+    /// An attribute has a value outside of its allowed range.
+    InvalidValue(&'static str, &'static str),
+}
+
+impl Display for ValidationWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        use self::ValidationWarning::*;
+        match self {
+            MissingElement(x, y) => write!(f, "missing element `{}` in `{}`", x, y),
+            EmptyElement(x, y) => write!(f, "empty element `{}` in `{}`", x, y),
+            InvalidValue(x, y) => write!(f, "invalid value for attribute `{}` in `{}`", x, y),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Lightweight views over the raw XML of UniProt entries.
+
+use std::borrow::Cow;
+use std::io::BufRead;
+
+use quick_xml::events::Event;
+use quick_xml::Error as XmlError;
+use quick_xml::Reader;
+
+use crate::error::Error;
+use crate::parser::utils::entry_end_tag;
+
+/// The field of an entry being currently read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Accession,
+    Name,
+    Sequence,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A view over the main fields of a UniProt entry, borrowed from its XML.
+///
+/// The values are borrowed from the buffer of the [`RefParser`] that
+/// produced the view, unless they contain XML escapes in which case they
+/// are unescaped into an owned string.
+///
+/// [`RefParser`]: ./struct.RefParser.html
+pub struct EntryRef<'a> {
+    /// The primary accession of the entry.
+    pub accession: Cow<'a, str>,
+    /// The first name of the entry.
+    pub name: Cow<'a, str>,
+    /// The protein sequence of the entry, without whitespace.
+    pub sequence: Cow<'a, str>,
+}
+
+impl<'a> EntryRef<'a> {
+    /// Extract the view from the complete text of an `entry` element.
+    ///
+    /// Errors are returned along with their byte position in the text.
+    fn from_slice(text: &'a [u8]) -> Result<Self, (usize, Error)> {
+        let mut xml = Reader::from_reader(text);
+        xml.expand_empty_elements(true);
+        Self::read(&mut xml).map_err(|e| (xml.buffer_position(), e))
+    }
+
+    /// Extract the view from the events of an `entry` element.
+    fn read(xml: &mut Reader<&'a [u8]>) -> Result<Self, Error> {
+        let mut accession = None;
+        let mut name = None;
+        let mut sequence = None;
+        let mut field = None;
+        let mut depth = 0usize;
+
+        loop {
+            match xml.read_event()? {
+                Event::Start(e) => {
+                    depth += 1;
+                    field = match e.local_name().as_ref() {
+                        _ if depth != 2 => None,
+                        b"accession" if accession.is_none() => Some(Field::Accession),
+                        b"name" if name.is_none() => Some(Field::Name),
+                        b"sequence" => Some(Field::Sequence),
+                        _ => None,
+                    };
+                }
+                Event::Text(t) => match field.take() {
+                    Some(Field::Accession) => accession = Some(t.unescape()?),
+                    Some(Field::Name) => name = Some(t.unescape()?),
+                    Some(Field::Sequence) => sequence = Some(strip_whitespace(t.unescape()?)),
+                    None => (),
+                },
+                Event::End(_) => {
+                    field = None;
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                Event::Eof => {
+                    let e = String::from("entry");
+                    return Err(Error::from(XmlError::UnexpectedEof(e)));
+                }
+                _ => (),
+            }
+        }
+
+        Ok(EntryRef {
+            accession: accession.ok_or(Error::MissingElement("accession", "entry"))?,
+            name: name.ok_or(Error::MissingElement("name", "entry"))?,
+            sequence: sequence.ok_or(Error::MissingElement("sequence", "entry"))?,
+        })
+    }
+}
+
+/// Remove the ASCII whitespace from a sequence, copying it only if needed.
+///
+/// Sequences may be wrapped or indented, like in [`Sequence::value`], but
+/// are usually written on a single line and can be borrowed unchanged.
+///
+/// [`Sequence::value`]: ./model/struct.Sequence.html#structfield.value
+fn strip_whitespace(text: Cow<'_, str>) -> Cow<'_, str> {
+    if text.bytes().any(|b| b.is_ascii_whitespace()) {
+        Cow::Owned(text.chars().filter(|c| !c.is_ascii_whitespace()).collect())
+    } else {
+        text
+    }
+}
+
+/// A parser yielding [`EntryRef`] views over the entries of a UniProt file.
+///
+/// Since each view borrows from the internal buffer of the parser, which
+/// is reused for every entry, the parser cannot implement `Iterator`: use
+/// [`RefParser::next_entry`] in a `while let` loop instead. Only the
+/// accession, name and sequence of each entry are extracted, which avoids
+/// most of the allocations performed when building an owned [`Entry`].
+///
+/// Entries are read and extracted in the calling thread, even with the
+/// `threading` feature: the views cannot outlive the buffer of the parser,
+/// so they are not sent through the worker threads of a [`ThreadedParser`].
+///
+/// [`EntryRef`]: ./struct.EntryRef.html
+/// [`Entry`]: ./struct.Entry.html
+/// [`RefParser::next_entry`]: #method.next_entry
+/// [`ThreadedParser`]: ../parser/struct.ThreadedParser.html
+pub struct RefParser<B: BufRead> {
+    reader: B,
+    buffer: Vec<u8>,
+    finished: bool,
+    position: u64,
+    error_position: Option<u64>,
+}
+
+impl<B: BufRead> RefParser<B> {
+    /// Create a new `RefParser` wrapping the given reader.
+    pub fn new(reader: B) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            finished: false,
+            position: 0,
+            error_position: None,
+        }
+    }
+
+    /// Get the byte position of the error returned by the last call to `next_entry`.
+    ///
+    /// The position is counted from the beginning of the reader given to
+    /// the parser. Returns `None` if the last item was not an error.
+    pub fn error_position(&self) -> Option<u64> {
+        self.error_position
+    }
+
+    /// Get a view over the next entry, or `None` if there are no more entries.
+    pub fn next_entry(&mut self) -> Option<Result<EntryRef<'_>, Error>> {
+        self.error_position = None;
+        if self.finished {
+            return None;
+        }
+        match self.read_entry() {
+            Ok(true) => {
+                let start = self.position - self.buffer.len() as u64;
+                match EntryRef::from_slice(&self.buffer) {
+                    Ok(entry) => Some(Ok(entry)),
+                    Err((offset, e)) => {
+                        self.error_position = Some(start + offset as u64);
+                        Some(Err(e.at(start + offset as u64)))
+                    }
+                }
+            }
+            Ok(false) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                self.error_position = Some(self.position);
+                Some(Err(e.at(self.position)))
+            }
+        }
+    }
+
+    /// Read the text of the next `entry` element into the buffer.
+    ///
+    /// Returns `Ok(false)` if the reader reached EOF before another entry.
+    fn read_entry(&mut self) -> Result<bool, Error> {
+        // skip everything until the beginning of an entry
+        let end_tag = loop {
+            self.buffer.clear();
+            let n = self.reader.read_until(b'>', &mut self.buffer)?;
+            if n == 0 {
+                return Ok(false);
+            }
+            self.position += n as u64;
+            if let Some(i) = memchr::memrchr(b'<', &self.buffer) {
+                if let Some(end) = entry_end_tag(&self.buffer[i..]) {
+                    self.buffer.drain(..i);
+                    break end;
+                }
+            }
+        };
+
+        // read until the end of the entry
+        while !self.buffer.ends_with(&end_tag) {
+            let n = self.reader.read_until(b'>', &mut self.buffer)?;
+            if n == 0 {
+                let e = String::from("entry");
+                return Err(Error::from(XmlError::UnexpectedEof(e)));
+            }
+            self.position += n as u64;
+        }
+
+        Ok(true)
+    }
+}
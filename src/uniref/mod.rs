@@ -1,20 +1,30 @@
 //! Data types for the UniRef databases.
 
+#[cfg(feature = "parser")]
 use std::io::BufRead;
 
+#[cfg(feature = "parser")]
+use crate::error::Error;
+
 mod model;
 
 #[doc(inline)]
 pub use self::model::*;
 
 /// The sequential parser type for UniRef entries.
+#[cfg(feature = "parser")]
 pub type SequentialParser<B> = super::parser::SequentialParser<B, UniRef>;
 
 #[cfg(feature = "threading")]
 /// The threaded parser type for UniRef entries.
 pub type ThreadedParser<B> = super::parser::ThreadedParser<B, UniRef>;
 
+#[cfg(feature = "async")]
+/// The asynchronous parser type for UniRef entries.
+pub type AsyncParser<B> = super::parser::AsyncParser<B, UniRef>;
+
 /// The parser type for UniRef entries.
+#[cfg(feature = "parser")]
 pub type Parser<B> = super::parser::Parser<B, UniRef>;
 
 /// Parse a UniRef database XML file.
@@ -48,16 +58,44 @@ pub type Parser<B> = super::parser::Parser<B, UniRef>;
 ///
 /// println!("{:?}", entry);
 /// ```
+#[cfg(feature = "parser")]
 pub fn parse<B: BufRead + Send + 'static>(reader: B) -> Parser<B> {
     Parser::new(reader)
 }
 
+/// Parse a UniRef database XML file from an asynchronous reader.
+///
+/// The returned stream yields the clusters of a UniRef100, UniRef90 or
+/// UniRef50 file, whose root element must be one of `UniRef`, `UniRef100`,
+/// `UniRef90` or `UniRef50`. Since UniRef clusters can have thousands of
+/// members, each `entry` element is buffered entirely before it is parsed.
+///
+/// # Example
+/// ```rust
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// use futures_util::StreamExt;
+///
+/// let f = tokio::fs::File::open("tests/uniref50.xml").await.unwrap();
+/// let stream = uniprot::uniref::parse_async(tokio::io::BufReader::new(f));
+/// let entries = stream.collect::<Vec<_>>().await;
+/// # }
+/// ```
+#[cfg(feature = "async")]
+pub fn parse_async<B>(reader: B) -> AsyncParser<B>
+where
+    B: tokio::io::AsyncBufRead + Unpin,
+{
+    AsyncParser::new(reader)
+}
+
 /// Parse a single UniRef entry.
+#[cfg(feature = "parser")]
 pub fn parse_entry<B: BufRead>(reader: B) -> <SequentialParser<B> as Iterator>::Item {
     SequentialParser::parse_entry(reader)
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "parser"))]
 mod tests {
 
     use super::*;
@@ -73,6 +111,127 @@ mod tests {
         assert_eq!(entries.len(), 59);
     }
 
+    #[test]
+    fn sequence_whitespace() {
+        let f = std::fs::File::open("tests/uniref50.xml").unwrap();
+        let entries = SequentialParser::new(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+        for entry in &entries {
+            let sequence = entry.representative_member.sequence.as_ref().unwrap();
+            assert_eq!(sequence.sequence.len(), sequence.length);
+            assert!(!sequence.sequence.contains(char::is_whitespace));
+        }
+    }
+
+    #[test]
+    fn member_properties() {
+        let f = std::fs::File::open("tests/uniref50.xml").unwrap();
+        let entry = super::parse(std::io::BufReader::new(f))
+            .next()
+            .expect("an entry should be parsed")
+            .expect("the entry should be parsed successfully");
+
+        let member = &entry.representative_member;
+        assert_eq!(member.accession(), Some("A0A5A9P0L4"));
+        assert_eq!(member.uniparc_id(), Some("UPI0012572FB4"));
+        assert_eq!(member.protein_name(), Some("Peptidylprolyl isomerase"));
+        assert_eq!(member.taxon_id(), Some(1572043));
+        assert_eq!(member.identity(), None);
+
+        let mut member = member.clone();
+        member
+            .db_reference
+            .properties
+            .push(Property::new("member identity".into(), "0.95".into()));
+        assert_eq!(member.identity(), Some(0.95));
+    }
+
+    #[test]
+    fn entry_properties() {
+        let f = std::fs::File::open("tests/uniref50.xml").unwrap();
+        let entries = super::parse(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+
+        let entry = &entries[0];
+        assert_eq!(entry.common_taxon(), Some("Triplophysa tibetana"));
+        assert_eq!(entry.common_taxon_id(), Some(1572043));
+        for entry in &entries {
+            let listed = entry.members.len() + 1;
+            if entry.id == "UniRef50_UPI001476BEC4" {
+                // one of the members of this cluster is not listed in the file
+                assert_eq!(entry.member_count(), Some(listed + 1));
+            } else {
+                assert_eq!(entry.member_count(), Some(listed));
+            }
+        }
+
+        let mut entry = entry.clone();
+        entry
+            .properties
+            .retain(|p| !p.ty.starts_with("common taxon"));
+        assert_eq!(entry.common_taxon(), None);
+        assert_eq!(entry.common_taxon_id(), None);
+    }
+
+    #[test]
+    fn member_sequences() {
+        let f = std::fs::File::open("tests/uniref50.xml").unwrap();
+        let entries = super::parse(std::io::BufReader::new(f))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("entries should parse successfully");
+
+        let representative = &entries[0].representative_member;
+        let sequence = representative
+            .sequence
+            .as_ref()
+            .expect("representative should have a sequence");
+        assert_eq!(sequence.length, 45354);
+        assert_eq!(sequence.checksum, 0x7BCEECC7BB60029B);
+        assert_eq!(representative.sequence_length(), Some(45354));
+
+        let member = entries
+            .iter()
+            .flat_map(|entry| entry.members.iter())
+            .next()
+            .expect("a cluster member should be found");
+        assert!(member.sequence.is_none());
+        assert_eq!(member.sequence_length(), None);
+    }
+
+    #[test]
+    fn metadata() {
+        let f = std::fs::File::open("tests/uniref50.xml").unwrap();
+        let parser = SequentialParser::new(std::io::BufReader::new(f));
+        let metadata = parser.metadata().expect("metadata should be found");
+        assert_eq!(metadata.version.as_deref(), Some("2021_03"));
+        assert_eq!(metadata.release_date.as_deref(), Some("2021-06-02"));
+        assert_eq!(metadata.copyright, None);
+    }
+
+    #[test]
+    fn fail_invalid_updated() {
+        let txt =
+            &b"<UniRef><entry id=\"UniRef50_A0A5A9P0L4\" updated=\"2019-12-18Z\"></entry></UniRef>"
+                [..];
+        let err = super::parse_entry(std::io::Cursor::new(txt)).unwrap_err();
+        match err.into_inner() {
+            Error::InvalidValue("updated", "entry", v) => assert_eq!(v.0, "2019-12-18Z"),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn fail_empty_updated() {
+        let txt = &b"<UniRef><entry id=\"UniRef50_A0A5A9P0L4\" updated=\"\"></entry></UniRef>"[..];
+        let err = super::parse_entry(std::io::Cursor::new(txt)).unwrap_err();
+        match err.into_inner() {
+            Error::InvalidValue("updated", "entry", v) => assert_eq!(v.0, ""),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
     mod sequential {
         use super::*;
 
@@ -93,7 +252,7 @@ mod tests {
                 .expect("should raise an error")
                 .unwrap_err();
 
-            match err {
+            match err.into_inner() {
                 Error::Xml(XmlError::UnexpectedEof(_)) => (),
                 other => panic!("unexpected error: {:?}", other),
             }
@@ -106,7 +265,7 @@ mod tests {
                 .next()
                 .expect("should raise an error")
                 .unwrap_err();
-            match err {
+            match err.into_inner() {
                 Error::UnexpectedRoot(r) => assert_eq!(r, "something"),
                 other => panic!("unexpected error: {:?}", other),
             }
@@ -134,7 +293,7 @@ mod tests {
                 .expect("should not yield `None`")
                 .unwrap_err();
 
-            match err {
+            match err.into_inner() {
                 Error::Xml(XmlError::UnexpectedEof(_)) => (),
                 other => panic!("unexpected error: {:?}", other),
             }
@@ -147,7 +306,7 @@ mod tests {
                 .next()
                 .expect("should raise an error")
                 .unwrap_err();
-            match err {
+            match err.into_inner() {
                 Error::UnexpectedRoot(r) => assert_eq!(r, "something"),
                 other => panic!("unexpected error: {:?}", other),
             }
@@ -1,11 +1,19 @@
+#[cfg(feature = "parser")]
 use std::io::BufRead;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
 
 use super::Reference;
 use super::Sequence;
@@ -17,11 +25,61 @@ pub struct Member {
     pub db_reference: Reference,
 }
 
+impl Member {
+    /// Get the value of the first property of the given type, if any.
+    fn property(&self, ty: &str) -> Option<&str> {
+        self.db_reference
+            .properties
+            .iter()
+            .find(|p| p.ty == ty)
+            .map(|p| p.value.as_ref())
+    }
+
+    /// Get the UniProtKB accession of the member, if any.
+    ///
+    /// Members that only reference a UniParc sequence have no accession.
+    pub fn accession(&self) -> Option<&str> {
+        self.property("UniProtKB accession")
+    }
+
+    /// Get the UniParc identifier of the member, if any.
+    pub fn uniparc_id(&self) -> Option<&str> {
+        self.property("UniParc ID")
+    }
+
+    /// Get the name of the protein of the member, if any.
+    pub fn protein_name(&self) -> Option<&str> {
+        self.property("protein name")
+    }
+
+    /// Get the NCBI Taxonomy identifier of the member organism, if any.
+    pub fn taxon_id(&self) -> Option<u32> {
+        self.property("NCBI taxonomy").and_then(|x| x.parse().ok())
+    }
+
+    /// Get the sequence identity of the member to the representative, if any.
+    ///
+    /// The representative member of a cluster usually has no identity.
+    pub fn identity(&self) -> Option<f64> {
+        self.property("member identity")
+            .and_then(|x| x.parse().ok())
+    }
+
+    /// Get the length of the member sequence, if any.
+    ///
+    /// Only the representative member of a cluster usually has a sequence.
+    pub fn sequence_length(&self) -> Option<usize> {
+        self.sequence.as_ref().map(|s| s.length)
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Member {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert!(
             event.local_name().as_ref() == b"member"
@@ -31,12 +89,12 @@ impl FromXml for Member {
         let mut sequence = None;
         let mut dbref = None;
 
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"sequence" => {
-                sequence = Some(FromXml::from_xml(&e, reader, buffer)?);
+                sequence = Some(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"dbReference" => {
-                dbref = Some(FromXml::from_xml(&e, reader, buffer)?);
+                dbref = Some(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
@@ -11,21 +11,34 @@ mod reference;
 pub use self::member::Member;
 pub use self::reference::Reference;
 pub use crate::common::date::Date;
+pub use crate::common::date::DatePrecision;
 pub use crate::common::property::Property;
 pub use crate::common::sequence::Sequence;
 
-use std::ops::Deref;
-use std::ops::DerefMut;
+use core::ops::Deref;
+use core::ops::DerefMut;
 
+use core::iter::FromIterator;
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
+#[cfg(feature = "parser")]
 use std::io::BufRead;
-use std::iter::FromIterator;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
+use crate::parser::utils::extract_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
+#[cfg(feature = "parser")]
 use crate::parser::UniprotDatabase;
 
 // ---------------------------------------------------------------------------
@@ -34,6 +47,7 @@ use crate::parser::UniprotDatabase;
 #[derive(Debug, Clone)]
 pub struct Entry {
     pub id: ShortString,
+    /// The date the cluster was last updated, in `YYYY-MM-DD` format.
     pub updated: Date,
     pub name: ShortString,
     pub properties: Vec<Property>,
@@ -41,39 +55,76 @@ pub struct Entry {
     pub members: Vec<Member>,
 }
 
+impl Entry {
+    /// Get the value of the first property of the given type, if any.
+    fn property(&self, ty: &str) -> Option<&str> {
+        self.properties
+            .iter()
+            .find(|p| p.ty == ty)
+            .map(|p| p.value.as_ref())
+    }
+
+    /// Get the name of the common taxon of the cluster members, if any.
+    ///
+    /// Older UniRef releases do not report the common taxon.
+    pub fn common_taxon(&self) -> Option<&str> {
+        self.property("common taxon")
+    }
+
+    /// Get the NCBI Taxonomy identifier of the common taxon, if any.
+    pub fn common_taxon_id(&self) -> Option<u32> {
+        self.property("common taxon ID")
+            .and_then(|x| x.parse().ok())
+    }
+
+    /// Get the number of members of the cluster, if any.
+    ///
+    /// The count includes the representative member, and may be larger
+    /// than the number of members listed in the entry.
+    pub fn member_count(&self) -> Option<usize> {
+        self.property("member count").and_then(|x| x.parse().ok())
+    }
+}
+
+#[cfg(feature = "parser")]
 impl FromXml for Entry {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"entry");
 
         // parse attributes
         let id = decode_attribute(event, reader, "id", "reference")?;
-        let updated = decode_attribute(event, reader, "updated", "entry")?;
+        let updated = extract_attribute(event, "updated")?
+            .ok_or(Error::MissingAttribute("updated", "entry"))?
+            .decode_and_unescape_value(reader)
+            .map(|s| Date::from_iso8601(&s))?
+            .map_err(|e| Error::InvalidValue("updated", "entry", e))?;
 
         // parse fields
         let mut name = None;
         let mut representative_member = None;
         let mut properties = Vec::new();
         let mut members = Vec::new();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"name" => {
                 if name.replace(parse_text!(e, reader, buffer)).is_some() {
                     return Err(Error::DuplicateElement("name", "entry"));
                 }
             },
             e @ b"representativeMember" => {
-                if representative_member.replace(FromXml::from_xml(&e, reader, buffer)?).is_some() {
+                if representative_member.replace(FromXml::from_xml(&e, reader, buffer, options)?).is_some() {
                     return Err(Error::DuplicateElement("representativemember", "entry"));
                 }
             },
             e @ b"member" => {
-                members.push(FromXml::from_xml(&e, reader, buffer)?);
+                members.push(FromXml::from_xml(&e, reader, buffer, options)?);
             },
             e @ b"property" => {
-                properties.push(FromXml::from_xml(&e, reader, buffer)?);
+                properties.push(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
@@ -135,6 +186,16 @@ impl From<UniRef> for Vec<Entry> {
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<'a> rayon::iter::IntoParallelIterator for &'a UniRef {
+    type Iter = rayon::slice::Iter<'a, Entry>;
+    type Item = &'a Entry;
+    fn into_par_iter(self) -> Self::Iter {
+        rayon::iter::IntoParallelIterator::into_par_iter(&self.entries)
+    }
+}
+
+#[cfg(feature = "parser")]
 impl UniprotDatabase for UniRef {
     type Entry = Entry;
     const ROOTS: &'static [&'static [u8]] = &[b"UniRef", b"UniRef50", b"UniRef90", b"UniRef100"];
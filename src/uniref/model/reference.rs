@@ -1,12 +1,21 @@
+#[cfg(feature = "parser")]
 use std::io::BufRead;
 
+#[cfg(feature = "parser")]
 use quick_xml::events::BytesStart;
+#[cfg(feature = "parser")]
 use quick_xml::Reader;
 
+use crate::common::prelude::*;
 use crate::common::ShortString;
+#[cfg(feature = "parser")]
 use crate::error::Error;
+#[cfg(feature = "parser")]
 use crate::parser::utils::decode_attribute;
+#[cfg(feature = "parser")]
 use crate::parser::FromXml;
+#[cfg(feature = "parser")]
+use crate::parser::ParseOptions;
 
 use super::Property;
 
@@ -18,11 +27,13 @@ pub struct Reference {
     pub properties: Vec<Property>,
 }
 
+#[cfg(feature = "parser")]
 impl FromXml for Reference {
     fn from_xml<B: BufRead>(
         event: &BytesStart,
         reader: &mut Reader<B>,
         buffer: &mut Vec<u8>,
+        options: &ParseOptions,
     ) -> Result<Self, Error> {
         debug_assert_eq!(event.local_name().as_ref(), b"dbReference");
 
@@ -31,9 +42,9 @@ impl FromXml for Reference {
         let ty = decode_attribute(event, reader, "type", "reference")?;
 
         let mut properties = Vec::new();
-        parse_inner! {event, reader, buffer,
+        parse_inner! {event, reader, buffer, options,
             e @ b"property" => {
-                properties.push(FromXml::from_xml(&e, reader, buffer)?);
+                properties.push(FromXml::from_xml(&e, reader, buffer, options)?);
             }
         }
 
@@ -0,0 +1,42 @@
+//! Check that the data model of `uniprot` can be used in `no_std` crates.
+
+#![no_std]
+
+extern crate alloc;
+extern crate uniprot;
+
+use alloc::vec;
+
+use uniprot::uniprot::Dataset;
+use uniprot::uniprot::Entry;
+use uniprot::uniprot::Sequence;
+
+/// Build a minimal UniProtKB entry without any parser.
+pub fn entry() -> Entry {
+    let mut entry = Entry::new(Dataset::SwissProt);
+    entry.accessions = vec!["P69905".into(), "P01922".into()];
+    entry.names = vec!["HBA_HUMAN".into()];
+    entry.sequence = Some(Sequence {
+        value: "MVLSPADKTNVKAAWGKVGAHAGEYGAEALERMFLSFPTTKTYFPHF".into(),
+        length: 47,
+        ..Default::default()
+    });
+    entry
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn fields() {
+        let entry = entry();
+        assert_eq!(entry.primary_accession(), Some("P69905"));
+        assert_eq!(entry.names[0].as_str(), "HBA_HUMAN");
+        assert_eq!(entry.version, 1);
+        assert!(entry.recommended_name().is_none());
+        let sequence = entry.sequence.as_ref().unwrap();
+        assert_eq!(sequence.value.len(), sequence.length);
+    }
+}